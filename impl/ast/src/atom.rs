@@ -1,5 +1,5 @@
 /// Imports
-use common::token::Span;
+use common::{symbol::Symbol, token::Span};
 
 /// Represents item publicity
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -14,14 +14,14 @@ pub enum TypeHint {
     /// Local type
     Local {
         span: Span,
-        name: String,
+        name: Symbol,
         args: Vec<TypeHint>,
     },
     /// Module type
     Module {
         span: Span,
-        module: String,
-        name: String,
+        module: Symbol,
+        name: Symbol,
         args: Vec<TypeHint>,
     },
     /// Function type
@@ -36,10 +36,24 @@ pub enum TypeHint {
     Infer,
 }
 
+/// Implementation
+impl TypeHint {
+    /// Returns span of the type hint, if it is presented
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            TypeHint::Local { span, .. }
+            | TypeHint::Module { span, .. }
+            | TypeHint::Function { span, .. }
+            | TypeHint::Unit(span) => Some(span.clone()),
+            TypeHint::Infer => None,
+        }
+    }
+}
+
 /// Function param
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Param {
     pub span: Span,
-    pub name: String,
+    pub name: Symbol,
     pub hint: TypeHint,
 }
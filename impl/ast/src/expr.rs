@@ -1,6 +1,6 @@
 /// Imports
-use crate::{stmt::Block};
-use common::token::Span;
+use crate::stmt::Block;
+use common::{id::HirId, symbol::Symbol, token::Span};
 
 /// Literal
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -80,6 +80,16 @@ pub enum BinOp {
     Lt,
 }
 
+/// Part of the interpolated string
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InterpPart {
+    /// Literal text
+    Lit(String),
+
+    /// Interpolated value (e.g `{name}`)
+    Expr(Expr),
+}
+
 /// Expression kind
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ExprKind {
@@ -99,24 +109,28 @@ pub enum ExprKind {
     Call(Box<Expr>, Vec<Expr>),
 
     /// Id expr (e.g, `foo`)
-    Id(String),
+    Id(Symbol),
 
     /// Field expr (e.g, `wibbe.wobble`)
-    Field(Box<Expr>, String),
+    Field(Box<Expr>, Symbol),
 
     /// Closure expr (e.g `|param, param, ..n| ...`)
-    Closure(Vec<String>, Box<Expr>),
+    Closure(Vec<Symbol>, Box<Expr>),
 
     /// Assignment expr (e.g `a = b`)
     Assign(Box<Expr>, Box<Expr>),
 
     /// Block
     Block(Box<Block>),
+
+    /// Interpolated string (e.g `"hello, {name}"`)
+    Interp(Vec<InterpPart>),
 }
 
 /// Expression
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Expr {
+    pub id: HirId,
     pub kind: ExprKind,
     pub span: Span,
 }
@@ -3,45 +3,47 @@ use crate::{
     atom::{Param, Publicity, TypeHint},
     stmt::Block,
 };
-use common::token::Span;
+use common::{id::HirId, symbol::Symbol, token::Span};
 
 /// Represents struct field
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Field {
+    pub docs: Option<String>,
     pub span: Span,
-    pub name: String,
+    pub name: Symbol,
     pub hint: TypeHint,
 }
 
 /// Represents struct top-level item
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Struct {
-    pub name: String,
-    pub generics: Vec<String>,
+    pub name: Symbol,
+    pub generics: Vec<Symbol>,
     pub fields: Vec<Field>,
 }
 
 /// Represents enum variant
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Variant {
+    pub docs: Option<String>,
     pub span: Span,
-    pub name: String,
+    pub name: Symbol,
     pub params: Vec<TypeHint>,
 }
 
 /// Represents enum top-level item
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Enum {
-    pub name: String,
-    pub generics: Vec<String>,
+    pub name: Symbol,
+    pub generics: Vec<Symbol>,
     pub variants: Vec<Variant>,
 }
 
 /// Function top-level item
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Function {
-    pub name: String,
-    pub generics: Vec<String>,
+    pub name: Symbol,
+    pub generics: Vec<Symbol>,
     pub params: Vec<Param>,
     pub ret: TypeHint,
     pub block: Block,
@@ -51,10 +53,10 @@ pub struct Function {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum UseKind {
     /// `as $name`
-    As(String),
+    As(Symbol),
 
     /// `for ...`
-    For(Vec<String>),
+    For(Vec<Symbol>),
 
     /// Just import
     Just,
@@ -83,9 +85,27 @@ pub enum ItemKind {
     Use(Use),
 }
 
+/// Attribute argument
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AttributeArg {
+    pub span: Span,
+    pub name: Symbol,
+}
+
+/// Item attribute: `@name(arg, ...)`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Attribute {
+    pub span: Span,
+    pub name: Symbol,
+    pub args: Vec<AttributeArg>,
+}
+
 /// Top-level item
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Item {
+    pub id: HirId,
+    pub docs: Option<String>,
+    pub attrs: Vec<Attribute>,
     pub publicity: Publicity,
     pub kind: ItemKind,
     pub span: Span,
@@ -95,4 +115,6 @@ pub struct Item {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Module {
     pub items: Vec<Item>,
+    /// Distinct identifiers, except keywords, with their first occurrences
+    pub idents: Vec<(Symbol, Span)>,
 }
@@ -2,3 +2,4 @@ pub mod atom;
 pub mod expr;
 pub mod item;
 pub mod stmt;
+pub mod visit;
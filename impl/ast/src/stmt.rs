@@ -1,6 +1,6 @@
 /// Imports
 use crate::{atom::TypeHint, expr::Expr};
-use common::token::Span;
+use common::{id::HirId, symbol::Symbol, token::Span};
 
 /// For range
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -14,8 +14,8 @@ pub enum Range {
 /// Statement kind
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum StmtKind {
-    /// Let definition
-    Let(String, TypeHint, Expr),
+    /// Let definition: name, its span, type hint and value
+    Let(Symbol, Span, TypeHint, Expr),
 
     /// Expr without trailing semi-colon
     Expr(Expr),
@@ -29,7 +29,7 @@ impl StmtKind {
     /// Returns true if statement requires semicolon after it
     pub fn requires_semi(&self) -> bool {
         match self {
-            StmtKind::Let(_, _, _) | StmtKind::Semi(_) => true,
+            StmtKind::Let(..) | StmtKind::Semi(_) => true,
             StmtKind::Expr(_) => false,
         }
     }
@@ -38,6 +38,7 @@ impl StmtKind {
 /// Represents statement
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Stmt {
+    pub id: HirId,
     pub kind: StmtKind,
     pub span: Span,
 }
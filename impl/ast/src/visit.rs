@@ -0,0 +1,585 @@
+/// Imports
+use crate::{
+    atom::{Param, TypeHint},
+    expr::{Expr, ExprKind, InterpPart},
+    item::{Attribute, Field, Function, Item, ItemKind, Module, Use, Variant},
+    stmt::{Block, Stmt, StmtKind},
+};
+
+/// AST visitor. Every method defaults to the matching `walk_*`
+/// function, that visits children of the node, so visitors override
+/// only nodes they care about, calling `walk_*` to keep descending.
+///
+/// Nodes are borrowed for `'ast`, so visitors could collect them.
+///
+pub trait Visitor<'ast>: Sized {
+    fn visit_module(&mut self, module: &'ast Module) {
+        walk_module(self, module)
+    }
+
+    fn visit_item(&mut self, item: &'ast Item) {
+        walk_item(self, item)
+    }
+
+    fn visit_attribute(&mut self, _attr: &'ast Attribute) {}
+
+    fn visit_field(&mut self, field: &'ast Field) {
+        walk_field(self, field)
+    }
+
+    fn visit_variant(&mut self, variant: &'ast Variant) {
+        walk_variant(self, variant)
+    }
+
+    fn visit_function(&mut self, function: &'ast Function) {
+        walk_function(self, function)
+    }
+
+    fn visit_use(&mut self, _use: &'ast Use) {}
+
+    fn visit_param(&mut self, param: &'ast Param) {
+        walk_param(self, param)
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        walk_stmt(self, stmt)
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        walk_expr(self, expr)
+    }
+
+    fn visit_type_hint(&mut self, hint: &'ast TypeHint) {
+        walk_type_hint(self, hint)
+    }
+}
+
+/// Visits module items
+pub fn walk_module<'ast, V: Visitor<'ast>>(v: &mut V, module: &'ast Module) {
+    module.items.iter().for_each(|item| v.visit_item(item));
+}
+
+/// Visits item attributes and its kind
+pub fn walk_item<'ast, V: Visitor<'ast>>(v: &mut V, item: &'ast Item) {
+    item.attrs.iter().for_each(|attr| v.visit_attribute(attr));
+    match &item.kind {
+        ItemKind::Struct(it) => it.fields.iter().for_each(|field| v.visit_field(field)),
+        ItemKind::Enum(it) => it
+            .variants
+            .iter()
+            .for_each(|variant| v.visit_variant(variant)),
+        ItemKind::Function(it) => v.visit_function(it),
+        ItemKind::Use(it) => v.visit_use(it),
+    }
+}
+
+/// Visits field type hint
+pub fn walk_field<'ast, V: Visitor<'ast>>(v: &mut V, field: &'ast Field) {
+    v.visit_type_hint(&field.hint);
+}
+
+/// Visits variant params type hints
+pub fn walk_variant<'ast, V: Visitor<'ast>>(v: &mut V, variant: &'ast Variant) {
+    variant
+        .params
+        .iter()
+        .for_each(|hint| v.visit_type_hint(hint));
+}
+
+/// Visits function params, return type hint and body
+pub fn walk_function<'ast, V: Visitor<'ast>>(v: &mut V, function: &'ast Function) {
+    function
+        .params
+        .iter()
+        .for_each(|param| v.visit_param(param));
+    v.visit_type_hint(&function.ret);
+    v.visit_block(&function.block);
+}
+
+/// Visits param type hint
+pub fn walk_param<'ast, V: Visitor<'ast>>(v: &mut V, param: &'ast Param) {
+    v.visit_type_hint(&param.hint);
+}
+
+/// Visits block statements
+pub fn walk_block<'ast, V: Visitor<'ast>>(v: &mut V, block: &'ast Block) {
+    block.stmts.iter().for_each(|stmt| v.visit_stmt(stmt));
+}
+
+/// Visits statement type hint and expression
+pub fn walk_stmt<'ast, V: Visitor<'ast>>(v: &mut V, stmt: &'ast Stmt) {
+    match &stmt.kind {
+        StmtKind::Let(_, _, hint, expr) => {
+            v.visit_type_hint(hint);
+            v.visit_expr(expr);
+        }
+        StmtKind::Expr(expr) | StmtKind::Semi(expr) => v.visit_expr(expr),
+    }
+}
+
+/// Visits subexpressions in the evaluation order
+pub fn walk_expr<'ast, V: Visitor<'ast>>(v: &mut V, expr: &'ast Expr) {
+    match &expr.kind {
+        ExprKind::Lit(_) | ExprKind::Id(_) => {}
+        ExprKind::Unary(_, value) | ExprKind::Field(value, _) => v.visit_expr(value),
+        ExprKind::Bin(_, lhs, rhs) | ExprKind::Assign(lhs, rhs) => {
+            v.visit_expr(lhs);
+            v.visit_expr(rhs);
+        }
+        ExprKind::If(cond, then, else_) => {
+            v.visit_expr(cond);
+            v.visit_expr(then);
+            if let Some(else_) = else_ {
+                v.visit_expr(else_);
+            }
+        }
+        ExprKind::Call(what, args) => {
+            v.visit_expr(what);
+            args.iter().for_each(|arg| v.visit_expr(arg));
+        }
+        ExprKind::Closure(_, body) => v.visit_expr(body),
+        ExprKind::Block(block) => v.visit_block(block),
+        ExprKind::Interp(parts) => {
+            for part in parts {
+                if let InterpPart::Expr(value) = part {
+                    v.visit_expr(value);
+                }
+            }
+        }
+    }
+}
+
+/// Visits generic args, params and return type hints
+pub fn walk_type_hint<'ast, V: Visitor<'ast>>(v: &mut V, hint: &'ast TypeHint) {
+    match hint {
+        TypeHint::Local { args, .. } | TypeHint::Module { args, .. } => {
+            args.iter().for_each(|arg| v.visit_type_hint(arg));
+        }
+        TypeHint::Function { params, ret, .. } => {
+            params.iter().for_each(|param| v.visit_type_hint(param));
+            v.visit_type_hint(ret);
+        }
+        TypeHint::Unit(_) | TypeHint::Infer => {}
+    }
+}
+
+/// Mutable AST visitor, that changes nodes in place.
+/// Methods default to the matching `walk_*_mut` functions.
+pub trait VisitorMut: Sized {
+    fn visit_module_mut(&mut self, module: &mut Module) {
+        walk_module_mut(self, module)
+    }
+
+    fn visit_item_mut(&mut self, item: &mut Item) {
+        walk_item_mut(self, item)
+    }
+
+    fn visit_attribute_mut(&mut self, _attr: &mut Attribute) {}
+
+    fn visit_field_mut(&mut self, field: &mut Field) {
+        walk_field_mut(self, field)
+    }
+
+    fn visit_variant_mut(&mut self, variant: &mut Variant) {
+        walk_variant_mut(self, variant)
+    }
+
+    fn visit_function_mut(&mut self, function: &mut Function) {
+        walk_function_mut(self, function)
+    }
+
+    fn visit_use_mut(&mut self, _use: &mut Use) {}
+
+    fn visit_param_mut(&mut self, param: &mut Param) {
+        walk_param_mut(self, param)
+    }
+
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        walk_stmt_mut(self, stmt)
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        walk_expr_mut(self, expr)
+    }
+
+    fn visit_type_hint_mut(&mut self, hint: &mut TypeHint) {
+        walk_type_hint_mut(self, hint)
+    }
+}
+
+/// Visits module items
+pub fn walk_module_mut<V: VisitorMut>(v: &mut V, module: &mut Module) {
+    module
+        .items
+        .iter_mut()
+        .for_each(|item| v.visit_item_mut(item));
+}
+
+/// Visits item attributes and its kind
+pub fn walk_item_mut<V: VisitorMut>(v: &mut V, item: &mut Item) {
+    item.attrs
+        .iter_mut()
+        .for_each(|attr| v.visit_attribute_mut(attr));
+    match &mut item.kind {
+        ItemKind::Struct(it) => it
+            .fields
+            .iter_mut()
+            .for_each(|field| v.visit_field_mut(field)),
+        ItemKind::Enum(it) => it
+            .variants
+            .iter_mut()
+            .for_each(|variant| v.visit_variant_mut(variant)),
+        ItemKind::Function(it) => v.visit_function_mut(it),
+        ItemKind::Use(it) => v.visit_use_mut(it),
+    }
+}
+
+/// Visits field type hint
+pub fn walk_field_mut<V: VisitorMut>(v: &mut V, field: &mut Field) {
+    v.visit_type_hint_mut(&mut field.hint);
+}
+
+/// Visits variant params type hints
+pub fn walk_variant_mut<V: VisitorMut>(v: &mut V, variant: &mut Variant) {
+    variant
+        .params
+        .iter_mut()
+        .for_each(|hint| v.visit_type_hint_mut(hint));
+}
+
+/// Visits function params, return type hint and body
+pub fn walk_function_mut<V: VisitorMut>(v: &mut V, function: &mut Function) {
+    function
+        .params
+        .iter_mut()
+        .for_each(|param| v.visit_param_mut(param));
+    v.visit_type_hint_mut(&mut function.ret);
+    v.visit_block_mut(&mut function.block);
+}
+
+/// Visits param type hint
+pub fn walk_param_mut<V: VisitorMut>(v: &mut V, param: &mut Param) {
+    v.visit_type_hint_mut(&mut param.hint);
+}
+
+/// Visits block statements
+pub fn walk_block_mut<V: VisitorMut>(v: &mut V, block: &mut Block) {
+    block
+        .stmts
+        .iter_mut()
+        .for_each(|stmt| v.visit_stmt_mut(stmt));
+}
+
+/// Visits statement type hint and expression
+pub fn walk_stmt_mut<V: VisitorMut>(v: &mut V, stmt: &mut Stmt) {
+    match &mut stmt.kind {
+        StmtKind::Let(_, _, hint, expr) => {
+            v.visit_type_hint_mut(hint);
+            v.visit_expr_mut(expr);
+        }
+        StmtKind::Expr(expr) | StmtKind::Semi(expr) => v.visit_expr_mut(expr),
+    }
+}
+
+/// Visits subexpressions in the evaluation order
+pub fn walk_expr_mut<V: VisitorMut>(v: &mut V, expr: &mut Expr) {
+    match &mut expr.kind {
+        ExprKind::Lit(_) | ExprKind::Id(_) => {}
+        ExprKind::Unary(_, value) | ExprKind::Field(value, _) => v.visit_expr_mut(value),
+        ExprKind::Bin(_, lhs, rhs) | ExprKind::Assign(lhs, rhs) => {
+            v.visit_expr_mut(lhs);
+            v.visit_expr_mut(rhs);
+        }
+        ExprKind::If(cond, then, else_) => {
+            v.visit_expr_mut(cond);
+            v.visit_expr_mut(then);
+            if let Some(else_) = else_ {
+                v.visit_expr_mut(else_);
+            }
+        }
+        ExprKind::Call(what, args) => {
+            v.visit_expr_mut(what);
+            args.iter_mut().for_each(|arg| v.visit_expr_mut(arg));
+        }
+        ExprKind::Closure(_, body) => v.visit_expr_mut(body),
+        ExprKind::Block(block) => v.visit_block_mut(block),
+        ExprKind::Interp(parts) => {
+            for part in parts {
+                if let InterpPart::Expr(value) = part {
+                    v.visit_expr_mut(value);
+                }
+            }
+        }
+    }
+}
+
+/// Visits generic args, params and return type hints
+pub fn walk_type_hint_mut<V: VisitorMut>(v: &mut V, hint: &mut TypeHint) {
+    match hint {
+        TypeHint::Local { args, .. } | TypeHint::Module { args, .. } => {
+            args.iter_mut().for_each(|arg| v.visit_type_hint_mut(arg));
+        }
+        TypeHint::Function { params, ret, .. } => {
+            params
+                .iter_mut()
+                .for_each(|param| v.visit_type_hint_mut(param));
+            v.visit_type_hint_mut(ret);
+        }
+        TypeHint::Unit(_) | TypeHint::Infer => {}
+    }
+}
+
+/// AST folder, that rebuilds nodes by value.
+/// Methods default to the matching `fold_*` functions,
+/// that fold children and rebuild the node from them.
+pub trait Folder: Sized {
+    fn fold_module(&mut self, module: Module) -> Module {
+        fold_module(self, module)
+    }
+
+    fn fold_item(&mut self, item: Item) -> Item {
+        fold_item(self, item)
+    }
+
+    fn fold_attribute(&mut self, attr: Attribute) -> Attribute {
+        attr
+    }
+
+    fn fold_field(&mut self, field: Field) -> Field {
+        fold_field(self, field)
+    }
+
+    fn fold_variant(&mut self, variant: Variant) -> Variant {
+        fold_variant(self, variant)
+    }
+
+    fn fold_function(&mut self, function: Function) -> Function {
+        fold_function(self, function)
+    }
+
+    fn fold_use(&mut self, use_: Use) -> Use {
+        use_
+    }
+
+    fn fold_param(&mut self, param: Param) -> Param {
+        fold_param(self, param)
+    }
+
+    fn fold_block(&mut self, block: Block) -> Block {
+        fold_block(self, block)
+    }
+
+    fn fold_stmt(&mut self, stmt: Stmt) -> Stmt {
+        fold_stmt(self, stmt)
+    }
+
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        fold_expr(self, expr)
+    }
+
+    fn fold_type_hint(&mut self, hint: TypeHint) -> TypeHint {
+        fold_type_hint(self, hint)
+    }
+}
+
+/// Folds module items
+pub fn fold_module<F: Folder>(f: &mut F, module: Module) -> Module {
+    Module {
+        items: module
+            .items
+            .into_iter()
+            .map(|item| f.fold_item(item))
+            .collect(),
+        ..module
+    }
+}
+
+/// Folds item attributes and its kind
+pub fn fold_item<F: Folder>(f: &mut F, item: Item) -> Item {
+    let attrs = item
+        .attrs
+        .into_iter()
+        .map(|attr| f.fold_attribute(attr))
+        .collect();
+    let kind = match item.kind {
+        ItemKind::Struct(mut it) => {
+            it.fields = it
+                .fields
+                .into_iter()
+                .map(|field| f.fold_field(field))
+                .collect();
+            ItemKind::Struct(it)
+        }
+        ItemKind::Enum(mut it) => {
+            it.variants = it
+                .variants
+                .into_iter()
+                .map(|variant| f.fold_variant(variant))
+                .collect();
+            ItemKind::Enum(it)
+        }
+        ItemKind::Function(it) => ItemKind::Function(f.fold_function(it)),
+        ItemKind::Use(it) => ItemKind::Use(f.fold_use(it)),
+    };
+    Item {
+        attrs,
+        kind,
+        ..item
+    }
+}
+
+/// Folds field type hint
+pub fn fold_field<F: Folder>(f: &mut F, field: Field) -> Field {
+    Field {
+        hint: f.fold_type_hint(field.hint),
+        ..field
+    }
+}
+
+/// Folds variant params type hints
+pub fn fold_variant<F: Folder>(f: &mut F, variant: Variant) -> Variant {
+    Variant {
+        params: variant
+            .params
+            .into_iter()
+            .map(|hint| f.fold_type_hint(hint))
+            .collect(),
+        ..variant
+    }
+}
+
+/// Folds function params, return type hint and body
+pub fn fold_function<F: Folder>(f: &mut F, function: Function) -> Function {
+    Function {
+        params: function
+            .params
+            .into_iter()
+            .map(|param| f.fold_param(param))
+            .collect(),
+        ret: f.fold_type_hint(function.ret),
+        block: f.fold_block(function.block),
+        ..function
+    }
+}
+
+/// Folds param type hint
+pub fn fold_param<F: Folder>(f: &mut F, param: Param) -> Param {
+    Param {
+        hint: f.fold_type_hint(param.hint),
+        ..param
+    }
+}
+
+/// Folds block statements
+pub fn fold_block<F: Folder>(f: &mut F, block: Block) -> Block {
+    Block {
+        stmts: block
+            .stmts
+            .into_iter()
+            .map(|stmt| f.fold_stmt(stmt))
+            .collect(),
+        ..block
+    }
+}
+
+/// Folds statement type hint and expression
+pub fn fold_stmt<F: Folder>(f: &mut F, stmt: Stmt) -> Stmt {
+    let kind = match stmt.kind {
+        StmtKind::Let(name, span, hint, expr) => {
+            let hint = f.fold_type_hint(hint);
+            StmtKind::Let(name, span, hint, f.fold_expr(expr))
+        }
+        StmtKind::Expr(expr) => StmtKind::Expr(f.fold_expr(expr)),
+        StmtKind::Semi(expr) => StmtKind::Semi(f.fold_expr(expr)),
+    };
+    Stmt { kind, ..stmt }
+}
+
+/// Folds subexpressions in the evaluation order
+pub fn fold_expr<F: Folder>(f: &mut F, expr: Expr) -> Expr {
+    let kind = match expr.kind {
+        ExprKind::Lit(lit) => ExprKind::Lit(lit),
+        ExprKind::Unary(op, value) => ExprKind::Unary(op, Box::new(f.fold_expr(*value))),
+        ExprKind::Bin(op, lhs, rhs) => {
+            let lhs = f.fold_expr(*lhs);
+            ExprKind::Bin(op, Box::new(lhs), Box::new(f.fold_expr(*rhs)))
+        }
+        ExprKind::If(cond, then, else_) => {
+            let cond = f.fold_expr(*cond);
+            let then = f.fold_expr(*then);
+            ExprKind::If(
+                Box::new(cond),
+                Box::new(then),
+                else_.map(|it| Box::new(f.fold_expr(*it))),
+            )
+        }
+        ExprKind::Call(what, args) => {
+            let what = f.fold_expr(*what);
+            ExprKind::Call(
+                Box::new(what),
+                args.into_iter().map(|arg| f.fold_expr(arg)).collect(),
+            )
+        }
+        ExprKind::Id(name) => ExprKind::Id(name),
+        ExprKind::Field(what, name) => ExprKind::Field(Box::new(f.fold_expr(*what)), name),
+        ExprKind::Closure(params, body) => ExprKind::Closure(params, Box::new(f.fold_expr(*body))),
+        ExprKind::Assign(what, to) => {
+            let what = f.fold_expr(*what);
+            ExprKind::Assign(Box::new(what), Box::new(f.fold_expr(*to)))
+        }
+        ExprKind::Block(block) => ExprKind::Block(Box::new(f.fold_block(*block))),
+        ExprKind::Interp(parts) => ExprKind::Interp(
+            parts
+                .into_iter()
+                .map(|part| match part {
+                    InterpPart::Lit(text) => InterpPart::Lit(text),
+                    InterpPart::Expr(value) => InterpPart::Expr(f.fold_expr(value)),
+                })
+                .collect(),
+        ),
+    };
+    Expr { kind, ..expr }
+}
+
+/// Folds generic args, params and return type hints
+pub fn fold_type_hint<F: Folder>(f: &mut F, hint: TypeHint) -> TypeHint {
+    let fold_all = |f: &mut F, hints: Vec<TypeHint>| {
+        hints
+            .into_iter()
+            .map(|hint| f.fold_type_hint(hint))
+            .collect()
+    };
+    match hint {
+        TypeHint::Local { span, name, args } => TypeHint::Local {
+            span,
+            name,
+            args: fold_all(f, args),
+        },
+        TypeHint::Module {
+            span,
+            module,
+            name,
+            args,
+        } => TypeHint::Module {
+            span,
+            module,
+            name,
+            args: fold_all(f, args),
+        },
+        TypeHint::Function { span, params, ret } => TypeHint::Function {
+            span,
+            params: fold_all(f, params),
+            ret: Box::new(f.fold_type_hint(*ret)),
+        },
+        TypeHint::Unit(span) => TypeHint::Unit(span),
+        TypeHint::Infer => TypeHint::Infer,
+    }
+}
@@ -0,0 +1,77 @@
+/// Imports
+use std::process::ExitCode;
+
+/// Long-form explanations of the diagnostic codes, ordered by code.
+/// Codes are grouped by the compiler phase, that reports them:
+/// `E01xx` - lexer, `E02xx` - parser, `E03xx` - type checker.
+/// Lints use the `W` prefix instead of `E`
+const EXPLANATIONS: &[(&str, &str)] = &[
+    ("E0101", include_str!("explain/E0101.md")),
+    ("E0102", include_str!("explain/E0102.md")),
+    ("E0103", include_str!("explain/E0103.md")),
+    ("E0104", include_str!("explain/E0104.md")),
+    ("E0105", include_str!("explain/E0105.md")),
+    ("E0106", include_str!("explain/E0106.md")),
+    ("E0107", include_str!("explain/E0107.md")),
+    ("E0108", include_str!("explain/E0108.md")),
+    ("E0201", include_str!("explain/E0201.md")),
+    ("E0202", include_str!("explain/E0202.md")),
+    ("E0203", include_str!("explain/E0203.md")),
+    ("E0204", include_str!("explain/E0204.md")),
+    ("E0205", include_str!("explain/E0205.md")),
+    ("E0301", include_str!("explain/E0301.md")),
+    ("E0302", include_str!("explain/E0302.md")),
+    ("E0303", include_str!("explain/E0303.md")),
+    ("E0304", include_str!("explain/E0304.md")),
+    ("E0305", include_str!("explain/E0305.md")),
+    ("E0306", include_str!("explain/E0306.md")),
+    ("E0307", include_str!("explain/E0307.md")),
+    ("E0308", include_str!("explain/E0308.md")),
+    ("E0309", include_str!("explain/E0309.md")),
+    ("E0310", include_str!("explain/E0310.md")),
+    ("E0311", include_str!("explain/E0311.md")),
+    ("E0312", include_str!("explain/E0312.md")),
+    ("E0313", include_str!("explain/E0313.md")),
+    ("E0314", include_str!("explain/E0314.md")),
+    ("E0315", include_str!("explain/E0315.md")),
+    ("W0301", include_str!("explain/W0301.md")),
+    ("W0302", include_str!("explain/W0302.md")),
+    ("W0303", include_str!("explain/W0303.md")),
+    ("W0304", include_str!("explain/W0304.md")),
+    ("W0305", include_str!("explain/W0305.md")),
+    ("W0306", include_str!("explain/W0306.md")),
+];
+
+/// Looks up explanation by the code. Code is case-insensitive
+/// and could be written without the prefix: `e0303`, `0303`
+pub fn lookup(code: &str) -> Option<&'static str> {
+    let code = code.to_uppercase();
+    EXPLANATIONS
+        .iter()
+        .find(|(it, _)| *it == code || it[1..] == code)
+        .map(|(_, explanation)| *explanation)
+}
+
+/// Runs `explain` command: prints explanation of the code,
+/// or lists all known codes if no code is given
+pub fn run(code: Option<&str>) -> ExitCode {
+    match code {
+        Some(code) => match lookup(code) {
+            Some(explanation) => {
+                println!("{explanation}");
+                ExitCode::SUCCESS
+            }
+            None => {
+                eprintln!("error: no explanation for the code `{code}`");
+                ExitCode::FAILURE
+            }
+        },
+        None => {
+            for (code, explanation) in EXPLANATIONS {
+                let summary = explanation.lines().next().unwrap_or_default();
+                println!("{code}: {summary}");
+            }
+            ExitCode::SUCCESS
+        }
+    }
+}
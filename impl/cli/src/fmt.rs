@@ -0,0 +1,73 @@
+/// Imports
+use crate::report::{self, ErrorFormat};
+use common::source::SourceMap;
+use std::{
+    panic::{self, AssertUnwindSafe},
+    process::ExitCode,
+};
+
+/// Formats the file, returns its text and the formatted text.
+/// Read and syntax errors are reported, `None` is returned then
+fn format(path: &str) -> Option<(String, String)> {
+    let text = match std::fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) => {
+            eprintln!("error: can't read `{path}`: {e}");
+            return None;
+        }
+    };
+    let mut sources = SourceMap::default();
+    let id = sources.add(path, text.clone());
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| formatter::format(sources.get(id))));
+    macros::ice::reset();
+    match result {
+        Ok(formatted) => Some((text, formatted)),
+        Err(payload) => match macros::take_fatal() {
+            Some(fatal) => {
+                report::emit(ErrorFormat::Human, &sources, &[fatal]);
+                None
+            }
+            None => panic::resume_unwind(payload),
+        },
+    }
+}
+
+/// Runs `fmt [--check] <file>...` command: formats files in place.
+/// With `--check` files are not written, the command fails
+/// if any of them is not formatted
+pub fn run(args: &[String], usage: &str) -> ExitCode {
+    let check = args.iter().any(|arg| arg == "--check");
+    let paths = args
+        .iter()
+        .filter(|arg| *arg != "--check")
+        .collect::<Vec<_>>();
+    if paths.is_empty() || paths.iter().any(|path| path.starts_with('-')) {
+        eprintln!("{usage}");
+        return ExitCode::FAILURE;
+    }
+
+    let mut failed = false;
+    for path in paths {
+        match format(path) {
+            Some((text, formatted)) if text == formatted => {}
+            Some(_) if check => {
+                eprintln!("error: `{path}` is not formatted");
+                failed = true;
+            }
+            Some((_, formatted)) => {
+                if let Err(e) = std::fs::write(path, formatted) {
+                    eprintln!("error: can't write `{path}`: {e}");
+                    failed = true;
+                }
+            }
+            None => failed = true,
+        }
+    }
+
+    if !failed {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
@@ -1,10 +1,85 @@
-use std::sync::Arc;
+/// Modules
+mod explain;
+mod fmt;
+mod report;
 
+/// Imports
+use common::source::SourceMap;
 use lexer::Lexer;
-use miette::NamedSource;
+use miette::{Report, Severity};
 use parser::Parser;
+use report::ErrorFormat;
+use std::{
+    panic::{self, AssertUnwindSafe},
+    process::ExitCode,
+};
+use tycheck::{
+    check::ModuleTyck,
+    cx::{icx::InferCx, tcx::TyCx},
+    lint::{self, Level, LintLevels},
+};
 
-fn main() {
+/// Command line usage
+const USAGE: &str = "usage: cli [--error-format=human|json|sarif] [-A|-W|-D <lint>]... <file>
+       cli fmt [--check] <file>...
+       cli explain [code]";
+
+/// Parses lint level flag, both `-D lint` and `-Dlint` forms
+fn lint_flag<'a>(
+    arg: &'a str,
+    args: &mut impl Iterator<Item = &'a String>,
+) -> Option<Result<(Level, &'a str), String>> {
+    let (level, rest) = match arg.get(..2)? {
+        "-A" => (Level::Allow, &arg[2..]),
+        "-W" => (Level::Warn, &arg[2..]),
+        "-D" => (Level::Deny, &arg[2..]),
+        _ => return None,
+    };
+    let name = match rest {
+        "" => match args.next() {
+            Some(name) => name.as_str(),
+            None => return Some(Err(format!("`{arg}` requires a lint name"))),
+        },
+        name => name,
+    };
+    if name != lint::WARNINGS && lint::find(name).is_none() {
+        return Some(Err(format!("unknown lint `{name}`")));
+    }
+    Some(Ok((level, name)))
+}
+
+/// Checks the file, returns source map and reported diagnostics.
+/// Fatal lexer and parser errors stop checking and are returned last
+fn check(path: &str, levels: LintLevels) -> Result<(SourceMap, Vec<Report>), String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("can't read `{path}`: {e}"))?;
+    let mut tcx = TyCx::default();
+    let id = tcx.sources.add(path, text);
+
+    let mut reports = Vec::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let file = tcx.sources.get(id);
+        let mut parser = Parser::new(file.source(), Lexer::new(file));
+        let module = parser.parse();
+
+        let mut icx = InferCx::new(&mut tcx);
+        let mut tyck = ModuleTyck::new(&mut icx).with_lint_levels(levels);
+        tyck.check_module(module);
+        reports.extend(tyck.take_diagnostics().into_iter().map(Report::new));
+    }));
+    macros::ice::reset();
+    match result {
+        Ok(()) => Ok((tcx.sources, reports)),
+        Err(payload) => match macros::take_fatal() {
+            Some(fatal) => {
+                reports.push(fatal);
+                Ok((tcx.sources, reports))
+            }
+            None => panic::resume_unwind(payload),
+        },
+    }
+}
+
+fn main() -> ExitCode {
     let _ = miette::set_hook(Box::new(|_| {
         Box::new(
             miette::MietteHandlerOpts::new()
@@ -16,18 +91,76 @@ fn main() {
                 .build(),
         )
     }));
-    let sources = r#"
-fn hello() {
-    println("Hi!")
-}
 
-struct House {
-  street: u16,
-  id: u64,
-}
-"#;
-    let file = Arc::new(NamedSource::new("test.b", sources.to_string()));
-    let lexer = Lexer::new(file.clone(), sources);
-    let mut parser = Parser::new(file, lexer);
-    println!("{:#?}", parser.parse());
+    // Fatal errors are emitted by the reporter,
+    // so the panic hook reports only the internal compiler errors
+    macros::ice::install_hook();
+
+    // `explain [code]` and `fmt [--check] <file>...` commands
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    match args.first().map(String::as_str) {
+        Some("explain") => return explain::run(args.get(1).map(String::as_str)),
+        Some("fmt") => return fmt::run(&args[1..], USAGE),
+        _ => {}
+    }
+
+    // Parsing arguments
+    let mut format = ErrorFormat::Human;
+    let mut levels = LintLevels::default();
+    let mut path = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match lint_flag(arg, &mut iter) {
+            Some(Ok((level, name))) => {
+                levels.set(name, level);
+                continue;
+            }
+            Some(Err(err)) => {
+                eprintln!("error: {err}");
+                return ExitCode::FAILURE;
+            }
+            None => {}
+        }
+        match arg.strip_prefix("--error-format=") {
+            Some(value) => match value.parse() {
+                Ok(value) => format = value,
+                Err(err) => {
+                    eprintln!("error: {err}");
+                    return ExitCode::FAILURE;
+                }
+            },
+            None if path.is_none() && !arg.starts_with('-') => path = Some(arg.as_str()),
+            None => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    let Some(path) = path else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    // Checking and reporting, warnings
+    // don't fail the check, unless promoted
+    match check(path, levels) {
+        Ok((sources, reports)) => {
+            report::emit(format, &sources, &reports);
+            let failed = reports.iter().any(|report| {
+                !matches!(
+                    report.severity(),
+                    Some(Severity::Warning | Severity::Advice)
+                )
+            });
+            if !failed {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
 }
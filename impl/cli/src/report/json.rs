@@ -0,0 +1,100 @@
+/// Imports
+use super::{Diag, Label, severity_name};
+use std::fmt::{self, Display, Write};
+
+/// JSON value
+pub enum Json {
+    Null,
+    Bool(bool),
+    Num(usize),
+    Str(String),
+    Arr(Vec<Json>),
+    Obj(Vec<(&'static str, Json)>),
+}
+
+/// Implementation
+impl Json {
+    /// Creates string or null
+    pub fn opt_str(value: Option<&str>) -> Json {
+        value.map_or(Json::Null, |s| Json::Str(s.to_string()))
+    }
+}
+
+/// Writes escaped JSON string
+fn write_str(f: &mut fmt::Formatter<'_>, s: &str) -> fmt::Result {
+    f.write_char('"')?;
+    for ch in s.chars() {
+        match ch {
+            '"' => f.write_str("\\\"")?,
+            '\\' => f.write_str("\\\\")?,
+            '\n' => f.write_str("\\n")?,
+            '\r' => f.write_str("\\r")?,
+            '\t' => f.write_str("\\t")?,
+            ch if ch.is_control() => write!(f, "\\u{:04x}", ch as u32)?,
+            ch => f.write_char(ch)?,
+        }
+    }
+    f.write_char('"')
+}
+
+/// Compact JSON rendering
+impl Display for Json {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Json::Null => f.write_str("null"),
+            Json::Bool(value) => write!(f, "{value}"),
+            Json::Num(value) => write!(f, "{value}"),
+            Json::Str(value) => write_str(f, value),
+            Json::Arr(values) => {
+                f.write_char('[')?;
+                for (idx, value) in values.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_char(',')?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_char(']')
+            }
+            Json::Obj(fields) => {
+                f.write_char('{')?;
+                for (idx, (key, value)) in fields.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_char(',')?;
+                    }
+                    write_str(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                f.write_char('}')
+            }
+        }
+    }
+}
+
+/// Serializes label span
+fn span(label: &Label) -> Json {
+    Json::Obj(vec![
+        ("file", Json::Str(label.file.clone())),
+        ("byte_start", Json::Num(label.start.offset)),
+        ("byte_end", Json::Num(label.end.offset)),
+        ("line_start", Json::Num(label.start.line)),
+        ("column_start", Json::Num(label.start.column)),
+        ("line_end", Json::Num(label.end.line)),
+        ("column_end", Json::Num(label.end.column)),
+        ("label", Json::opt_str(label.text.as_deref())),
+        ("primary", Json::Bool(label.primary)),
+    ])
+}
+
+/// Serializes diagnostic
+pub fn diagnostic(diag: &Diag) -> Json {
+    Json::Obj(vec![
+        ("code", Json::opt_str(diag.code.as_deref())),
+        (
+            "severity",
+            Json::Str(severity_name(diag.severity).to_string()),
+        ),
+        ("message", Json::Str(diag.message.clone())),
+        ("help", Json::opt_str(diag.help.as_deref())),
+        ("spans", Json::Arr(diag.labels.iter().map(span).collect())),
+    ])
+}
@@ -0,0 +1,167 @@
+/// Modules
+mod json;
+mod sarif;
+
+/// Imports
+use common::source::SourceMap;
+use miette::{Diagnostic, Report, Severity, SourceCode, SourceSpan};
+use std::str::FromStr;
+
+/// Diagnostics output format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorFormat {
+    /// Graphical miette output
+    Human,
+
+    /// One JSON object per diagnostic, one per line
+    Json,
+
+    /// SARIF 2.1.0 log
+    Sarif,
+}
+
+/// Error format parsing implementation
+impl FromStr for ErrorFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(ErrorFormat::Human),
+            "json" => Ok(ErrorFormat::Json),
+            "sarif" => Ok(ErrorFormat::Sarif),
+            other => Err(format!(
+                "unknown error format `{other}`, expected `human`, `json` or `sarif`"
+            )),
+        }
+    }
+}
+
+/// Position in the source file, lines and columns start from 1,
+/// columns are counted in chars
+pub struct Position {
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Labeled source span of the diagnostic
+pub struct Label {
+    /// Source file name
+    pub file: String,
+
+    /// Start of the span
+    pub start: Position,
+
+    /// End of the span, exclusive
+    pub end: Position,
+
+    /// Label text
+    pub text: Option<String>,
+
+    /// Is primary span of the diagnostic
+    pub primary: bool,
+}
+
+/// Diagnostic, flattened for the machine-readable output
+pub struct Diag {
+    pub code: Option<String>,
+    pub severity: Severity,
+    pub message: String,
+    pub help: Option<String>,
+    pub labels: Vec<Label>,
+}
+
+/// Implementation
+impl Diag {
+    /// Flattens diagnostic. The first label is primary,
+    /// unless diagnostic marks the primary one explicitly
+    pub fn new(diagnostic: &dyn Diagnostic, sources: &SourceMap) -> Self {
+        let labels = diagnostic
+            .labels()
+            .map(|labels| labels.collect::<Vec<_>>())
+            .unwrap_or_default();
+        let explicit = labels.iter().any(|label| label.primary());
+        let labels = match diagnostic.source_code() {
+            Some(source) => labels
+                .iter()
+                .enumerate()
+                .filter_map(|(idx, label)| {
+                    let (file, start) = position(sources, source, label.offset())?;
+                    let (_, end) = position(sources, source, label.offset() + label.len())?;
+                    Some(Label {
+                        file,
+                        start,
+                        end,
+                        text: label.label().map(str::to_string),
+                        primary: if explicit { label.primary() } else { idx == 0 },
+                    })
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        Self {
+            code: diagnostic.code().map(|code| code.to_string()),
+            severity: diagnostic.severity().unwrap_or(Severity::Error),
+            message: diagnostic.to_string(),
+            help: diagnostic.help().map(|help| help.to_string()),
+            labels,
+        }
+    }
+}
+
+/// Returns source name and position of the byte offset.
+/// Positions are computed by the source map, the diagnostic
+/// source is used only to find the file by its name
+fn position(
+    sources: &SourceMap,
+    source: &dyn SourceCode,
+    offset: usize,
+) -> Option<(String, Position)> {
+    let contents = source
+        .read_span(&SourceSpan::new(offset.into(), 0), 0, 0)
+        .ok()?;
+    let file = sources.find(contents.name()?)?;
+    let at = file.line_col(offset);
+    Some((
+        file.name().to_string(),
+        Position {
+            offset,
+            line: at.line,
+            column: at.column,
+        },
+    ))
+}
+
+/// Returns severity name
+pub fn severity_name(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Advice => "advice",
+        Severity::Warning => "warning",
+        _ => "error",
+    }
+}
+
+/// Emits reports in the given format to the stdout,
+/// human-readable output goes to the stderr
+pub fn emit(format: ErrorFormat, sources: &SourceMap, reports: &[Report]) {
+    match format {
+        ErrorFormat::Human => {
+            for report in reports {
+                eprintln!("{report:?}");
+            }
+        }
+        ErrorFormat::Json => {
+            for report in reports {
+                println!("{}", json::diagnostic(&Diag::new(report.as_ref(), sources)));
+            }
+        }
+        ErrorFormat::Sarif => {
+            let diags = reports
+                .iter()
+                .map(|report| Diag::new(report.as_ref(), sources))
+                .collect::<Vec<_>>();
+            println!("{}", sarif::log(&diags));
+        }
+    }
+}
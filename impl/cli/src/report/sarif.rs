@@ -0,0 +1,123 @@
+/// Imports
+use super::{Diag, Label, json::Json};
+use miette::Severity;
+use std::collections::BTreeSet;
+
+/// SARIF schema of the produced log
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+/// Returns SARIF result level
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Advice => "note",
+        Severity::Warning => "warning",
+        _ => "error",
+    }
+}
+
+/// Percent-encodes file path as a relative URI reference. Only
+/// unreserved characters and path separators are kept as is
+fn uri(path: &str) -> String {
+    let mut uri = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' => {
+                uri.push(byte as char)
+            }
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    uri
+}
+
+/// Serializes label as SARIF location
+fn location(label: &Label) -> Json {
+    let mut location = vec![(
+        "physicalLocation",
+        Json::Obj(vec![
+            (
+                "artifactLocation",
+                Json::Obj(vec![("uri", Json::Str(uri(&label.file)))]),
+            ),
+            (
+                "region",
+                Json::Obj(vec![
+                    ("startLine", Json::Num(label.start.line)),
+                    ("startColumn", Json::Num(label.start.column)),
+                    ("endLine", Json::Num(label.end.line)),
+                    ("endColumn", Json::Num(label.end.column)),
+                    ("byteOffset", Json::Num(label.start.offset)),
+                    (
+                        "byteLength",
+                        Json::Num(label.end.offset - label.start.offset),
+                    ),
+                ]),
+            ),
+        ]),
+    )];
+    if let Some(text) = &label.text {
+        location.push((
+            "message",
+            Json::Obj(vec![("text", Json::Str(text.clone()))]),
+        ));
+    }
+    Json::Obj(location)
+}
+
+/// Serializes diagnostic as SARIF result. Primary labels become
+/// locations, secondary ones become related locations
+fn result(diag: &Diag) -> Json {
+    let message = match &diag.help {
+        Some(help) => format!("{}\nhelp: {help}", diag.message),
+        None => diag.message.clone(),
+    };
+    let (primary, secondary): (Vec<_>, Vec<_>) =
+        diag.labels.iter().partition(|label| label.primary);
+
+    let mut result = Vec::new();
+    if let Some(code) = &diag.code {
+        result.push(("ruleId", Json::Str(code.clone())));
+    }
+    result.push(("level", Json::Str(level(diag.severity).to_string())));
+    result.push(("message", Json::Obj(vec![("text", Json::Str(message))])));
+    result.push((
+        "locations",
+        Json::Arr(primary.into_iter().map(location).collect()),
+    ));
+    if !secondary.is_empty() {
+        result.push((
+            "relatedLocations",
+            Json::Arr(secondary.into_iter().map(location).collect()),
+        ));
+    }
+    Json::Obj(result)
+}
+
+/// Serializes diagnostics as SARIF log with a single run
+pub fn log(diags: &[Diag]) -> Json {
+    let rules = diags
+        .iter()
+        .filter_map(|diag| diag.code.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|code| Json::Obj(vec![("id", Json::Str(code))]))
+        .collect();
+    let driver = Json::Obj(vec![
+        ("name", Json::Str(env!("CARGO_PKG_NAME").to_string())),
+        ("version", Json::Str(env!("CARGO_PKG_VERSION").to_string())),
+        ("rules", Json::Arr(rules)),
+    ]);
+
+    Json::Obj(vec![
+        ("$schema", Json::Str(SCHEMA.to_string())),
+        ("version", Json::Str("2.1.0".to_string())),
+        (
+            "runs",
+            Json::Arr(vec![Json::Obj(vec![
+                ("tool", Json::Obj(vec![("driver", driver)])),
+                ("columnKind", Json::Str("unicodeCodePoints".to_string())),
+                ("results", Json::Arr(diags.iter().map(result).collect())),
+            ])]),
+        ),
+    ])
+}
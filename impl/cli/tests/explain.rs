@@ -0,0 +1,161 @@
+/// Imports
+use ast::atom::Publicity;
+use common::{symbol::Symbol, token::Span};
+use lexer::Lexer;
+use miette::Severity;
+use parser::Parser;
+use std::{
+    collections::HashMap,
+    fs,
+    panic::{self, AssertUnwindSafe},
+    path::Path,
+    process::Command,
+};
+use tir::def::{AdtDef, ItemDef, ItemDefKind, ModDef, StructDef};
+use tycheck::{
+    check::ModuleTyck,
+    cx::{icx::InferCx, tcx::TyCx},
+};
+
+/// Runs `explain` command and returns its success with the output
+fn explain(code: &str) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_cli"))
+        .args(["explain", code])
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+/// Returns explanations with their codes, ordered by code
+fn explanations() -> Vec<(String, String)> {
+    let dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("src/explain");
+    let mut explanations = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| {
+            let path = entry.unwrap().path();
+            let code = path.file_stem().unwrap().to_string_lossy().to_string();
+            (code, fs::read_to_string(path).unwrap())
+        })
+        .collect::<Vec<_>>();
+    explanations.sort();
+    explanations
+}
+
+/// Returns code examples of the explanation, the erroneous one goes first
+fn examples(explanation: &str) -> Vec<&str> {
+    explanation
+        .split("```")
+        .skip(1)
+        .step_by(2)
+        .map(|example| example.trim_start_matches(|ch| ch != '\n'))
+        .collect()
+}
+
+/// Checks the example and returns codes of the reported diagnostics
+/// with their severities. Examples could import the `geo` module,
+/// that defines a public `Point` struct
+fn check(example: &str) -> Vec<(String, Severity)> {
+    let mut tcx = TyCx::default();
+    let geo = tcx.sources.add("geo.bit", String::new());
+    let point = tcx.insert_adt(AdtDef::Struct(StructDef {
+        docs: None,
+        span: Span(geo, 0..0),
+        name: Symbol::intern("Point"),
+        generics: Vec::new(),
+        fields: Vec::new(),
+    }));
+    let defs = HashMap::from([(
+        Symbol::intern("Point"),
+        ItemDef {
+            publicity: Publicity::Pub,
+            kind: ItemDefKind::Adt(point),
+        },
+    )]);
+    tcx.insert_mod(ModDef { file: geo, defs });
+
+    let id = tcx.sources.add("main.bit", example.to_string());
+    let mut diagnostics = Vec::new();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let file = tcx.sources.get(id);
+        let module = Parser::new(file.source(), Lexer::new(file)).parse();
+        let mut icx = InferCx::new(&mut tcx);
+        let mut tyck = ModuleTyck::new(&mut icx);
+        tyck.check_module(module);
+        for diag in tyck.take_diagnostics() {
+            diagnostics.push(miette::Report::new(diag));
+        }
+    }));
+    if result.is_err() {
+        diagnostics.push(macros::take_fatal().expect("expected fatal report"));
+    }
+    diagnostics
+        .iter()
+        .map(|diag| {
+            let code = diag.code().map(|it| it.to_string()).unwrap_or_default();
+            (code, diag.severity().unwrap_or(Severity::Error))
+        })
+        .collect()
+}
+
+#[test]
+fn codes_are_looked_up_case_insensitively() {
+    let (success, text) = explain("E0303");
+    assert!(success);
+    assert!(text.starts_with("An expression has a type"), "{text}");
+    assert_eq!(explain("e0303"), (true, text.clone()));
+    assert_eq!(explain("0303"), (true, text));
+}
+
+#[test]
+fn unknown_codes_are_not_found() {
+    assert_eq!(explain("E0999"), (false, String::new()));
+    assert_eq!(explain("W0303"), explain("w0303"));
+    assert!(!explain("X0303").0);
+    assert!(!explain("E03").0);
+}
+
+#[test]
+fn every_explanation_is_registered() {
+    for (code, explanation) in explanations() {
+        let (success, text) = explain(&code);
+        assert!(success, "{code} is not registered");
+        assert_eq!(text.trim_end(), explanation.trim_end(), "{code}");
+    }
+}
+
+#[test]
+fn examples_report_their_own_codes() {
+    let mut failures = Vec::new();
+    for (code, explanation) in explanations() {
+        // Compiler bugs, like `E0104`, have no examples
+        let examples = examples(&explanation);
+        let Some((erroneous, correct)) = examples.split_first() else {
+            continue;
+        };
+
+        // Erroneous example reports its code and no other errors
+        let diags = check(erroneous);
+        if !diags.iter().any(|(it, _)| *it == code)
+            || diags
+                .iter()
+                .any(|(it, severity)| *it != code && *severity == Severity::Error)
+        {
+            failures.push(format!("{code}: {diags:?}"));
+        }
+
+        // Correct examples report neither the code, nor errors
+        for example in correct {
+            let diags = check(example);
+            if diags
+                .iter()
+                .any(|(it, severity)| *it == code || *severity == Severity::Error)
+            {
+                failures.push(format!("{code} correct example: {diags:?}"));
+            }
+        }
+    }
+    assert!(failures.is_empty(), "{failures:#?}");
+}
@@ -0,0 +1,100 @@
+/// Imports
+use std::{env, fs, process::Command};
+
+/// Source with a type mismatch after non-ASCII text and an unused variable
+const SOURCE: &str = "fn main() {
+    let _s = \"日本\"; let _a: Int = \"x\";
+    let unused = 1;
+}
+";
+
+/// File name, that needs escaping
+const FILE: &str = "a\"b\\c.bit";
+
+/// Checks the source in the given error format, returns the output lines
+fn emit(test: &str, format: &str) -> Vec<String> {
+    emit_file(test, format, FILE)
+}
+
+/// Checks the source, written to `file`, in the given error format
+fn emit_file(test: &str, format: &str, file: &str) -> Vec<String> {
+    let dir = env::temp_dir().join("cli-report-tests").join(test);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join(file), SOURCE).unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_cli"))
+        .current_dir(&dir)
+        .arg(format!("--error-format={format}"))
+        .arg(file)
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(str::to_string)
+        .collect()
+}
+
+#[test]
+fn json_diagnostics_are_escaped() {
+    let lines = emit("json_escaping", "json");
+    assert_eq!(lines.len(), 2, "{lines:?}");
+    assert!(
+        lines[0].starts_with(
+            "{\"code\":\"E0303\",\"severity\":\"error\",\
+             \"message\":\"type mismatch: expected `Int`, found `String`\","
+        ),
+        "{}",
+        lines[0]
+    );
+    assert!(lines[0].contains(r#""file":"a\"b\\c.bit""#), "{}", lines[0]);
+    assert!(
+        lines[1].starts_with("{\"code\":\"W0302\",\"severity\":\"warning\","),
+        "{}",
+        lines[1]
+    );
+}
+
+#[test]
+fn json_columns_are_counted_in_chars() {
+    let lines = emit("json_columns", "json");
+    // `Int` starts at the 28th char, but at the 43rd byte
+    assert!(
+        lines[0].contains(
+            "\"byte_start\":43,\"byte_end\":46,\
+             \"line_start\":2,\"column_start\":28,\
+             \"line_end\":2,\"column_end\":31,\
+             \"label\":\"expected due to this annotation\",\"primary\":false"
+        ),
+        "{}",
+        lines[0]
+    );
+}
+
+#[test]
+fn sarif_log_has_rules_and_locations() {
+    let lines = emit("sarif_shape", "sarif");
+    assert_eq!(lines.len(), 1, "{lines:?}");
+    let log = &lines[0];
+    for part in [
+        r#"{"$schema":"https://json.schemastore.org/sarif-2.1.0.json","version":"2.1.0","runs":[{"tool":{"driver":{"name":"cli","#,
+        r#""rules":[{"id":"E0303"},{"id":"W0302"}]}},"columnKind":"unicodeCodePoints","#,
+        r#""results":[{"ruleId":"E0303","level":"error","#,
+        r#""message":{"text":"type mismatch: expected `Int`, found `String`\nhelp: expected `Int` because of the type annotation"}"#,
+        r#""locations":[{"physicalLocation":{"artifactLocation":{"uri":"a%22b%5Cc.bit"},"region":{"startLine":2,"#,
+        r#""relatedLocations":[{"physicalLocation":{"artifactLocation":{"uri":"a%22b%5Cc.bit"},"region":{"startLine":2,"startColumn":28,"endLine":2,"endColumn":31,"byteOffset":43,"byteLength":3}},"message":{"text":"expected due to this annotation"}}]"#,
+        r#"{"ruleId":"W0302","level":"warning","#,
+    ] {
+        assert!(log.contains(part), "missing `{part}` in {log}");
+    }
+}
+
+#[test]
+fn sarif_uri_is_percent_encoded() {
+    let lines = emit_file("sarif_uri", "sarif", "my file.bit");
+    assert!(
+        lines[0].contains(r#""artifactLocation":{"uri":"my%20file.bit"}"#),
+        "{}",
+        lines[0]
+    );
+}
@@ -1 +1,17 @@
-pub type HirId = usize;
+/// Imports
+use crate::source::FileId;
+use std::fmt::Debug;
+
+/// Unique id of the expression, statement or item node,
+/// assigned by the parser. Ids are unique within the file
+/// and kept by the type checker, so the results of the
+/// checking can be looked up by the node id
+#[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct HirId(pub FileId, pub u32);
+
+/// Debug implementation
+impl Debug for HirId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "HirId({}:{})", self.0.0, self.1)
+    }
+}
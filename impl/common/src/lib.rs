@@ -1,2 +1,4 @@
 pub mod id;
+pub mod source;
+pub mod symbol;
 pub mod token;
@@ -0,0 +1,146 @@
+/// Imports
+use macros::bug;
+use miette::NamedSource;
+use std::{fmt::Debug, ops::Range, path::Path, sync::Arc};
+
+/// Source file id in the source map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FileId(pub u32);
+
+/// Position in the source file. Lines and columns start from 1,
+/// columns are counted in chars
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineCol {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Source file
+pub struct SourceFile {
+    /// File id
+    id: FileId,
+
+    /// Named source, used by diagnostics
+    source: Arc<NamedSource<String>>,
+
+    /// Byte offsets of the lines starts
+    lines: Vec<usize>,
+}
+
+/// Implementation
+impl SourceFile {
+    /// Creates source file
+    fn new(id: FileId, name: &str, text: String) -> Self {
+        let lines = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(idx, _)| idx + 1))
+            .collect();
+        Self {
+            id,
+            source: Arc::new(NamedSource::new(name, text)),
+            lines,
+        }
+    }
+
+    /// Returns file id
+    pub fn id(&self) -> FileId {
+        self.id
+    }
+
+    /// Returns file name
+    pub fn name(&self) -> &str {
+        self.source.name()
+    }
+
+    /// Returns file text
+    pub fn text(&self) -> &str {
+        self.source.inner()
+    }
+
+    /// Returns named source, used by diagnostics
+    pub fn source(&self) -> Arc<NamedSource<String>> {
+        self.source.clone()
+    }
+
+    /// Returns name of the module, defined by the file: its name without extension
+    pub fn module_name(&self) -> String {
+        Path::new(self.name())
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_else(|| self.name().to_string())
+    }
+
+    /// Converts byte offset into the line and column. Offsets
+    /// past the end of file are clamped to the end of file
+    pub fn line_col(&self, offset: usize) -> LineCol {
+        let text = self.text();
+        let mut offset = offset.min(text.len());
+        while !text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.lines.partition_point(|start| *start <= offset) - 1;
+        LineCol {
+            line: line + 1,
+            column: text[self.lines[line]..offset].chars().count() + 1,
+        }
+    }
+
+    /// Converts line and column into the byte offset
+    pub fn offset(&self, at: LineCol) -> Option<usize> {
+        let start = *self.lines.get(at.line.checked_sub(1)?)?;
+        let line = &self.text()[start..];
+        let line = &line[..line.find('\n').unwrap_or(line.len())];
+        match at.column.checked_sub(1)? {
+            column if column == line.chars().count() => Some(start + line.len()),
+            column => line.char_indices().nth(column).map(|(idx, _)| start + idx),
+        }
+    }
+}
+
+/// Debug implementation
+impl Debug for SourceFile {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SourceFile")
+            .field("id", &self.id)
+            .field("name", &self.name())
+            .finish()
+    }
+}
+
+/// Holds all source files of the compilation
+#[derive(Default, Debug)]
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+}
+
+/// Implementation
+impl SourceMap {
+    /// Adds file, returns its id
+    pub fn add(&mut self, name: &str, text: String) -> FileId {
+        let id = FileId(self.files.len() as u32);
+        self.files.push(SourceFile::new(id, name, text));
+        id
+    }
+
+    /// Returns file by its id
+    pub fn get(&self, id: FileId) -> &SourceFile {
+        self.files
+            .get(id.0 as usize)
+            .unwrap_or_else(|| bug!("file {id:?} not found"))
+    }
+
+    /// Looks up file by its name
+    pub fn find(&self, name: &str) -> Option<&SourceFile> {
+        self.files.iter().find(|file| file.name() == name)
+    }
+
+    /// Returns named source of the file, used by diagnostics
+    pub fn source(&self, id: FileId) -> Arc<NamedSource<String>> {
+        self.get(id).source()
+    }
+
+    /// Converts byte range in the file into start and end positions
+    pub fn range(&self, id: FileId, range: Range<usize>) -> (LineCol, LineCol) {
+        let file = self.get(id);
+        (file.line_col(range.start), file.line_col(range.end))
+    }
+}
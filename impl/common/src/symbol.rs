@@ -0,0 +1,195 @@
+/// Imports
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display},
+    hash::{BuildHasherDefault, Hasher},
+    sync::{LazyLock, Mutex, RwLock},
+};
+
+/// Interned string: identifier, keyword or literal text.
+///
+/// Symbols are compared and hashed by their index, so equal
+/// strings are interned once and share the symbol. Interned
+/// strings live until the end of the program.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Symbol(u32);
+
+/// Fast non-cryptographic hasher for the short strings,
+/// the same multiply-rotate scheme as `rustc`'s `FxHasher`
+#[derive(Default)]
+struct FxHasher(u64);
+
+/// Hasher implementation
+impl Hasher for FxHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            let word = u64::from_le_bytes(chunk.try_into().unwrap());
+            self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(SEED);
+        }
+        for &byte in chunks.remainder() {
+            self.0 = (self.0.rotate_left(5) ^ byte as u64).wrapping_mul(SEED);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+}
+
+/// Global strings interner. Interning is serialized by the mutex,
+/// while reads go through the append-only [`STRINGS`] table
+struct Interner {
+    /// Symbols by interned strings
+    symbols: HashMap<&'static str, Symbol, BuildHasherDefault<FxHasher>>,
+}
+
+/// Implementation
+impl Interner {
+    /// Creates interner with the pre-interned symbols
+    fn prefill(init: &[&'static str]) -> Self {
+        Self {
+            symbols: init
+                .iter()
+                .enumerate()
+                .map(|(i, s)| (*s, Symbol(i as u32)))
+                .collect(),
+        }
+    }
+
+    /// Interns string
+    fn intern(&mut self, string: &str) -> Symbol {
+        if let Some(symbol) = self.symbols.get(string) {
+            return *symbol;
+        }
+        let string: &'static str = Box::leak(string.into());
+        let mut strings = STRINGS.write().unwrap();
+        let symbol = Symbol(strings.len() as u32);
+        strings.push(string);
+        self.symbols.insert(string, symbol);
+        symbol
+    }
+}
+
+/// Interner, shared by all threads
+static INTERNER: LazyLock<Mutex<Interner>> =
+    LazyLock::new(|| Mutex::new(Interner::prefill(PREINTERNED)));
+
+/// Interned strings by their symbols. Strings are only appended,
+/// so readers never wait for each other
+static STRINGS: LazyLock<RwLock<Vec<&'static str>>> =
+    LazyLock::new(|| RwLock::new(PREINTERNED.to_vec()));
+
+/// Implementation
+impl Symbol {
+    /// Interns string and returns its symbol
+    pub fn intern(string: &str) -> Self {
+        INTERNER.lock().unwrap().intern(string)
+    }
+
+    /// Returns interned string. Pre-interned symbols are
+    /// resolved without taking any lock
+    pub fn as_str(&self) -> &'static str {
+        let idx = self.0 as usize;
+        match PREINTERNED.get(idx) {
+            Some(string) => string,
+            None => STRINGS.read().unwrap()[idx],
+        }
+    }
+}
+
+/// Display implementation
+impl Display for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Debug implementation
+impl Debug for Symbol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(self.as_str(), f)
+    }
+}
+
+/// Comparison with the string
+impl PartialEq<&str> for Symbol {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+/// Conversion from the string
+impl From<&str> for Symbol {
+    fn from(string: &str) -> Self {
+        Symbol::intern(string)
+    }
+}
+
+/// Declares pre-interned symbols: keywords in `kw`
+/// module and frequently used names in `sym` module
+macro_rules! symbols {
+    (
+        keywords { $($kw:ident: $kw_text:literal,)* }
+        symbols { $($sym:ident: $sym_text:literal,)* }
+    ) => {
+        /// Indices of the pre-interned symbols
+        #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
+        #[repr(u32)]
+        enum Preinterned {
+            $($kw,)*
+            $($sym,)*
+        }
+
+        /// Pre-interned strings, in order of their indices
+        const PREINTERNED: &[&str] = &[$($kw_text,)* $($sym_text,)*];
+
+        /// Keywords
+        pub mod kw {
+            use super::{Preinterned, Symbol};
+            $(pub const $kw: Symbol = Symbol(Preinterned::$kw as u32);)*
+        }
+
+        /// Frequently used names
+        pub mod sym {
+            use super::{Preinterned, Symbol};
+            $(pub const $sym: Symbol = Symbol(Preinterned::$sym as u32);)*
+        }
+    };
+}
+
+symbols! {
+    keywords {
+        FOR: "for",
+        WHILE: "while",
+        IN: "in",
+        LET: "let",
+        USE: "use",
+        STRUCT: "struct",
+        ENUM: "enum",
+        IF: "if",
+        ELSE: "else",
+        RETURN: "return",
+        CONTINUE: "continue",
+        BREAK: "break",
+        AS: "as",
+        TRUE: "true",
+        FALSE: "false",
+        FN: "fn",
+        PUB: "pub",
+        MUT: "mut",
+    }
+    symbols {
+        INT: "Int",
+        FLOAT: "Float",
+        STRING: "String",
+        BOOL: "Bool",
+        MAIN: "main",
+        ALLOW: "allow",
+        WARN: "warn",
+        DENY: "deny",
+        EMPTY: "",
+    }
+}
@@ -1,13 +1,12 @@
 /// Imports
-use miette::NamedSource;
+use crate::{source::FileId, symbol::Symbol};
 use std::{
     fmt::Debug,
     ops::{Add, Range},
-    sync::Arc,
 };
 
 /// Represents token kind
-#[derive(Debug, PartialEq, Clone, Eq)]
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
 pub enum TokenKind {
     Use,       // `use` keyword
     For,       // `for` keyword
@@ -56,6 +55,7 @@ pub enum TokenKind {
     Gt,        // >
     Lt,        // <
     Colon,     // :
+    At,        // @
     Semi,      // ;
     DoubleEq,  // ==
     DoubleBar, // ||
@@ -64,6 +64,9 @@ pub enum TokenKind {
     Arrow,     // ->
     Number,    // any number
     String,    // "quoted text"
+    StrStart,  // "text{ — interpolated string start
+    StrMid,    // }text{ — interpolated string part between values
+    StrEnd,    // }text" — interpolated string end
     Id,        // identifier
     Bool,      // bool
 }
@@ -73,20 +76,36 @@ pub enum TokenKind {
 pub struct Token {
     pub span: Span,
     pub kind: TokenKind,
-    pub lexeme: String,
+    pub lexeme: Symbol,
 }
 
 /// Implementation
 impl Token {
     /// Creates new token
-    pub fn new(span: Span, kind: TokenKind, lexeme: String) -> Self {
+    pub fn new(span: Span, kind: TokenKind, lexeme: Symbol) -> Self {
         Self { span, kind, lexeme }
     }
 }
 
-/// Represents span
+/// Represents trivia kind: text between tokens,
+/// that doesn't affect the program
+#[derive(Debug, PartialEq, Clone, Copy, Eq, Hash)]
+pub enum TriviaKind {
+    Whitespace, // spaces, tabs and new lines
+    Comment,    // `# text` or `#[ text ]#`
+    DocComment, // `/// text`
+}
+
+/// Represents trivia
+#[derive(Debug, PartialEq, Clone, Eq)]
+pub struct Trivia {
+    pub span: Span,
+    pub kind: TriviaKind,
+}
+
+/// Represents span: file and byte range in it
 #[derive(Clone, PartialEq, Eq, Hash)]
-pub struct Span(pub Arc<NamedSource<String>>, pub Range<usize>);
+pub struct Span(pub FileId, pub Range<usize>);
 
 /// Debug implementation
 impl Debug for Span {
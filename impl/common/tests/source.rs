@@ -0,0 +1,58 @@
+/// Imports
+use common::source::{LineCol, SourceMap};
+
+#[test]
+fn line_col_counts_chars_from_byte_offsets() {
+    let mut sources = SourceMap::default();
+    let id = sources.add("main.bit", "let а = 1;\nlet b = \"ü\";\n".to_string());
+    let file = sources.get(id);
+
+    // `а` is cyrillic and takes two bytes
+    assert_eq!(file.line_col(4), LineCol { line: 1, column: 5 });
+    assert_eq!(file.line_col(6), LineCol { line: 1, column: 6 });
+    assert_eq!(file.line_col(12), LineCol { line: 2, column: 1 });
+    assert_eq!(
+        file.line_col(24),
+        LineCol {
+            line: 2,
+            column: 12
+        }
+    );
+
+    // Offsets inside chars and past the end are clamped
+    assert_eq!(file.line_col(5), LineCol { line: 1, column: 5 });
+    assert_eq!(file.line_col(100), LineCol { line: 3, column: 1 });
+}
+
+#[test]
+fn offset_is_inverse_of_line_col() {
+    let mut sources = SourceMap::default();
+    let text = "fn main() {\n    let ü = \"日本\";\n}\n";
+    let id = sources.add("main.bit", text.to_string());
+    let file = sources.get(id);
+
+    for (offset, _) in text.char_indices() {
+        assert_eq!(file.offset(file.line_col(offset)), Some(offset));
+    }
+    assert_eq!(
+        file.offset(LineCol {
+            line: 2,
+            column: 40
+        }),
+        None
+    );
+    assert_eq!(file.offset(LineCol { line: 9, column: 1 }), None);
+}
+
+#[test]
+fn files_are_found_by_id_and_name() {
+    let mut sources = SourceMap::default();
+    let a = sources.add("src/a.bit", String::new());
+    let b = sources.add("src/b.bit", String::new());
+
+    assert_ne!(a, b);
+    assert_eq!(sources.get(b).name(), "src/b.bit");
+    assert_eq!(sources.get(b).module_name(), "b");
+    assert_eq!(sources.find("src/a.bit").map(|file| file.id()), Some(a));
+    assert!(sources.find("src/c.bit").is_none());
+}
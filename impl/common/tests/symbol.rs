@@ -0,0 +1,51 @@
+/// Imports
+use common::symbol::{Symbol, kw, sym};
+
+#[test]
+fn equal_strings_share_symbol() {
+    let a = Symbol::intern("point");
+    let b = Symbol::intern(&String::from("point"));
+    assert_eq!(a, b);
+    assert_ne!(a, Symbol::intern("Point"));
+    assert_eq!(a.as_str(), "point");
+    assert_eq!(a.to_string(), "point");
+    assert_eq!(format!("{a:?}"), "\"point\"");
+}
+
+#[test]
+fn pre_interned_symbols_match_their_text() {
+    for (symbol, text) in [
+        (kw::FN, "fn"),
+        (kw::LET, "let"),
+        (kw::MUT, "mut"),
+        (sym::INT, "Int"),
+        (sym::MAIN, "main"),
+        (sym::EMPTY, ""),
+    ] {
+        assert_eq!(Symbol::intern(text), symbol);
+        assert_eq!(symbol, text);
+    }
+}
+
+#[test]
+fn symbols_are_shared_between_threads() {
+    let handles = (0..4)
+        .map(|t| {
+            std::thread::spawn(move || {
+                (0..200)
+                    .map(|i| {
+                        let name = format!("name{}", (i + t * 50) % 300);
+                        let symbol = Symbol::intern(&name);
+                        assert_eq!(symbol.as_str(), name);
+                        symbol
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        for symbol in handle.join().unwrap() {
+            assert_eq!(Symbol::intern(symbol.as_str()), symbol);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+/// Imports
+use crate::{
+    green::{GreenElement, GreenNode, GreenToken},
+    kind::SyntaxKind,
+};
+use macros::bug;
+use std::sync::Arc;
+
+/// Position in the builder, where a node could be started
+/// later, wrapping already built elements
+#[derive(Debug, Clone, Copy)]
+pub struct Checkpoint(usize);
+
+/// Builds green tree from the flat stream of the
+/// `start_node`, `token` and `finish_node` events
+#[derive(Default)]
+pub struct GreenBuilder {
+    /// Started nodes with indices of their first children
+    parents: Vec<(SyntaxKind, usize)>,
+
+    /// Built children of the started nodes
+    children: Vec<GreenElement>,
+}
+
+/// Implementation
+impl GreenBuilder {
+    /// Starts new node
+    pub fn start_node(&mut self, kind: SyntaxKind) {
+        self.parents.push((kind, self.children.len()));
+    }
+
+    /// Returns checkpoint in the current position
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.children.len())
+    }
+
+    /// Starts new node, that wraps everything built after the checkpoint.
+    /// Used by left-recursive productions, like binary expressions
+    pub fn start_node_at(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        let Checkpoint(first) = checkpoint;
+        if first > self.children.len() {
+            bug!("checkpoint is out of the built children")
+        }
+        if let Some((_, parent_first)) = self.parents.last()
+            && first < *parent_first
+        {
+            bug!("checkpoint is out of the current node")
+        }
+        self.parents.push((kind, first));
+    }
+
+    /// Finishes current node
+    pub fn finish_node(&mut self) {
+        let Some((kind, first)) = self.parents.pop() else {
+            bug!("no node to finish")
+        };
+        let children = self.children.split_off(first);
+        self.children
+            .push(GreenElement::Node(Arc::new(GreenNode::new(kind, children))));
+    }
+
+    /// Adds token to the current node
+    pub fn token(&mut self, kind: SyntaxKind, text: &str) {
+        self.children
+            .push(GreenElement::Token(Arc::new(GreenToken::new(kind, text))));
+    }
+
+    /// Finishes building, returns the root node
+    pub fn finish(mut self) -> Arc<GreenNode> {
+        if !self.parents.is_empty() {
+            bug!("unfinished nodes are left")
+        }
+        match self.children.pop() {
+            Some(GreenElement::Node(root)) if self.children.is_empty() => root,
+            _ => bug!("expected exactly one root node"),
+        }
+    }
+}
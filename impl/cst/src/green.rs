@@ -0,0 +1,127 @@
+/// Imports
+use crate::kind::SyntaxKind;
+use std::sync::Arc;
+
+/// Green token: kind and text of the token or trivia.
+///
+/// Green elements are immutable and know nothing about their
+/// position, so equal subtrees could be shared and reused.
+///
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub struct GreenToken {
+    kind: SyntaxKind,
+    text: String,
+}
+
+/// Implementation
+impl GreenToken {
+    /// Creates new green token
+    pub fn new(kind: SyntaxKind, text: &str) -> Self {
+        Self {
+            kind,
+            text: text.to_string(),
+        }
+    }
+
+    /// Returns token kind
+    pub fn kind(&self) -> &SyntaxKind {
+        &self.kind
+    }
+
+    /// Returns token text
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Returns text length in bytes
+    pub fn len(&self) -> usize {
+        self.text.len()
+    }
+
+    /// Returns true if token text is empty
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+}
+
+/// Green element: node or token
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub enum GreenElement {
+    Node(Arc<GreenNode>),
+    Token(Arc<GreenToken>),
+}
+
+/// Implementation
+impl GreenElement {
+    /// Returns element kind
+    pub fn kind(&self) -> &SyntaxKind {
+        match self {
+            GreenElement::Node(node) => node.kind(),
+            GreenElement::Token(token) => token.kind(),
+        }
+    }
+
+    /// Returns element text length in bytes
+    pub fn len(&self) -> usize {
+        match self {
+            GreenElement::Node(node) => node.len(),
+            GreenElement::Token(token) => token.len(),
+        }
+    }
+
+    /// Returns true if element text is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// Green node: kind and children of the node, with
+/// the cached length of the text they cover
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub struct GreenNode {
+    kind: SyntaxKind,
+    len: usize,
+    children: Vec<GreenElement>,
+}
+
+/// Implementation
+impl GreenNode {
+    /// Creates new green node
+    pub fn new(kind: SyntaxKind, children: Vec<GreenElement>) -> Self {
+        Self {
+            kind,
+            len: children.iter().map(|child| child.len()).sum(),
+            children,
+        }
+    }
+
+    /// Returns node kind
+    pub fn kind(&self) -> &SyntaxKind {
+        &self.kind
+    }
+
+    /// Returns node children
+    pub fn children(&self) -> &[GreenElement] {
+        &self.children
+    }
+
+    /// Returns length of the covered text in bytes
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns true if node covers no text
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Writes covered text to the buffer
+    pub fn write_text(&self, buffer: &mut String) {
+        for child in &self.children {
+            match child {
+                GreenElement::Node(node) => node.write_text(buffer),
+                GreenElement::Token(token) => buffer.push_str(token.text()),
+            }
+        }
+    }
+}
@@ -0,0 +1,115 @@
+/// Imports
+use common::token::{TokenKind, TriviaKind};
+
+/// Represents syntax kind of the concrete syntax tree element
+#[derive(Debug, PartialEq, Clone, Eq, Hash)]
+pub enum SyntaxKind {
+    /// Token
+    Token(TokenKind),
+
+    /// Trivia: whitespaces and comments
+    Trivia(TriviaKind),
+
+    /// Module, the root node
+    Module,
+
+    /// Top-level item with attributes and publicity
+    Item,
+
+    /// Item attribute: `@name(arg, ...)`
+    Attribute,
+
+    /// Struct: `struct Name<T> { fields }`
+    Struct,
+
+    /// Struct field: `name: Type`
+    Field,
+
+    /// Enum: `enum Name<T> { variants }`
+    Enum,
+
+    /// Enum variant: `Name(Type, ...)`
+    Variant,
+
+    /// Function: `fn name<T>(params) -> Type { ... }`
+    Function,
+
+    /// Use: `use path as name`
+    Use,
+
+    /// Generic params: `<T, K>`
+    GenericParams,
+
+    /// Generic args: `<Int, T>`
+    GenericArgs,
+
+    /// Function params: `(a: Int, b: Int)`
+    Params,
+
+    /// Function param: `a: Int`
+    Param,
+
+    /// Type hint
+    TypeHint,
+
+    /// Block: `{ stmts }`
+    Block,
+
+    /// Let statement: `let name: Type = expr;`
+    LetStmt,
+
+    /// Expression statement: `expr;`
+    ExprStmt,
+
+    /// Literal expression
+    LitExpr,
+
+    /// Id expression
+    IdExpr,
+
+    /// Parenthesized expression: `(expr)`
+    ParenExpr,
+
+    /// Field expression: `expr.name`
+    FieldExpr,
+
+    /// Call expression: `expr(args)`
+    CallExpr,
+
+    /// Call arguments: `(arg, arg)`
+    Args,
+
+    /// Unary expression: `-expr`
+    UnaryExpr,
+
+    /// Binary expression: `lhs + rhs`
+    BinExpr,
+
+    /// Assignment expression: `lhs = rhs`
+    AssignExpr,
+
+    /// If expression: `if cond { ... } else { ... }`
+    IfExpr,
+
+    /// Closure expression: `|params| expr`
+    ClosureExpr,
+
+    /// Closure params: `|a, b|`
+    ClosureParams,
+
+    /// Interpolated string: `"text {expr}"`
+    InterpExpr,
+}
+
+/// Implementation
+impl SyntaxKind {
+    /// Returns true if kind is a token or trivia kind
+    pub fn is_token(&self) -> bool {
+        matches!(self, SyntaxKind::Token(_) | SyntaxKind::Trivia(_))
+    }
+
+    /// Returns true if kind is a trivia kind
+    pub fn is_trivia(&self) -> bool {
+        matches!(self, SyntaxKind::Trivia(_))
+    }
+}
@@ -0,0 +1,5 @@
+/// Modules
+pub mod builder;
+pub mod green;
+pub mod kind;
+pub mod red;
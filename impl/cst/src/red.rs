@@ -0,0 +1,214 @@
+/// Imports
+use crate::{
+    green::{GreenElement, GreenNode, GreenToken},
+    kind::SyntaxKind,
+};
+use std::{
+    fmt::{self, Debug, Display},
+    ops::Range,
+    rc::Rc,
+    sync::Arc,
+};
+
+/// Red node data
+struct NodeData {
+    green: Arc<GreenNode>,
+    parent: Option<SyntaxNode>,
+    offset: usize,
+}
+
+/// Red node: a cursor over the green node, that knows its parent
+/// and absolute offset in the source. Red nodes are created lazily,
+/// while the tree is traversed, and are cheap to clone.
+///
+#[derive(Clone)]
+pub struct SyntaxNode(Rc<NodeData>);
+
+/// Red token: green token with its parent and absolute offset
+#[derive(Clone)]
+pub struct SyntaxToken {
+    green: Arc<GreenToken>,
+    parent: SyntaxNode,
+    offset: usize,
+}
+
+/// Red element: node or token
+#[derive(Clone, Debug)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+/// Implementation
+impl SyntaxNode {
+    /// Creates root node of the tree
+    pub fn new_root(green: Arc<GreenNode>) -> Self {
+        Self(Rc::new(NodeData {
+            green,
+            parent: None,
+            offset: 0,
+        }))
+    }
+
+    /// Returns node kind
+    pub fn kind(&self) -> &SyntaxKind {
+        self.0.green.kind()
+    }
+
+    /// Returns green node
+    pub fn green(&self) -> &Arc<GreenNode> {
+        &self.0.green
+    }
+
+    /// Returns parent node
+    pub fn parent(&self) -> Option<SyntaxNode> {
+        self.0.parent.clone()
+    }
+
+    /// Returns byte range of the covered text
+    pub fn range(&self) -> Range<usize> {
+        self.0.offset..self.0.offset + self.0.green.len()
+    }
+
+    /// Returns covered text
+    pub fn text(&self) -> String {
+        let mut buffer = String::with_capacity(self.0.green.len());
+        self.0.green.write_text(&mut buffer);
+        buffer
+    }
+
+    /// Returns child nodes and tokens
+    pub fn children_with_tokens(&self) -> impl Iterator<Item = SyntaxElement> + '_ {
+        let mut offset = self.0.offset;
+        self.0.green.children().iter().map(move |child| {
+            let start = offset;
+            offset += child.len();
+            match child {
+                GreenElement::Node(green) => SyntaxElement::Node(SyntaxNode(Rc::new(NodeData {
+                    green: green.clone(),
+                    parent: Some(self.clone()),
+                    offset: start,
+                }))),
+                GreenElement::Token(green) => SyntaxElement::Token(SyntaxToken {
+                    green: green.clone(),
+                    parent: self.clone(),
+                    offset: start,
+                }),
+            }
+        })
+    }
+
+    /// Returns child nodes
+    pub fn children(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.children_with_tokens().filter_map(|child| match child {
+            SyntaxElement::Node(node) => Some(node),
+            SyntaxElement::Token(_) => None,
+        })
+    }
+
+    /// Returns node and all its descendant nodes in preorder
+    pub fn descendants(&self) -> Vec<SyntaxNode> {
+        let mut nodes = vec![self.clone()];
+        for child in self.children() {
+            nodes.extend(child.descendants());
+        }
+        nodes
+    }
+
+    /// Returns all descendant tokens, including trivia, in source order
+    pub fn tokens(&self) -> Vec<SyntaxToken> {
+        let mut tokens = Vec::new();
+        for child in self.children_with_tokens() {
+            match child {
+                SyntaxElement::Node(node) => tokens.extend(node.tokens()),
+                SyntaxElement::Token(token) => tokens.push(token),
+            }
+        }
+        tokens
+    }
+
+    /// Returns token, that covers the byte offset
+    pub fn token_at_offset(&self, offset: usize) -> Option<SyntaxToken> {
+        for child in self.children_with_tokens() {
+            match child {
+                SyntaxElement::Node(node) if node.range().contains(&offset) => {
+                    return node.token_at_offset(offset);
+                }
+                SyntaxElement::Token(token) if token.range().contains(&offset) => {
+                    return Some(token);
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+/// Display implementation, writes the covered text
+impl Display for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text())
+    }
+}
+
+/// Debug implementation, writes the tree with
+/// kinds and ranges of the elements
+impl Debug for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fn write(f: &mut fmt::Formatter<'_>, node: &SyntaxNode, depth: usize) -> fmt::Result {
+            writeln!(
+                f,
+                "{:indent$}{:?}@{:?}",
+                "",
+                node.kind(),
+                node.range(),
+                indent = depth * 2
+            )?;
+            for child in node.children_with_tokens() {
+                match child {
+                    SyntaxElement::Node(node) => write(f, &node, depth + 1)?,
+                    SyntaxElement::Token(token) => {
+                        writeln!(f, "{:indent$}{token:?}", "", indent = (depth + 1) * 2)?
+                    }
+                }
+            }
+            Ok(())
+        }
+        write(f, self, 0)
+    }
+}
+
+/// Implementation
+impl SyntaxToken {
+    /// Returns token kind
+    pub fn kind(&self) -> &SyntaxKind {
+        self.green.kind()
+    }
+
+    /// Returns token text
+    pub fn text(&self) -> &str {
+        self.green.text()
+    }
+
+    /// Returns parent node
+    pub fn parent(&self) -> &SyntaxNode {
+        &self.parent
+    }
+
+    /// Returns byte range of the token text
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.green.len()
+    }
+
+    /// Returns true if token is trivia
+    pub fn is_trivia(&self) -> bool {
+        self.kind().is_trivia()
+    }
+}
+
+/// Debug implementation
+impl Debug for SyntaxToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}@{:?} {:?}", self.kind(), self.range(), self.text())
+    }
+}
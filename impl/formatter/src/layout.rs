@@ -0,0 +1,76 @@
+/// Imports
+use common::token::TriviaKind;
+use cst::{kind::SyntaxKind, red::SyntaxNode};
+use std::{
+    collections::{HashSet, VecDeque},
+    ops::Range,
+};
+
+/// Comment or doc comment of the source
+pub(crate) struct Comment {
+    /// Byte range of the comment
+    pub(crate) range: Range<usize>,
+
+    /// Comment text without trailing whitespace
+    pub(crate) text: String,
+
+    /// Whether comment follows a token on the same line
+    pub(crate) trailing: bool,
+}
+
+/// Parts of the source, that are not presented in the AST,
+/// collected from the concrete syntax tree: comments and parens.
+pub(crate) struct Layout {
+    /// Comments in the source order
+    pub(crate) comments: VecDeque<Comment>,
+
+    /// Ranges of the parenthesized expressions, without parens
+    pub(crate) parens: HashSet<Range<usize>>,
+}
+
+/// Implementation
+impl Layout {
+    /// Collects layout of the syntax tree
+    pub(crate) fn new(root: &SyntaxNode) -> Self {
+        // Comments, with a flag of token on the current line
+        let mut comments = VecDeque::new();
+        let mut on_line = false;
+        for token in root.tokens() {
+            match token.kind() {
+                SyntaxKind::Trivia(TriviaKind::Whitespace) => {
+                    on_line = on_line && !token.text().contains('\n');
+                }
+                SyntaxKind::Trivia(TriviaKind::Comment | TriviaKind::DocComment) => {
+                    comments.push_back(Comment {
+                        range: token.range(),
+                        text: token.text().trim_end().to_string(),
+                        trailing: on_line,
+                    });
+                }
+                _ => on_line = true,
+            }
+        }
+
+        // Parens, ranges are taken between the first
+        // and the last significant tokens inside them
+        let parens = root
+            .descendants()
+            .into_iter()
+            .filter(|node| *node.kind() == SyntaxKind::ParenExpr)
+            .filter_map(|node| {
+                let tokens = node
+                    .tokens()
+                    .into_iter()
+                    .filter(|token| !token.is_trivia())
+                    .collect::<Vec<_>>();
+                match tokens.as_slice() {
+                    [_, first, .., last, _] => Some(first.range().start..last.range().end),
+                    [_, inner, _] => Some(inner.range()),
+                    _ => None,
+                }
+            })
+            .collect();
+
+        Self { comments, parens }
+    }
+}
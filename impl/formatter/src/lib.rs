@@ -0,0 +1,17 @@
+/// Modules
+mod layout;
+mod printer;
+
+/// Imports
+use crate::{layout::Layout, printer::Printer};
+use common::source::SourceFile;
+use lexer::Lexer;
+use parser::Parser;
+
+/// Formats source file in the canonical style. Comments are
+/// preserved, formatting the result again doesn't change it.
+/// Syntax errors are raised as fatal errors
+pub fn format(file: &SourceFile) -> String {
+    let (module, cst) = Parser::new(file.source(), Lexer::new(file)).parse_with_cst();
+    Printer::new(file.text(), Layout::new(&cst)).module(&module)
+}
@@ -0,0 +1,504 @@
+/// Imports
+use crate::layout::Layout;
+use ast::{
+    atom::{Param, Publicity, TypeHint},
+    expr::{BinOp, Expr, ExprKind, Lit, UnOp},
+    item::{Attribute, Enum, Function, Item, ItemKind, Module, Struct, Use, UseKind},
+    stmt::{Block, Stmt, StmtKind},
+};
+use common::symbol::Symbol;
+use std::ops::Range;
+
+/// Indentation unit
+const INDENT: &str = "    ";
+
+/// Binding power of the expressions, from the loosest to the tightest.
+/// Closures are the loosest, since their bodies take the rest of the expression
+const CLOSURE: u8 = 0;
+const ASSIGN: u8 = 1;
+const LOGICAL_OR: u8 = 2;
+const LOGICAL_AND: u8 = 3;
+const BITWISE_OR: u8 = 4;
+const BITWISE_XOR: u8 = 5;
+const BITWISE_AND: u8 = 6;
+const EQUALITY: u8 = 7;
+const COMPARE: u8 = 8;
+const TERM: u8 = 9;
+const FACTOR: u8 = 10;
+const UNARY: u8 = 11;
+const ATOM: u8 = 12;
+
+/// Returns binary operator text and its binding power
+fn bin_op(op: &BinOp) -> (&'static str, u8) {
+    match op {
+        BinOp::Add => ("+", TERM),
+        BinOp::Sub => ("-", TERM),
+        BinOp::Mul => ("*", FACTOR),
+        BinOp::Div => ("/", FACTOR),
+        BinOp::Mod => ("%", FACTOR),
+        BinOp::And => ("&&", LOGICAL_AND),
+        BinOp::Or => ("||", LOGICAL_OR),
+        BinOp::BitAnd => ("&", BITWISE_AND),
+        BinOp::BitOr => ("|", BITWISE_OR),
+        BinOp::Xor => ("^", BITWISE_XOR),
+        BinOp::Eq => ("==", EQUALITY),
+        BinOp::Ne => ("!=", EQUALITY),
+        BinOp::Ge => (">=", COMPARE),
+        BinOp::Le => ("<=", COMPARE),
+        BinOp::Gt => (">", COMPARE),
+        BinOp::Lt => ("<", COMPARE),
+    }
+}
+
+/// Returns binding power of the expression
+fn binding_power(expr: &Expr) -> u8 {
+    match &expr.kind {
+        ExprKind::Closure(..) => CLOSURE,
+        ExprKind::Assign(..) => ASSIGN,
+        ExprKind::Bin(op, ..) => bin_op(op).1,
+        ExprKind::Unary(..) => UNARY,
+        _ => ATOM,
+    }
+}
+
+/// Prints type hint
+fn type_hint(hint: &TypeHint) -> String {
+    let args = |args: &[TypeHint]| match args {
+        [] => String::new(),
+        args => format!("<{}>", type_hints(args)),
+    };
+    match hint {
+        TypeHint::Local { name, args: it, .. } => format!("{name}{}", args(it)),
+        TypeHint::Module {
+            module,
+            name,
+            args: it,
+            ..
+        } => format!("{module}.{name}{}", args(it)),
+        TypeHint::Function { params, ret, .. } => {
+            format!("fn({}) -> {}", type_hints(params), type_hint(ret))
+        }
+        TypeHint::Unit(_) => "()".to_string(),
+        TypeHint::Infer => String::new(),
+    }
+}
+
+/// Prints comma separated type hints
+fn type_hints(hints: &[TypeHint]) -> String {
+    hints.iter().map(type_hint).collect::<Vec<_>>().join(", ")
+}
+
+/// Prints comma separated names
+fn names(names: &[Symbol]) -> String {
+    names
+        .iter()
+        .map(Symbol::as_str)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Prints generic params
+fn generics(generics: &[Symbol]) -> String {
+    match generics {
+        [] => String::new(),
+        generics => format!("<{}>", names(generics)),
+    }
+}
+
+/// Pretty-printer of the module in the canonical style:
+/// four spaces indentation, one item, statement, field
+/// or variant per line, trailing commas in the multi-line lists.
+///
+/// Comments are emitted before the next item, statement, field or
+/// variant, except the trailing ones, that stay at the end of the line.
+/// Up to one blank line between statements, fields and variants
+/// is kept, items are always separated by a blank line, except
+/// the consecutive `use` items. Literals are printed as written.
+///
+pub(crate) struct Printer<'s> {
+    /// Source text
+    src: &'s str,
+
+    /// Source layout
+    layout: Layout,
+
+    /// Printed text
+    out: String,
+
+    /// Current indentation level
+    indent: usize,
+
+    /// Whether nothing is printed in the current block yet
+    fresh: bool,
+}
+
+/// Implementation
+impl<'s> Printer<'s> {
+    /// Creates printer
+    pub(crate) fn new(src: &'s str, layout: Layout) -> Self {
+        Self {
+            src,
+            layout,
+            out: String::new(),
+            indent: 0,
+            fresh: true,
+        }
+    }
+
+    /// Writes text
+    fn write(&mut self, text: &str) {
+        self.out.push_str(text);
+    }
+
+    /// Writes indentation of the new line
+    fn write_indent(&mut self) {
+        self.fresh = false;
+        for _ in 0..self.indent {
+            self.out.push_str(INDENT);
+        }
+    }
+
+    /// Writes blank line, unless it's the start of
+    /// the block or the blank line is already written
+    fn blank_line(&mut self) {
+        if !self.fresh && !self.out.ends_with("\n\n") {
+            self.out.push('\n');
+        }
+    }
+
+    /// Returns true if the source has a blank line before the offset
+    fn blank_before(&self, offset: usize) -> bool {
+        self.src[..offset]
+            .chars()
+            .rev()
+            .take_while(|ch| ch.is_whitespace())
+            .filter(|ch| *ch == '\n')
+            .count()
+            > 1
+    }
+
+    /// Returns true if there are comments before the offset
+    fn has_comments_before(&self, offset: usize) -> bool {
+        self.layout
+            .comments
+            .front()
+            .is_some_and(|comment| comment.range.start < offset)
+    }
+
+    /// Writes comments before the offset, each on its own line
+    fn comments_before(&mut self, offset: usize) {
+        while self.has_comments_before(offset) {
+            let Some(comment) = self.layout.comments.pop_front() else {
+                break;
+            };
+            if self.blank_before(comment.range.start) {
+                self.blank_line();
+            }
+            self.write_indent();
+            self.write(&comment.text);
+            self.write("\n");
+        }
+    }
+
+    /// Ends the line, that is printed up to the `end` offset.
+    /// Trailing comments between `end` and `next` offsets are
+    /// kept on the line, the other ones are left for the next line
+    fn line_end(&mut self, end: usize, next: usize) {
+        let mut idx = 0;
+        while let Some(comment) = self.layout.comments.get(idx) {
+            if comment.range.start >= next {
+                break;
+            }
+            if comment.trailing && comment.range.start >= end {
+                let text = self.layout.comments.remove(idx).map(|it| it.text);
+                self.write(" ");
+                self.write(&text.unwrap_or_default());
+            } else {
+                idx += 1;
+            }
+        }
+        self.write("\n");
+    }
+
+    /// Writes element of the block, starting at the offset:
+    /// comments before it and the blank line, if the source has one
+    fn element_start(&mut self, offset: usize) {
+        self.comments_before(offset);
+        if self.blank_before(offset) {
+            self.blank_line();
+        }
+        self.write_indent();
+    }
+
+    /// Writes `{ .. }` lines, with elements printed by `element`.
+    /// Elements are given by their source ranges, `open` is the offset
+    /// after the opening brace and `close` is the offset of the closing one
+    fn braced<T>(
+        &mut self,
+        open: usize,
+        close: usize,
+        elements: &[T],
+        range: impl Fn(&T) -> Range<usize>,
+        mut element: impl FnMut(&mut Self, &T),
+    ) {
+        self.write("{");
+        if elements.is_empty() && !self.has_comments_before(close) {
+            self.write("}");
+            return;
+        }
+
+        self.indent += 1;
+        let start = |idx: usize| elements.get(idx).map_or(close, |it| range(it).start);
+        self.line_end(open, start(0));
+        self.fresh = true;
+        for (idx, it) in elements.iter().enumerate() {
+            let it_range = range(it);
+            self.element_start(it_range.start);
+            element(self, it);
+            self.line_end(it_range.end, start(idx + 1));
+        }
+        self.comments_before(close);
+        self.indent -= 1;
+        self.write_indent();
+        self.write("}");
+    }
+
+    /// Prints block
+    fn block(&mut self, block: &Block) {
+        let (open, close) = (block.span.1.start + 1, block.span.1.end - 1);
+        self.braced(
+            open,
+            close,
+            &block.stmts,
+            |stmt| stmt.span.1.clone(),
+            |p, stmt| p.stmt(stmt),
+        );
+    }
+
+    /// Prints statement
+    fn stmt(&mut self, stmt: &Stmt) {
+        match &stmt.kind {
+            StmtKind::Let(name, _, hint, value) => {
+                self.write("let ");
+                self.write(name.as_str());
+                if *hint != TypeHint::Infer {
+                    self.write(": ");
+                    self.write(&type_hint(hint));
+                }
+                self.write(" = ");
+                self.expr(value, CLOSURE);
+                self.write(";");
+            }
+            StmtKind::Expr(expr) => self.expr(expr, CLOSURE),
+            StmtKind::Semi(expr) => {
+                self.expr(expr, CLOSURE);
+                self.write(";");
+            }
+        }
+    }
+
+    /// Prints expression, that binds at least as tight as `power`.
+    /// Parens of the source are kept, the missing ones are added
+    fn expr(&mut self, expr: &Expr, power: u8) {
+        let parens = binding_power(expr) < power || self.layout.parens.contains(&expr.span.1);
+        if parens {
+            self.write("(");
+        }
+        match &expr.kind {
+            ExprKind::Lit(Lit::Number(number)) => self.write(number),
+            ExprKind::Lit(Lit::Bool(value)) => self.write(&value.to_string()),
+            ExprKind::Lit(Lit::String(_)) | ExprKind::Interp(_) => {
+                let src = self.src;
+                self.write(&src[expr.span.1.clone()]);
+            }
+            ExprKind::Unary(op, value) => {
+                self.write(match op {
+                    UnOp::Neg => "-",
+                    UnOp::Bang => "!",
+                    UnOp::Deref => "*",
+                });
+                self.expr(value, UNARY);
+            }
+            ExprKind::Bin(op, left, right) => {
+                let (text, power) = bin_op(op);
+                self.expr(left, power);
+                self.write(&format!(" {text} "));
+                // Right operand of the comparison is parsed as a factor
+                self.expr(right, if power == COMPARE { FACTOR } else { power + 1 });
+            }
+            ExprKind::If(cond, then, branch) => {
+                self.write("if ");
+                self.expr(cond, CLOSURE);
+                self.write(" ");
+                self.expr(then, CLOSURE);
+                if let Some(branch) = branch {
+                    self.write(" else ");
+                    self.expr(branch, CLOSURE);
+                }
+            }
+            ExprKind::Call(callee, args) => {
+                self.expr(callee, ATOM);
+                self.write("(");
+                for (idx, arg) in args.iter().enumerate() {
+                    if idx > 0 {
+                        self.write(", ");
+                    }
+                    self.expr(arg, CLOSURE);
+                }
+                self.write(")");
+            }
+            ExprKind::Id(name) => self.write(name.as_str()),
+            ExprKind::Field(base, name) => {
+                self.expr(base, ATOM);
+                self.write(".");
+                self.write(name.as_str());
+            }
+            ExprKind::Closure(params, body) => {
+                self.write(&format!("|{}| ", names(params)));
+                self.expr(body, CLOSURE);
+            }
+            ExprKind::Assign(target, value) => {
+                self.expr(target, ASSIGN);
+                self.write(" = ");
+                self.expr(value, LOGICAL_OR);
+            }
+            ExprKind::Block(block) => self.block(block),
+        }
+        if parens {
+            self.write(")");
+        }
+    }
+
+    /// Prints attribute
+    fn attribute(&mut self, attr: &Attribute) {
+        let args = attr
+            .args
+            .iter()
+            .map(|arg| arg.name.as_str())
+            .collect::<Vec<_>>();
+        self.write(&format!("@{}({})", attr.name, args.join(", ")));
+    }
+
+    /// Prints function param
+    fn param(param: &Param) -> String {
+        format!("{}: {}", param.name, type_hint(&param.hint))
+    }
+
+    /// Prints struct, `open` and `close` are offsets of its braces
+    fn struct_item(&mut self, it: &Struct, open: usize, close: usize) {
+        self.write(&format!("struct {}{} ", it.name, generics(&it.generics)));
+        self.braced(
+            open,
+            close,
+            &it.fields,
+            |field| field.span.1.clone(),
+            |p, field| p.write(&format!("{}: {},", field.name, type_hint(&field.hint))),
+        );
+    }
+
+    /// Prints enum, `open` and `close` are offsets of its braces
+    fn enum_item(&mut self, it: &Enum, open: usize, close: usize) {
+        self.write(&format!("enum {}{} ", it.name, generics(&it.generics)));
+        self.braced(
+            open,
+            close,
+            &it.variants,
+            |variant| variant.span.1.clone(),
+            |p, variant| match variant.params.as_slice() {
+                [] => p.write(&format!("{},", variant.name)),
+                params => p.write(&format!("{}({}),", variant.name, type_hints(params))),
+            },
+        );
+    }
+
+    /// Prints function
+    fn fn_item(&mut self, it: &Function) {
+        let params = it.params.iter().map(Self::param).collect::<Vec<_>>();
+        self.write(&format!(
+            "fn {}{}({}) ",
+            it.name,
+            generics(&it.generics),
+            params.join(", ")
+        ));
+        if it.ret != TypeHint::Infer {
+            self.write(&format!("-> {} ", type_hint(&it.ret)));
+        }
+        self.block(&it.block);
+    }
+
+    /// Prints use
+    fn use_item(&mut self, it: &Use) {
+        self.write(&format!("use {}", it.path));
+        match &it.kind {
+            UseKind::As(name) => self.write(&format!(" as {name}")),
+            UseKind::For(items) => self.write(&format!(" for {}", names(items))),
+            UseKind::Just => {}
+        }
+    }
+
+    /// Returns offset, where the item starts: its first attribute or itself
+    fn item_start(item: &Item) -> usize {
+        item.attrs
+            .first()
+            .map_or(item.span.1.start, |attr| attr.span.1.start)
+    }
+
+    /// Prints item, followed by the item at the `next` offset
+    fn item(&mut self, item: &Item, next: usize) {
+        // Attributes
+        for (idx, attr) in item.attrs.iter().enumerate() {
+            let attr_next = item
+                .attrs
+                .get(idx + 1)
+                .map_or(item.span.1.start, |it| it.span.1.start);
+            self.element_start(attr.span.1.start);
+            self.attribute(attr);
+            self.line_end(attr.span.1.end, attr_next);
+        }
+
+        // Item itself, the braces of the structs and
+        // enums are the first and the last chars of their bodies
+        self.element_start(item.span.1.start);
+        if item.publicity == Publicity::Pub {
+            self.write("pub ");
+        }
+        let open = self.src[item.span.1.clone()]
+            .find('{')
+            .map_or(item.span.1.start, |idx| item.span.1.start + idx + 1);
+        let close = item.span.1.end - 1;
+        match &item.kind {
+            ItemKind::Struct(it) => self.struct_item(it, open, close),
+            ItemKind::Enum(it) => self.enum_item(it, open, close),
+            ItemKind::Function(it) => self.fn_item(it),
+            ItemKind::Use(it) => self.use_item(it),
+        }
+        self.line_end(item.span.1.end, next);
+    }
+
+    /// Prints module, returns the printed text
+    pub(crate) fn module(mut self, module: &Module) -> String {
+        for (idx, item) in module.items.iter().enumerate() {
+            let start = Self::item_start(item);
+            let next = module
+                .items
+                .get(idx + 1)
+                .map_or(self.src.len(), Self::item_start);
+
+            // Consecutive uses are grouped, while
+            // other items are separated by a blank line
+            let lead = match self.layout.comments.front() {
+                Some(comment) if comment.range.start < start => comment.range.start,
+                _ => start,
+            };
+            let grouped = idx > 0
+                && matches!(item.kind, ItemKind::Use(_))
+                && matches!(module.items[idx - 1].kind, ItemKind::Use(_));
+            if idx > 0 && (!grouped || self.blank_before(lead)) {
+                self.blank_line();
+            }
+            self.item(item, next);
+        }
+        self.comments_before(self.src.len());
+        self.out
+    }
+}
@@ -0,0 +1,130 @@
+/// Imports
+use common::{source::SourceMap, token::TriviaKind};
+use cst::{kind::SyntaxKind, red::SyntaxNode};
+use lexer::Lexer;
+use parser::Parser;
+use std::{fs, path::Path};
+
+/// Formats the source text
+fn format(text: &str) -> String {
+    let mut sources = SourceMap::default();
+    let id = sources.add("test.bit", text.to_string());
+    formatter::format(sources.get(id))
+}
+
+/// Returns sorted comments of the source text
+fn comments(text: &str) -> Vec<String> {
+    let mut sources = SourceMap::default();
+    let id = sources.add("test.bit", text.to_string());
+    let file = sources.get(id);
+    let root: SyntaxNode = Parser::new(file.source(), Lexer::new(file))
+        .parse_with_cst()
+        .1;
+    let mut comments = root
+        .tokens()
+        .into_iter()
+        .filter(|token| {
+            matches!(
+                token.kind(),
+                SyntaxKind::Trivia(TriviaKind::Comment | TriviaKind::DocComment)
+            )
+        })
+        .map(|token| token.text().trim_end().to_string())
+        .collect::<Vec<_>>();
+    comments.sort();
+    comments
+}
+
+#[test]
+fn corpus_formatting_is_idempotent() {
+    let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/corpus");
+    let mut entries = fs::read_dir(corpus)
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert!(!entries.is_empty());
+
+    for path in entries {
+        let text = fs::read_to_string(&path).unwrap();
+        let formatted = format(&text);
+        assert_eq!(
+            format(&formatted),
+            formatted,
+            "formatting of `{}` is not idempotent",
+            path.display()
+        );
+        assert_eq!(
+            comments(&formatted),
+            comments(&text),
+            "comments of `{}` are not preserved",
+            path.display()
+        );
+    }
+}
+
+#[test]
+fn items_are_formatted_canonically() {
+    let text = r#"use io
+use math as m
+/// A point
+struct Point<T>{x: T,   # horizontal
+y:T}
+enum Shape { Dot(Point<Int>), Empty }
+fn main() -> Int { let p=Point(1,2);
+
+
+    let f = |x| x+1  ;
+    if p.x>=2 { f(p.x) } else { 0 } }
+"#;
+    assert_eq!(
+        format(text),
+        r#"use io
+use math as m
+
+/// A point
+struct Point<T> {
+    x: T, # horizontal
+    y: T,
+}
+
+enum Shape {
+    Dot(Point<Int>),
+    Empty,
+}
+
+fn main() -> Int {
+    let p = Point(1, 2);
+
+    let f = |x| x + 1;
+    if p.x >= 2 {
+        f(p.x)
+    } else {
+        0
+    }
+}
+"#
+    );
+}
+
+#[test]
+fn parens_are_kept_and_added_where_required() {
+    let text = "fn main() {\n    let a = ((1 + 2)) * 3;\n    let b = 1 < (2 + 3);\n    let c = -(|x| x);\n}\n";
+    assert_eq!(
+        format(text),
+        "fn main() {\n    let a = (1 + 2) * 3;\n    let b = 1 < (2 + 3);\n    let c = -(|x| x);\n}\n"
+    );
+}
+
+#[test]
+fn formatted_source_is_unchanged() {
+    let text = "fn main() {\n    # comment\n    let a = 1;\n}\n";
+    assert_eq!(format(text), text);
+    assert_eq!(format(""), "");
+}
+
+#[test]
+fn escaped_braces_round_trip() {
+    let text = "fn main() {\n    let a = \"{{}}\";\n    let b = \"a }} b {{c}}\";\n    let c = \"{{{a}}}\";\n}\n";
+    assert_eq!(format(text), text);
+}
@@ -8,7 +8,7 @@ use thiserror::Error;
 pub enum LexError<'a> {
     /// Unexpected char
     #[error("unexpected character `{ch}`.")]
-    #[diagnostic(code(lex::unexpected_char))]
+    #[diagnostic(code(E0101))]
     UnexpectedChar {
         ch: char,
         #[source_code]
@@ -18,16 +18,17 @@ pub enum LexError<'a> {
     },
     /// Unclosed string quotes
     #[error("found unclosed string quotes.")]
-    #[diagnostic(code(lex::unclosed_string_quotes))]
+    #[diagnostic(code(E0102))]
     UnclosedStringQuotes {
         #[source_code]
         src: Arc<NamedSource<String>>,
-        #[label("close string quotes by appending missed quote `\"`.")]
+        #[label("close string quotes by appending missed `{closing}`.")]
         span: SourceSpan,
+        closing: String,
     },
     /// Unclosed char quotes
     #[error("found unclosed char quotes.")]
-    #[diagnostic(code(lex::unclosed_char_quotes))]
+    #[diagnostic(code(E0103))]
     UnclosedCharQuotes {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -36,7 +37,7 @@ pub enum LexError<'a> {
     },
     /// Invalid float
     #[error("invalid float number.")]
-    #[diagnostic(code(lex::invalid_float_number), severity(bug))]
+    #[diagnostic(code(E0104), severity(Error))]
     InvalidFloat {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -45,7 +46,7 @@ pub enum LexError<'a> {
     },
     /// Invalid escape sequence
     #[error("invalid escape sequence.")]
-    #[diagnostic(code(lex::invalid_escape_sequence), help("{cause}"))]
+    #[diagnostic(code(E0105), help("{cause}"))]
     InvalidEscapeSequence {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -55,11 +56,32 @@ pub enum LexError<'a> {
     },
     /// Unknown escape sequence
     #[error("unknown escape sequence.")]
-    #[diagnostic(code(lex::unknown_escape_sequence))]
+    #[diagnostic(code(E0106))]
     UnknownEscapeSequence {
         #[source_code]
         src: Arc<NamedSource<String>>,
         #[label("this escape sequence isn't valid.")]
         span: SourceSpan,
     },
+    /// Unmatched brace in the string
+    #[error("unmatched `}}` in string.")]
+    #[diagnostic(code(E0107), help("escape the brace by doubling it: `}}}}`"))]
+    UnmatchedStringBrace {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("this brace doesn't close an interpolation.")]
+        span: SourceSpan,
+    },
+    /// Multi-line string without a new line after the opening quotes
+    #[error("multi-line string must start with a new line.")]
+    #[diagnostic(
+        code(E0108),
+        help("move the text after the opening `\"\"\"` to the next line")
+    )]
+    MultilineStringStart {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("expected a new line after these quotes.")]
+        span: SourceSpan,
+    },
 }
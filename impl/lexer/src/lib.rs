@@ -4,53 +4,138 @@ mod errors;
 
 /// Imports
 use crate::errors::LexError;
-use common::token::{Span, Token, TokenKind};
+use common::{
+    source::{FileId, SourceFile},
+    symbol::{Symbol, kw},
+    token::{Span, Token, TokenKind, Trivia, TriviaKind},
+};
 use macros::bail;
 use miette::NamedSource;
-use std::{str::Chars, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    str::Chars,
+    sync::Arc,
+};
+use unicode_normalization::UnicodeNormalization;
+
+/// String quotes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Quotes {
+    /// `"text"`
+    Single,
+
+    /// `"""` multi-line text `"""`, with the indentation,
+    /// stripped from every line
+    Triple(usize),
+}
+
+/// Implementation
+impl Quotes {
+    /// Closing quotes text
+    fn closing(self) -> &'static str {
+        match self {
+            Quotes::Single => "\"",
+            Quotes::Triple(_) => "\"\"\"",
+        }
+    }
+}
 
 /// Represents lexer
 pub struct Lexer<'s> {
-    /// Current file source
+    /// Current file source, used by diagnostics
     source: Arc<NamedSource<String>>,
 
+    /// Current file id
+    file: FileId,
+
     /// Lexer source
     src: Chars<'s>,
 
-    /// Current and next
+    /// Byte offset of the current char, and current and next chars
     idx: usize,
     current: Option<char>,
     next: Option<char>,
+
+    /// Distinct identifiers, except keywords, with their first occurrences
+    idents: Vec<(Symbol, Span)>,
+    seen: HashSet<Symbol>,
+
+    /// Quotes and brace depths of the open string interpolations
+    interps: Vec<(Quotes, usize)>,
+
+    /// Doc comment lines, that are not attached yet
+    doc: Vec<String>,
+
+    /// Doc comments by the start offsets of the following tokens
+    docs: HashMap<usize, String>,
+
+    /// Skipped trivia, that is not taken yet
+    trivia: VecDeque<Trivia>,
 }
 
 /// Implementation
 impl<'s> Lexer<'s> {
     /// Creates new lexer
-    pub fn new(file: Arc<NamedSource<String>>, source: &'s str) -> Self {
-        let mut chars = source.chars();
+    pub fn new(file: &'s SourceFile) -> Self {
+        let mut chars = file.text().chars();
         let (current, next) = (chars.next(), chars.next());
         Self {
-            source: file,
+            source: file.source(),
+            file: file.id(),
             src: chars,
             current,
             next,
             idx: 0,
+            idents: Vec::new(),
+            seen: HashSet::new(),
+            interps: Vec::new(),
+            doc: Vec::new(),
+            docs: HashMap::new(),
+            trivia: VecDeque::new(),
         }
     }
 
+    /// Returns id of the file being lexed
+    pub fn file(&self) -> FileId {
+        self.file
+    }
+
+    /// Returns distinct identifiers, except keywords,
+    /// lexed so far, with their first occurrences
+    pub fn idents(&self) -> &[(Symbol, Span)] {
+        &self.idents
+    }
+
+    /// Takes doc comment, attached to the token, that starts at `offset`
+    pub fn take_docs(&mut self, offset: usize) -> Option<String> {
+        self.docs.remove(&offset)
+    }
+
+    /// Takes skipped trivia, that precedes `offset`
+    pub fn take_trivia(&mut self, offset: usize) -> Vec<Trivia> {
+        let amount = self
+            .trivia
+            .iter()
+            .take_while(|it| it.span.1.start < offset)
+            .count();
+        self.trivia.drain(..amount).collect()
+    }
+
     fn advance(&mut self) {
+        if let Some(ch) = self.current {
+            self.idx += ch.len_utf8();
+        }
         self.current = self.next.take();
         self.next = self.src.next();
-        self.idx += 1;
     }
 
     /// Advances char and returns token
     fn advance_with(&mut self, tk: TokenKind, lexeme: &str) -> Token {
         self.advance();
         Token::new(
-            Span(self.source.clone(), self.idx - 1..self.idx),
+            Span(self.file, self.idx - 1..self.idx),
             tk,
-            lexeme.to_string(),
+            Symbol::intern(lexeme),
         )
     }
 
@@ -59,18 +144,14 @@ impl<'s> Lexer<'s> {
         self.advance();
         self.advance();
         Token::new(
-            Span(self.source.clone(), self.idx - 2..self.idx),
+            Span(self.file, self.idx - 2..self.idx),
             tk,
-            lexeme.to_string(),
+            Symbol::intern(lexeme),
         )
     }
 
-    /// Scans unicode codepoint.
-    fn scan_unicode_codepoint(&mut self, small: bool) -> char {
-        // Bumping `u`
-        let start_location = self.idx;
-        self.advance();
-
+    /// Scans unicode codepoint, `u` at `start_location` is already bumped.
+    fn scan_unicode_codepoint(&mut self, start_location: usize, small: bool) -> char {
         // Calculating amount of hex digits
         let hex_digits_amount = if small { 4 } else { 8 };
 
@@ -114,7 +195,7 @@ impl<'s> Lexer<'s> {
         }
         self.advance();
 
-        let result = match char::from_u32(u32::from_str_radix(&buffer, 16).expect("Invalid hex")) {
+        match char::from_u32(u32::from_str_radix(&buffer, 16).expect("Invalid hex")) {
             Some(c) => c,
             None => {
                 bail!(LexError::InvalidEscapeSequence {
@@ -123,16 +204,11 @@ impl<'s> Lexer<'s> {
                     cause: "failed to convert `unciode char` into `u32`."
                 })
             }
-        };
-        result
+        }
     }
 
-    /// Scans byte codepoint.
-    fn scan_byte_codepoint(&mut self) -> char {
-        // Bumping `x`
-        let start_location = self.idx;
-        self.advance();
-
+    /// Scans byte codepoint, `x` at `start_location` is already bumped.
+    fn scan_byte_codepoint(&mut self, start_location: usize) -> char {
         if self.current != Some('{') {
             bail!(LexError::InvalidEscapeSequence {
                 src: self.source.clone(),
@@ -173,7 +249,7 @@ impl<'s> Lexer<'s> {
         }
         self.advance();
 
-        let result = match char::from_u32(u32::from_str_radix(&buffer, 16).expect("Invalid hex")) {
+        match char::from_u32(u32::from_str_radix(&buffer, 16).expect("Invalid hex")) {
             Some(c) => c,
             None => {
                 bail!(LexError::InvalidEscapeSequence {
@@ -182,8 +258,7 @@ impl<'s> Lexer<'s> {
                     cause: "failed to convert `unciode char` into `u32`."
                 })
             }
-        };
-        result
+        }
     }
 
     /// Advances escape sequence.
@@ -192,6 +267,7 @@ impl<'s> Lexer<'s> {
         self.advance();
 
         // Reading next character.
+        let start = self.idx;
         let ch = self.current;
         self.advance();
 
@@ -202,12 +278,12 @@ impl<'s> Lexer<'s> {
             Some('"') => '"',
             Some('`') => '`',
             Some('\\') => '\\',
-            Some('u') => self.scan_unicode_codepoint(true),
-            Some('U') => self.scan_unicode_codepoint(false),
-            Some('x') => self.scan_byte_codepoint(),
+            Some('u') => self.scan_unicode_codepoint(start, true),
+            Some('U') => self.scan_unicode_codepoint(start, false),
+            Some('x') => self.scan_byte_codepoint(start),
             _ => bail!(LexError::UnknownEscapeSequence {
                 src: self.source.clone(),
-                span: (self.idx - 1..self.idx).into()
+                span: (start..self.idx).into()
             }),
         }
     }
@@ -215,43 +291,298 @@ impl<'s> Lexer<'s> {
     /// Advances string
     fn advance_string(&mut self) -> Token {
         // Advancing `"`
+        let start = self.idx;
         self.advance();
+        self.advance_string_part(start, true, Quotes::Single)
+    }
+
+    /// Advances multi-line string. Its text starts from the next line
+    /// after the opening quotes, and the indentation of the closing
+    /// quotes is stripped from every line:
+    ///
+    /// ```text
+    /// let s = """
+    ///     first line
+    ///       second line
+    ///     """;
+    /// ```
+    ///
+    /// is `"first line\n  second line"`.
+    ///
+    fn advance_multiline_string(&mut self) -> Token {
+        // Advancing `"""`
         let start = self.idx;
+        self.advance();
+        self.advance();
+        self.advance();
 
+        // Advancing new line
+        if self.current == Some('\r') && self.next == Some('\n') {
+            self.advance();
+        }
+        if self.current != Some('\n') {
+            bail!(LexError::MultilineStringStart {
+                src: self.source.clone(),
+                span: (start..self.idx).into(),
+            })
+        }
+        self.advance();
+
+        // Stripping indentation of the first line
+        let indent = match self.closing_indent() {
+            Some(indent) => indent,
+            None => bail!(LexError::UnclosedStringQuotes {
+                src: self.source.clone(),
+                span: (start..self.idx).into(),
+                closing: "\"\"\"".to_string(),
+            }),
+        };
+        self.skip_indent(indent);
+        self.advance_string_part(start, true, Quotes::Triple(indent))
+    }
+
+    /// Returns indentation of the closing `"""`, if they are placed on
+    /// their own line, or zero, if there is a text before them
+    fn closing_indent(&self) -> Option<usize> {
+        let mut chars = self
+            .current
+            .into_iter()
+            .chain(self.next)
+            .chain(self.src.clone())
+            .peekable();
+        loop {
+            // Counting line indentation
+            let mut indent = 0;
+            while matches!(chars.peek(), Some(' ' | '\t')) {
+                chars.next();
+                indent += 1;
+            }
+
+            // Looking for the closing quotes in the line
+            let mut text = false;
+            loop {
+                if chars.clone().take(3).eq("\"\"\"".chars()) {
+                    return Some(if text { 0 } else { indent });
+                }
+                match chars.next()? {
+                    '\n' => break,
+                    '\\' => {
+                        chars.next();
+                    }
+                    _ => {}
+                }
+                text = true;
+            }
+        }
+    }
+
+    /// Skips up to `indent` whitespaces in the start of the line
+    fn skip_indent(&mut self, indent: usize) {
+        for _ in 0..indent {
+            if !matches!(self.current, Some(' ' | '\t')) {
+                break;
+            }
+            self.advance();
+        }
+    }
+
+    /// Is closing quotes of the string
+    fn is_closing(&self, quotes: Quotes) -> bool {
+        match quotes {
+            Quotes::Single => self.current == Some('"'),
+            Quotes::Triple(_) => {
+                self.current == Some('"')
+                    && self.next == Some('"')
+                    && self.src.clone().next() == Some('"')
+            }
+        }
+    }
+
+    /// Advances string part, that starts with the opening quotes at `start`,
+    /// if `opening` is set, or with the `}` closing an interpolated value.
+    ///
+    /// The part ends with closing quotes or with `{` starting an interpolated
+    /// value, so the string `"a{b}c{d}e"` is lexed into `StrStart(a)`, tokens
+    /// of `b`, `StrMid(c)`, tokens of `d` and `StrEnd(e)`. Braces are escaped
+    /// by doubling them: `{{` and `}}`.
+    ///
+    fn advance_string_part(&mut self, start: usize, opening: bool, quotes: Quotes) -> Token {
         // Text buffer
         let mut buffer = String::new();
 
-        // Building string before reaching `"`
-        while self.current != Some('"') {
-            // Checking for next char
-            match &self.current {
-                Some('\\') => buffer.push(self.advance_escape_sequence()),
-                Some(_) => {
+        // Building string before reaching closing quotes or `{`
+        let kind = loop {
+            match (self.current, self.next) {
+                _ if self.is_closing(quotes) => {
+                    if let Quotes::Triple(indent) = quotes {
+                        // New line before the closing quotes is not the part of text
+                        if buffer.ends_with('\n') && self.line_indent() == indent {
+                            buffer.pop();
+                            if buffer.ends_with('\r') {
+                                buffer.pop();
+                            }
+                        }
+                        self.advance();
+                        self.advance();
+                    }
+                    self.advance();
+                    break if opening {
+                        TokenKind::String
+                    } else {
+                        TokenKind::StrEnd
+                    };
+                }
+                (Some('{'), Some('{')) | (Some('}'), Some('}')) => {
                     buffer.push(self.current.unwrap());
                     self.advance();
+                    self.advance();
                 }
-                None => bail!(LexError::UnclosedStringQuotes {
+                (Some('{'), _) => {
+                    self.advance();
+                    self.interps.push((quotes, 0));
+                    break if opening {
+                        TokenKind::StrStart
+                    } else {
+                        TokenKind::StrMid
+                    };
+                }
+                (Some('}'), _) if self.is_text_after_brace(quotes) => {
+                    bail!(LexError::UnmatchedStringBrace {
+                        src: self.source.clone(),
+                        span: (self.idx..self.idx + 1).into(),
+                    })
+                }
+                (Some('}'), _) => {
+                    // Brace closes the block, so the string itself is unclosed
+                    while !matches!(self.current, Some('\n') | None) {
+                        self.advance();
+                    }
+                    bail!(LexError::UnclosedStringQuotes {
+                        src: self.source.clone(),
+                        span: (start..self.idx).into(),
+                        closing: quotes.closing().to_string(),
+                    })
+                }
+                (Some('\\'), _) => buffer.push(self.advance_escape_sequence()),
+                (Some('\n'), _) => {
+                    buffer.push('\n');
+                    self.advance();
+                    if let Quotes::Triple(indent) = quotes {
+                        self.skip_indent(indent);
+                    }
+                }
+                (Some(ch), _) => {
+                    buffer.push(ch);
+                    self.advance();
+                }
+                (None, _) => bail!(LexError::UnclosedStringQuotes {
                     src: self.source.clone(),
                     span: (start..self.idx).into(),
+                    closing: quotes.closing().to_string(),
                 }),
             }
+        };
+
+        let end = self.idx;
+        Token::new(Span(self.file, start..end), kind, Symbol::intern(&buffer))
+    }
+
+    /// Is string text placed after the current `}` on the same line.
+    /// Otherwise the brace is more likely to close the block around
+    /// the unclosed string, than to be unescaped. Multi-line strings
+    /// are checked to be closed before their text is advanced.
+    fn is_text_after_brace(&self, quotes: Quotes) -> bool {
+        match quotes {
+            Quotes::Single => self
+                .next
+                .into_iter()
+                .chain(self.src.clone())
+                .take_while(|ch| *ch != '\n')
+                .any(|ch| !ch.is_whitespace()),
+            Quotes::Triple(_) => true,
+        }
+    }
+
+    /// Returns amount of chars between the line start and the current char
+    fn line_indent(&self) -> usize {
+        let text = self.source.inner();
+        let line = text[..self.idx].rfind('\n').map(|idx| idx + 1).unwrap_or(0);
+        text[line..self.idx].chars().count()
+    }
+
+    /// Is raw string start: `r"` or `r#"`, with any amount of hashes
+    fn is_raw_string_start(&self) -> bool {
+        self.current == Some('r')
+            && self
+                .next
+                .into_iter()
+                .chain(self.src.clone())
+                .find(|ch| *ch != '#')
+                == Some('"')
+    }
+
+    /// Advances raw string: `r"text"` or `r#"text"#`. Escape sequences
+    /// and interpolation are not processed, hashes allow quotes in the
+    /// text: the string ends with the quote, followed by the same
+    /// amount of hashes, as the opening one
+    fn advance_raw_string(&mut self) -> Token {
+        // Advancing `r` and hashes
+        let start = self.idx;
+        self.advance();
+        let mut hashes = 0;
+        while self.current == Some('#') {
+            hashes += 1;
+            self.advance();
         }
 
         // Advancing `"`
         self.advance();
+
+        // Building string before reaching closing quote
+        let mut buffer = String::new();
+        loop {
+            match self.current {
+                Some('"') if self.is_raw_string_end(hashes) => break,
+                Some(ch) => {
+                    buffer.push(ch);
+                    self.advance();
+                }
+                None => bail!(LexError::UnclosedStringQuotes {
+                    src: self.source.clone(),
+                    span: (start..self.idx).into(),
+                    closing: format!("\"{}", "#".repeat(hashes)),
+                }),
+            }
+        }
+
+        // Advancing `"` and hashes
+        for _ in 0..=hashes {
+            self.advance();
+        }
         let end = self.idx;
         Token::new(
-            Span(self.source.clone(), start..end),
+            Span(self.file, start..end),
             TokenKind::String,
-            buffer,
+            Symbol::intern(&buffer),
         )
     }
 
+    /// Is raw string end: quote, followed by `hashes` hashes
+    fn is_raw_string_end(&self, hashes: usize) -> bool {
+        self.next
+            .into_iter()
+            .chain(self.src.clone())
+            .take(hashes)
+            .filter(|ch| *ch == '#')
+            .count()
+            == hashes
+    }
+
     /// Advances character
     fn advance_char(&mut self) -> Token {
         // Advancing `'`
-        self.advance();
         let start = self.idx;
+        self.advance();
 
         // Checking for escape sequence
         let ch = match self.current {
@@ -279,9 +610,9 @@ impl<'s> Lexer<'s> {
         let end = self.idx;
 
         Token::new(
-            Span(self.source.clone(), start..end),
+            Span(self.file, start..end),
             TokenKind::String,
-            ch.to_string(),
+            Symbol::intern(ch.encode_utf8(&mut [0; 4])),
         )
     }
 
@@ -321,33 +652,33 @@ impl<'s> Lexer<'s> {
         }
         let end = self.idx;
         Token::new(
-            Span(self.source.clone(), start..end),
+            Span(self.file, start..end),
             TokenKind::Number,
-            buffer,
+            Symbol::intern(&buffer),
         )
     }
 
     /// Token kind for id
-    fn token_kind_for_id(value: &str) -> TokenKind {
+    fn token_kind_for_id(value: Symbol) -> TokenKind {
         match value {
-            "for" => TokenKind::For,
-            "while" => TokenKind::While,
-            "in" => TokenKind::In,
-            "let" => TokenKind::Let,
-            "use" => TokenKind::Use,
-            "struct" => TokenKind::Struct,
-            "enum" => TokenKind::Enum,
-            "if" => TokenKind::If,
-            "else" => TokenKind::Else,
-            "return" => TokenKind::Return,
-            "continue" => TokenKind::Continue,
-            "break" => TokenKind::Break,
-            "as" => TokenKind::As,
-            "true" => TokenKind::Bool,
-            "false" => TokenKind::Bool,
-            "fn" => TokenKind::Fn,
-            "pub" => TokenKind::Pub,
-            "mut" => TokenKind::Mut,
+            kw::FOR => TokenKind::For,
+            kw::WHILE => TokenKind::While,
+            kw::IN => TokenKind::In,
+            kw::LET => TokenKind::Let,
+            kw::USE => TokenKind::Use,
+            kw::STRUCT => TokenKind::Struct,
+            kw::ENUM => TokenKind::Enum,
+            kw::IF => TokenKind::If,
+            kw::ELSE => TokenKind::Else,
+            kw::RETURN => TokenKind::Return,
+            kw::CONTINUE => TokenKind::Continue,
+            kw::BREAK => TokenKind::Break,
+            kw::AS => TokenKind::As,
+            kw::TRUE => TokenKind::Bool,
+            kw::FALSE => TokenKind::Bool,
+            kw::FN => TokenKind::Fn,
+            kw::PUB => TokenKind::Pub,
+            kw::MUT => TokenKind::Mut,
             _ => TokenKind::Id,
         }
     }
@@ -358,27 +689,62 @@ impl<'s> Lexer<'s> {
         // Text buffer
         let mut buffer = String::new();
         // Building id before reaching
-        // char that is not letter, not digit,
-        // and not underscore.
-        while (self.is_id_letter() || self.is_digit()) && !self.is_eof() {
+        // char that is not `XID_Continue`
+        while self.is_id_continue() && !self.is_eof() {
             buffer.push(self.current.unwrap());
             self.advance();
         }
         let end = self.idx;
-        Token::new(
-            Span(self.source.clone(), start..end),
-            Self::token_kind_for_id(&buffer),
-            buffer,
-        )
+
+        // Normalizing id, so visually identical
+        // names are the same
+        let buffer = if buffer.is_ascii() {
+            buffer
+        } else {
+            buffer.nfc().collect()
+        };
+        let span = Span(self.file, start..end);
+        let symbol = Symbol::intern(&buffer);
+        let kind = Self::token_kind_for_id(symbol);
+        if kind == TokenKind::Id && self.seen.insert(symbol) {
+            self.idents.push((symbol, span.clone()));
+        }
+        Token::new(span, kind, symbol)
     }
 
     /// Skips comment
     fn skip_comment(&mut self) {
         // #
         self.advance();
-        while self.current != Some('\n') {
+        while self.current != Some('\n') && !self.is_eof() {
+            self.advance();
+        }
+    }
+
+    /// Advances doc comment line: `/// text`. Lines are collected,
+    /// until the next token, which they are attached to
+    fn advance_doc_comment(&mut self) {
+        // ///
+        self.advance();
+        self.advance();
+        self.advance();
+
+        // Optional space after slashes
+        if self.current == Some(' ') {
+            self.advance();
+        }
+
+        let mut buffer = String::new();
+        while self.current != Some('\n') && !self.is_eof() {
+            buffer.push(self.current.unwrap());
             self.advance();
         }
+        self.doc.push(buffer.trim_end().to_string());
+    }
+
+    /// Is doc comment start: `///`
+    fn is_doc_comment_start(&self) -> bool {
+        self.current == Some('/') && self.next == Some('/') && self.src.clone().next() == Some('/')
     }
 
     /// Skips multiline comment
@@ -386,7 +752,7 @@ impl<'s> Lexer<'s> {
         // #[
         self.advance();
         self.advance();
-        while !(self.current == Some(']') && self.next == Some('#')) {
+        while (self.current != Some(']') || self.next != Some('#')) && !self.is_eof() {
             self.advance();
         }
         // ]#
@@ -394,16 +760,21 @@ impl<'s> Lexer<'s> {
         self.advance();
     }
 
-    /// Skips whitespaces and comments
+    /// Skips whitespaces and comments, recording them as trivia
     fn skip_trivia(&mut self) {
         loop {
-            // Skipping whitespaces
-            while self.is_whitespace() {
-                self.advance();
-            }
-
-            // Skipping comments
-            if self.current == Some('#') {
+            let start = self.idx;
+            let kind = if self.is_whitespace() {
+                // Skipping whitespaces
+                while self.is_whitespace() {
+                    self.advance();
+                }
+                TriviaKind::Whitespace
+            } else if self.is_doc_comment_start() {
+                // Collecting doc comments
+                self.advance_doc_comment();
+                TriviaKind::DocComment
+            } else if self.current == Some('#') {
                 // Skipping multiline comment
                 if self.next == Some('[') {
                     self.skip_multiline_comment();
@@ -412,10 +783,14 @@ impl<'s> Lexer<'s> {
                 else {
                     self.skip_comment();
                 }
-                continue;
-            }
-
-            break;
+                TriviaKind::Comment
+            } else {
+                break;
+            };
+            self.trivia.push_back(Trivia {
+                span: Span(self.file, start..self.idx),
+                kind,
+            });
         }
     }
 
@@ -427,9 +802,14 @@ impl<'s> Lexer<'s> {
         )
     }
 
-    /// Is id letter
+    /// Is id start: `XID_Start` char or underscore
     fn is_id_letter(&mut self) -> bool {
-        matches!(self.current, Some(it) if it.is_ascii_alphabetic() || it == '_')
+        matches!(self.current, Some(it) if unicode_ident::is_xid_start(it) || it == '_')
+    }
+
+    /// Is id continuation: `XID_Continue` char
+    fn is_id_continue(&mut self) -> bool {
+        matches!(self.current, Some(it) if unicode_ident::is_xid_continue(it))
     }
 
     /// Is digit
@@ -451,6 +831,12 @@ impl<'s> Iterator for Lexer<'s> {
         // Skipping trivia chars
         self.skip_trivia();
 
+        // Attaching doc comment to the token
+        if !self.doc.is_empty() {
+            let doc = std::mem::take(&mut self.doc).join("\n");
+            self.docs.insert(self.idx, doc);
+        }
+
         // Matching current and next
         match (self.current, self.next) {
             (Some('+'), Some('=')) => Some(self.advance_twice_with(TokenKind::PlusEq, "+=")),
@@ -482,15 +868,41 @@ impl<'s> Iterator for Lexer<'s> {
             (Some('<'), _) => Some(self.advance_with(TokenKind::Lt, "<")),
             (Some('.'), _) => Some(self.advance_with(TokenKind::Dot, ".")),
             (Some(','), _) => Some(self.advance_with(TokenKind::Comma, ",")),
-            (Some('{'), _) => Some(self.advance_with(TokenKind::Lbrace, "{")),
-            (Some('}'), _) => Some(self.advance_with(TokenKind::Rbrace, "}")),
+            (Some('{'), _) => {
+                if let Some((_, depth)) = self.interps.last_mut() {
+                    *depth += 1;
+                }
+                Some(self.advance_with(TokenKind::Lbrace, "{"))
+            }
+            (Some('}'), _) => match self.interps.last_mut() {
+                // Closing interpolated value
+                Some((quotes, 0)) => {
+                    let quotes = *quotes;
+                    let start = self.idx;
+                    self.interps.pop();
+                    self.advance();
+                    Some(self.advance_string_part(start, false, quotes))
+                }
+                Some((_, depth)) => {
+                    *depth -= 1;
+                    Some(self.advance_with(TokenKind::Rbrace, "}"))
+                }
+                None => Some(self.advance_with(TokenKind::Rbrace, "}")),
+            },
             (Some('['), _) => Some(self.advance_with(TokenKind::Lbracket, "[")),
             (Some(']'), _) => Some(self.advance_with(TokenKind::Rbracket, "]")),
             (Some('('), _) => Some(self.advance_with(TokenKind::Lparen, "(")),
             (Some(')'), _) => Some(self.advance_with(TokenKind::Rparen, ")")),
             (Some(':'), _) => Some(self.advance_with(TokenKind::Colon, ":")),
+            (Some('@'), _) => Some(self.advance_with(TokenKind::At, "@")),
             (Some(';'), _) => Some(self.advance_with(TokenKind::Semi, ";")),
+            (Some('"'), Some('"')) if self.src.clone().next() == Some('"') => {
+                Some(self.advance_multiline_string())
+            }
             (Some('"'), _) => Some(self.advance_string()),
+            (Some('r'), Some('"' | '#')) if self.is_raw_string_start() => {
+                Some(self.advance_raw_string())
+            }
             (Some('\''), _) => Some(self.advance_char()),
             (Some(ch), _) => {
                 if self.is_digit() {
@@ -501,7 +913,7 @@ impl<'s> Iterator for Lexer<'s> {
                     bail!(LexError::UnexpectedChar {
                         ch,
                         src: self.source.clone(),
-                        span: (self.idx..self.idx).into(),
+                        span: (self.idx..self.idx + ch.len_utf8()).into(),
                     })
                 }
             }
@@ -0,0 +1,75 @@
+/// Imports
+use common::source::SourceMap;
+use lexer::Lexer;
+use miette::Report;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Lexes text and returns the fatal error
+fn error(text: &str) -> Report {
+    let mut sources = SourceMap::default();
+    let id = sources.add("main.bit", text.to_string());
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        Lexer::new(sources.get(id)).collect::<Vec<_>>()
+    }));
+    assert!(result.is_err(), "expected lexing error");
+    macros::take_fatal().expect("expected fatal report")
+}
+
+/// Lexes text and returns code of the fatal error
+fn error_code(text: &str) -> String {
+    error(text).code().expect("expected error code").to_string()
+}
+
+/// Lexes text and returns label of the fatal error
+fn error_label(text: &str) -> String {
+    let report = error(text);
+    let mut labels = report.labels().expect("expected labels");
+    let label = labels.next().expect("expected label");
+    label.label().unwrap_or_default().to_string()
+}
+
+#[test]
+fn unclosed_string_in_block_is_reported() {
+    assert_eq!(error_code("fn main() {\n    let s = \"hello;\n}"), "E0102");
+    assert_eq!(error_code("fn main() { let s = \"hello; }\n"), "E0102");
+}
+
+#[test]
+fn unclosed_string_names_its_closing_quotes() {
+    assert_eq!(error_code("let s = r#\"abc"), "E0102");
+    assert_eq!(
+        error_label("let s = r#\"abc"),
+        "close string quotes by appending missed `\"#`."
+    );
+    assert_eq!(
+        error_label("let s = r\"abc"),
+        "close string quotes by appending missed `\"`."
+    );
+    assert_eq!(
+        error_label("let s = \"abc"),
+        "close string quotes by appending missed `\"`."
+    );
+    assert_eq!(
+        error_label("let s = \"\"\"\n    abc\n"),
+        "close string quotes by appending missed `\"\"\"`."
+    );
+}
+
+#[test]
+fn unmatched_brace_in_string_is_reported() {
+    assert_eq!(error_code("let s = \"a } b\";"), "E0107");
+    assert_eq!(error_code("let s = \"a}\";"), "E0107");
+
+    let report = error("let s = \"a } b\";");
+    assert_eq!(report.to_string(), "unmatched `}` in string.");
+    assert_eq!(
+        report.help().unwrap().to_string(),
+        "escape the brace by doubling it: `}}`"
+    );
+    let label = report.labels().unwrap().next().unwrap();
+    assert_eq!(
+        label.label(),
+        Some("this brace doesn't close an interpolation.")
+    );
+    assert_eq!((label.offset(), label.len()), (11, 1));
+}
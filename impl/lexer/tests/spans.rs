@@ -0,0 +1,218 @@
+/// Imports
+use common::{
+    source::SourceMap,
+    token::{Span, TokenKind, TriviaKind},
+};
+use lexer::Lexer;
+
+#[test]
+fn spans_are_utf8_byte_offsets() {
+    let text = "# комментарий\nlet u = \"日本\";";
+    let mut sources = SourceMap::default();
+    let id = sources.add("main.bit", text.to_string());
+    let tokens = Lexer::new(sources.get(id)).collect::<Vec<_>>();
+
+    let kinds = tokens.iter().map(|tk| tk.kind.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            TokenKind::Let,
+            TokenKind::Id,
+            TokenKind::Eq,
+            TokenKind::String,
+            TokenKind::Semi
+        ]
+    );
+    for tk in &tokens {
+        let Span(file, range) = &tk.span;
+        assert_eq!(*file, id);
+        assert!(text.get(range.clone()).is_some(), "{tk:?}");
+    }
+    assert_eq!(&text[tokens[0].span.1.clone()], "let");
+    assert_eq!(&text[tokens[4].span.1.clone()], ";");
+}
+
+#[test]
+fn identifiers_are_xid_and_nfc() {
+    // `é` is decomposed in the second identifier
+    let text = "площадь _x1 café cafe\u{301} 名前";
+    let mut sources = SourceMap::default();
+    let id = sources.add("main.bit", text.to_string());
+    let mut lexer = Lexer::new(sources.get(id));
+    let tokens = lexer.by_ref().collect::<Vec<_>>();
+
+    let lexemes = tokens
+        .iter()
+        .map(|tk| tk.lexeme.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(lexemes, ["площадь", "_x1", "café", "café", "名前"]);
+    assert!(tokens.iter().all(|tk| tk.kind == TokenKind::Id));
+    assert_eq!(&text[tokens[3].span.1.clone()], "cafe\u{301}");
+
+    // Normalized identifiers are recorded once
+    let idents = lexer
+        .idents()
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(idents, ["площадь", "_x1", "café", "名前"]);
+}
+
+#[test]
+fn keywords_are_not_identifiers() {
+    let text = "fn main() { let мир = 1; for x in мир {} }";
+    let mut sources = SourceMap::default();
+    let id = sources.add("main.bit", text.to_string());
+    let mut lexer = Lexer::new(sources.get(id));
+    lexer.by_ref().for_each(drop);
+
+    let idents = lexer
+        .idents()
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(idents, ["main", "мир", "x"]);
+}
+
+#[test]
+fn bracket_comments_are_not_attributes() {
+    let text = "#[todo(later) fix this]#\n@allow(dead_code)\nfn main() {}";
+    let mut sources = SourceMap::default();
+    let id = sources.add("main.bit", text.to_string());
+    let mut lexer = Lexer::new(sources.get(id));
+    let tokens = lexer.by_ref().collect::<Vec<_>>();
+
+    let kinds = tokens.iter().map(|tk| tk.kind.clone()).collect::<Vec<_>>();
+    assert_eq!(
+        kinds[..5],
+        [
+            TokenKind::At,
+            TokenKind::Id,
+            TokenKind::Lparen,
+            TokenKind::Id,
+            TokenKind::Rparen
+        ]
+    );
+    let trivia = lexer.take_trivia(tokens[0].span.1.start);
+    assert_eq!(trivia[0].kind, TriviaKind::Comment);
+    assert_eq!(&text[trivia[0].span.1.clone()], "#[todo(later) fix this]#");
+}
+
+#[test]
+fn interpolated_strings_are_split_into_parts() {
+    let text = "\"a {x} {{b}} {f(\"c{y}\")}!\"";
+    let mut sources = SourceMap::default();
+    let id = sources.add("main.bit", text.to_string());
+    let tokens = Lexer::new(sources.get(id)).collect::<Vec<_>>();
+
+    let kinds = tokens
+        .iter()
+        .map(|tk| (tk.kind.clone(), tk.lexeme.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            (TokenKind::StrStart, "a "),
+            (TokenKind::Id, "x"),
+            (TokenKind::StrMid, " {b} "),
+            (TokenKind::Id, "f"),
+            (TokenKind::Lparen, "("),
+            (TokenKind::StrStart, "c"),
+            (TokenKind::Id, "y"),
+            (TokenKind::StrEnd, ""),
+            (TokenKind::Rparen, ")"),
+            (TokenKind::StrEnd, "!"),
+        ]
+    );
+
+    // Values point inside of the string
+    assert_eq!(&text[tokens[1].span.1.clone()], "x");
+    assert_eq!(&text[tokens[6].span.1.clone()], "y");
+}
+
+#[test]
+fn doubled_braces_are_literal() {
+    let text = r#""{{a}}" "}}""#;
+    let mut sources = SourceMap::default();
+    let id = sources.add("main.bit", text.to_string());
+    let tokens = Lexer::new(sources.get(id)).collect::<Vec<_>>();
+
+    let kinds = tokens
+        .iter()
+        .map(|tk| (tk.kind.clone(), tk.lexeme.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [(TokenKind::String, "{a}"), (TokenKind::String, "}")]
+    );
+}
+
+#[test]
+fn raw_strings_are_not_escaped() {
+    let text = r####"r"C:\dir\{x}" r#"say "\d+""# r##"a "# b"##"####;
+    let mut sources = SourceMap::default();
+    let id = sources.add("main.bit", text.to_string());
+    let tokens = Lexer::new(sources.get(id)).collect::<Vec<_>>();
+
+    let lexemes = tokens
+        .iter()
+        .map(|tk| (tk.kind.clone(), tk.lexeme.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lexemes,
+        [
+            (TokenKind::String, r"C:\dir\{x}"),
+            (TokenKind::String, r#"say "\d+""#),
+            (TokenKind::String, r##"a "# b"##),
+        ]
+    );
+    assert_eq!(&text[tokens[1].span.1.clone()], r##"r#"say "\d+""#"##);
+}
+
+#[test]
+fn codepoint_escapes_are_unescaped() {
+    let text = r#""\u{263A}" "\U{0001F600}" "\x{41}""#;
+    let mut sources = SourceMap::default();
+    let id = sources.add("main.bit", text.to_string());
+    let tokens = Lexer::new(sources.get(id)).collect::<Vec<_>>();
+
+    let lexemes = tokens
+        .iter()
+        .map(|tk| tk.lexeme.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(lexemes, ["\u{263A}", "\u{1F600}", "A"]);
+}
+
+#[test]
+fn multiline_strings_strip_indentation() {
+    let text = "let s = \"\"\"\n    Hello, {name}!\n      {{indented}}\n\n    end\n    \"\"\";\nlet t = \"\"\"\n  a\n  b\"\"\";";
+    let mut sources = SourceMap::default();
+    let id = sources.add("main.bit", text.to_string());
+    let tokens = Lexer::new(sources.get(id)).collect::<Vec<_>>();
+
+    let lexemes = tokens
+        .iter()
+        .map(|tk| (tk.kind.clone(), tk.lexeme.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        lexemes,
+        [
+            (TokenKind::Let, "let"),
+            (TokenKind::Id, "s"),
+            (TokenKind::Eq, "="),
+            (TokenKind::StrStart, "Hello, "),
+            (TokenKind::Id, "name"),
+            (TokenKind::StrEnd, "!\n  {indented}\n\nend"),
+            (TokenKind::Semi, ";"),
+            (TokenKind::Let, "let"),
+            (TokenKind::Id, "t"),
+            (TokenKind::Eq, "="),
+            (TokenKind::String, "  a\n  b"),
+            (TokenKind::Semi, ";"),
+        ]
+    );
+
+    // Values point to their place in the source
+    let at = text.find("name").unwrap();
+    assert_eq!(tokens[4].span.1, at..at + 4);
+}
@@ -0,0 +1,136 @@
+/// Imports
+use miette::{LabeledSpan, MietteDiagnostic, NamedSource, Report, Severity};
+use std::{
+    cell::RefCell,
+    ops::Range,
+    panic::{self, PanicHookInfo},
+    sync::Arc,
+};
+
+/// Item, that is being compiled
+#[derive(Clone)]
+struct Item {
+    name: String,
+    src: Arc<NamedSource<String>>,
+    span: Range<usize>,
+}
+
+/// Compilation state, reported along with the internal compiler error
+#[derive(Default, Clone)]
+struct IceContext {
+    phase: Option<&'static str>,
+    item: Option<Item>,
+    node: Option<Range<usize>>,
+}
+
+thread_local! {
+    /// Compilation state of this thread
+    static CONTEXT: RefCell<IceContext> = RefCell::new(IceContext::default());
+}
+
+/// Sets current compilation phase, e.g. `parsing` or `type checking`
+pub fn set_phase(phase: &'static str) {
+    CONTEXT.with(|cx| cx.borrow_mut().phase = Some(phase));
+}
+
+/// Sets item, that is being compiled, with its source span
+pub fn set_item(name: &str, src: Arc<NamedSource<String>>, span: Range<usize>) {
+    let item = Item {
+        name: name.to_string(),
+        src,
+        span,
+    };
+    CONTEXT.with(|cx| {
+        let mut cx = cx.borrow_mut();
+        cx.item = Some(item);
+        cx.node = None;
+    });
+}
+
+/// Clears item, that is being compiled
+pub fn clear_item() {
+    CONTEXT.with(|cx| {
+        let mut cx = cx.borrow_mut();
+        cx.item = None;
+        cx.node = None;
+    });
+}
+
+/// Sets span of the expression or statement of the current item, that
+/// is being checked. Returns span of the enclosing one, to restore by
+/// [`leave_node`] after the node is checked
+pub fn enter_node(span: Range<usize>) -> Option<Range<usize>> {
+    CONTEXT.with(|cx| cx.borrow_mut().node.replace(span))
+}
+
+/// Restores span of the enclosing node, returned by [`enter_node`]
+pub fn leave_node(outer: Option<Range<usize>>) {
+    CONTEXT.with(|cx| cx.borrow_mut().node = outer);
+}
+
+/// Clears compilation state, when compilation is done
+pub fn reset() {
+    CONTEXT.with(|cx| *cx.borrow_mut() = IceContext::default());
+}
+
+/// Returns panic message
+fn message(info: &PanicHookInfo<'_>) -> String {
+    let payload = info.payload();
+    match (
+        payload.downcast_ref::<&str>(),
+        payload.downcast_ref::<String>(),
+    ) {
+        (Some(message), _) => message.to_string(),
+        (_, Some(message)) => message.clone(),
+        _ => "unknown panic".to_string(),
+    }
+}
+
+/// Builds internal compiler error report of the panic
+pub fn report(info: &PanicHookInfo<'_>) -> Report {
+    let cx = CONTEXT.with(|cx| cx.borrow().clone());
+
+    let mut notes = Vec::new();
+    if let Some(location) = info.location() {
+        notes.push(format!("panicked at {location}"));
+    }
+    if let Some(phase) = cx.phase {
+        notes.push(format!("compilation phase: {phase}"));
+    }
+    notes.push(
+        "this is a bug in the compiler, please file an issue \
+         with the source code, that caused it"
+            .to_string(),
+    );
+
+    let diagnostic = MietteDiagnostic::new(format!("internal compiler error: {}", message(info)))
+        .with_code("ICE")
+        .with_severity(Severity::Error)
+        .with_help(notes.join("\n"));
+    match cx.item {
+        Some(item) => {
+            let mut labels = vec![LabeledSpan::new_with_span(
+                Some(format!("while compiling `{}`", item.name)),
+                item.span,
+            )];
+            if let Some(node) = cx.node {
+                labels.push(LabeledSpan::new_primary_with_span(
+                    Some("while checking this".to_string()),
+                    node,
+                ));
+            }
+            Report::new(diagnostic.with_labels(labels)).with_source_code(item.src)
+        }
+        None => Report::new(diagnostic),
+    }
+}
+
+/// Installs panic hook, that reports panics as internal compiler errors.
+/// Fatal errors, raised by `bail!`, are left to the driver
+pub fn install_hook() {
+    panic::set_hook(Box::new(|info| {
+        if !crate::has_fatal() {
+            eprintln!("{:?}", report(info));
+        }
+    }));
+}
@@ -1,18 +1,47 @@
-/// Prints the error then
-/// exits proccess using `std::process::exit(1)`.
+/// Modules
+pub mod ice;
+
+/// Imports
+use std::cell::RefCell;
+
+thread_local! {
+    /// Last fatal report, raised by `bail!` on this thread
+    static FATAL: RefCell<Option<miette::Report>> = const { RefCell::new(None) };
+}
+
+/// Records fatal report, so the driver could take it
+/// after unwinding, and panics with its rendering.
+pub fn fatal(report: miette::Report) -> ! {
+    let rendered = format!("{report:?}");
+    FATAL.with(|fatal| *fatal.borrow_mut() = Some(report));
+    panic!("{rendered}");
+}
+
+/// Returns `true` if fatal report was raised on this thread and not taken yet
+pub fn has_fatal() -> bool {
+    FATAL.with(|fatal| fatal.borrow().is_some())
+}
+
+/// Takes the last fatal report, raised on this thread
+pub fn take_fatal() -> Option<miette::Report> {
+    FATAL.with(|fatal| fatal.borrow_mut().take())
+}
+
+/// Records the error then
+/// panics with its rendering, see [`fatal`].
 #[macro_export]
 macro_rules! bail {
     ($report:expr) => {{
         let report: miette::Report = $report.into();
-        panic!("{report:?}");
+        $crate::fatal(report)
     }};
 }
 
-/// Prints bug error then
-/// exits proccess using `std::process::exit(1)`.
+/// Panics with the internal compiler error message. Panic hook,
+/// installed by [`ice::install_hook`], reports it with the compilation state.
 #[macro_export]
 macro_rules! bug {
-    ($text:expr) => {{
-        panic!("{:?}", miette::miette!("hello"));
+    ($($arg:tt)+) => {{
+        panic!($($arg)+)
     }};
 }
@@ -0,0 +1,78 @@
+/// Imports
+use macros::{bug, ice};
+use miette::{NamedSource, Report};
+use std::{
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Mutex},
+};
+
+/// Serializes tests, that replace the global panic hook
+static HOOK: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    /// Report of the last internal compiler error
+    static REPORT: RefCell<Option<Report>> = const { RefCell::new(None) };
+}
+
+#[test]
+fn bug_is_reported_with_compilation_state() {
+    let _hook = HOOK.lock().unwrap_or_else(|err| err.into_inner());
+    let text = "fn main() {\n    broken()\n}";
+    let src = Arc::new(NamedSource::new("main.bit", text.to_string()));
+    ice::set_phase("type checking");
+    ice::set_item("main", src, 0..text.len());
+
+    // Reporting the panic by the hook, without printing it
+    panic::set_hook(Box::new(|info| {
+        REPORT.with(|report| *report.borrow_mut() = Some(ice::report(info)));
+    }));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| bug!("unexpected type `{}`", "?T1")));
+    let _ = panic::take_hook();
+    ice::reset();
+    assert!(result.is_err());
+
+    let report = REPORT.with(|report| report.borrow_mut().take()).unwrap();
+    assert_eq!(
+        report.to_string(),
+        "internal compiler error: unexpected type `?T1`"
+    );
+    assert_eq!(report.code().unwrap().to_string(), "ICE");
+    let help = report.help().unwrap().to_string();
+    assert!(help.contains("panicked at macros/tests/ice.rs"), "{help}");
+    assert!(help.contains("compilation phase: type checking"), "{help}");
+
+    let labels = report.labels().unwrap().collect::<Vec<_>>();
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].label(), Some("while compiling `main`"));
+    assert_eq!((labels[0].offset(), labels[0].len()), (0, text.len()));
+}
+
+#[test]
+fn bug_points_at_node_being_checked() {
+    let _hook = HOOK.lock().unwrap_or_else(|err| err.into_inner());
+    let text = "fn main() {\n    broken()\n}";
+    let src = Arc::new(NamedSource::new("main.bit", text.to_string()));
+    ice::set_phase("type checking");
+    ice::set_item("main", src, 0..text.len());
+
+    // Entering the call, then its callee, which is left before the panic
+    let outer = ice::enter_node(16..24);
+    let inner = ice::enter_node(16..22);
+    ice::leave_node(inner);
+    panic::set_hook(Box::new(|info| {
+        REPORT.with(|report| *report.borrow_mut() = Some(ice::report(info)));
+    }));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| bug!("unexpected call")));
+    let _ = panic::take_hook();
+    ice::leave_node(outer);
+    ice::reset();
+    assert!(result.is_err());
+
+    let report = REPORT.with(|report| report.borrow_mut().take()).unwrap();
+    let labels = report.labels().unwrap().collect::<Vec<_>>();
+    assert_eq!(labels.len(), 2);
+    assert_eq!(labels[1].label(), Some("while checking this"));
+    assert!(labels[1].primary());
+    assert_eq!(&text[labels[1].offset()..][..labels[1].len()], "broken()");
+}
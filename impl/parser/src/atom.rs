@@ -1,27 +1,34 @@
 /// Imports
 use crate::Parser;
 use ast::atom::{Param, TypeHint};
-use common::token::TokenKind;
+use common::{symbol::Symbol, token::TokenKind};
+use cst::kind::SyntaxKind;
 
 /// Implementation
 impl<'s> Parser<'s> {
     /// Parses generic args
     fn generic_args(&mut self) -> Vec<TypeHint> {
         if self.check(TokenKind::Lt) {
-            self.sep_by(TokenKind::Lt, TokenKind::Gt, TokenKind::Comma, |p| {
+            self.start_node(SyntaxKind::GenericArgs);
+            let args = self.sep_by(TokenKind::Lt, TokenKind::Gt, TokenKind::Comma, |p| {
                 p.type_hint()
-            })
+            });
+            self.finish_node();
+            args
         } else {
             Vec::new()
         }
     }
 
     /// Parses generic params
-    pub fn generic_params(&mut self) -> Vec<String> {
+    pub fn generic_params(&mut self) -> Vec<Symbol> {
         if self.check(TokenKind::Lt) {
-            self.sep_by(TokenKind::Lt, TokenKind::Gt, TokenKind::Comma, |p| {
+            self.start_node(SyntaxKind::GenericParams);
+            let params = self.sep_by(TokenKind::Lt, TokenKind::Gt, TokenKind::Comma, |p| {
                 p.expect(TokenKind::Id).lexeme
-            })
+            });
+            self.finish_node();
+            params
         } else {
             Vec::new()
         }
@@ -29,11 +36,13 @@ impl<'s> Parser<'s> {
 
     /// Parses params
     pub fn params(&mut self) -> Vec<Param> {
-        self.sep_by(
+        self.start_node(SyntaxKind::Params);
+        let params = self.sep_by(
             TokenKind::Lparen,
             TokenKind::Rparen,
             TokenKind::Comma,
             |p| {
+                p.start_node(SyntaxKind::Param);
                 let start_span = p.peek().span.clone();
 
                 let name = p.expect(TokenKind::Id).lexeme;
@@ -41,6 +50,7 @@ impl<'s> Parser<'s> {
                 let hint = p.type_hint();
 
                 let end_span = p.prev().span.clone();
+                p.finish_node();
 
                 Param {
                     span: start_span + end_span,
@@ -48,7 +58,9 @@ impl<'s> Parser<'s> {
                     hint,
                 }
             },
-        )
+        );
+        self.finish_node();
+        params
     }
 
     /// Parses id type hint
@@ -68,7 +80,7 @@ impl<'s> Parser<'s> {
             TypeHint::Module {
                 span: start_span + end_span,
                 module: id,
-                name: name,
+                name,
                 args: generics,
             }
         }
@@ -117,10 +129,13 @@ impl<'s> Parser<'s> {
 
     /// Parses type hint
     pub fn type_hint(&mut self) -> TypeHint {
-        if self.check(TokenKind::Fn) {
+        self.start_node(SyntaxKind::TypeHint);
+        let hint = if self.check(TokenKind::Fn) {
             self.fn_type_hint()
         } else {
             self.id_type_hint()
-        }
+        };
+        self.finish_node();
+        hint
     }
 }
@@ -9,7 +9,7 @@ use thiserror::Error;
 pub enum ParseError {
     /// Unexpected token
     #[error("unexpected token `{got:?}`. expected `{expected:?}`")]
-    #[diagnostic(code(parse::unexpected_tk))]
+    #[diagnostic(code(E0201))]
     UnexpectedToken {
         got: TokenKind,
         expected: TokenKind,
@@ -23,7 +23,7 @@ pub enum ParseError {
     /// Unexpected expr token
     #[error("unexpected expression token `{got:?}`")]
     #[diagnostic(
-        code(parse::unexpected_expr_tk),
+        code(E0202),
         help("token {got:?} can't be start of the expression")
     )]
     UnexpectedExprToken {
@@ -36,7 +36,7 @@ pub enum ParseError {
     /// Unexpected item token
     #[error("unexpected item token `{got:?}`")]
     #[diagnostic(
-        code(parse::unexpected_item_tk),
+        code(E0203),
         help("token {got:?} can't be start of the top-level item")
     )]
     UnexpectedItemToken {
@@ -48,7 +48,7 @@ pub enum ParseError {
     },
     /// Unexpected end of file
     #[error("unexpected end of file")]
-    #[diagnostic(code(parse::unexpected_eof))]
+    #[diagnostic(code(E0204))]
     UnexpectedEof {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -58,7 +58,7 @@ pub enum ParseError {
     /// Expected semicolon
     #[error("expected semicolon after non-closing statement")]
     #[diagnostic(
-        code(parse::expected_semicolon),
+        code(E0205),
         help("the semicolon can be omitted only after last statement in the block")
     )]
     ExpectedSemicolon {
@@ -1,29 +1,42 @@
 /// Imports
 use crate::{Parser, errors::ParseError};
-use ast::expr::{BinOp, Expr, ExprKind, Lit, UnOp};
-use common::token::{Span, TokenKind};
+use ast::expr::{BinOp, Expr, ExprKind, InterpPart, Lit, UnOp};
+use common::{
+    symbol::{kw, sym},
+    token::{Span, TokenKind},
+};
+use cst::kind::SyntaxKind;
 use macros::{bail, bug};
 
 /// Expr parsing implementation
 impl<'s> Parser<'s> {
     /// Creates expr by span and kind
-    fn mk_expr(&self, span: Span, kind: ExprKind) -> Expr {
-        Expr { span, kind }
+    fn mk_expr(&mut self, span: Span, kind: ExprKind) -> Expr {
+        Expr {
+            id: self.node_id(),
+            span,
+            kind,
+        }
     }
 
     /// Group expression parsing
     fn group(&mut self) -> Expr {
+        self.start_node(SyntaxKind::ParenExpr);
         self.expect(TokenKind::Lparen);
         let expr = self.expr();
         self.expect(TokenKind::Rparen);
+        self.finish_node();
         expr
     }
 
     /// Variable parsing
     fn variable(&mut self) -> Expr {
         // parsing base identifier
+        let checkpoint = self.checkpoint();
+        self.start_node(SyntaxKind::IdExpr);
         let start_span = self.peek().span.clone();
         let id = self.expect(TokenKind::Id).lexeme;
+        self.finish_node();
 
         // result node
         let mut result = self.mk_expr(start_span.clone(), ExprKind::Id(id));
@@ -32,10 +45,12 @@ impl<'s> Parser<'s> {
         loop {
             // checking for chain `a.b.c.d`
             if self.check(TokenKind::Dot) {
+                self.start_node_at(checkpoint, SyntaxKind::FieldExpr);
                 self.bump();
 
                 let id = self.expect(TokenKind::Id).lexeme;
                 let end_span = self.prev().span.clone();
+                self.finish_node();
 
                 result = self.mk_expr(
                     start_span.clone() + end_span,
@@ -46,13 +61,17 @@ impl<'s> Parser<'s> {
 
             // checking for call
             if self.check(TokenKind::Lparen) {
+                self.start_node_at(checkpoint, SyntaxKind::CallExpr);
+                self.start_node(SyntaxKind::Args);
                 let args = self.sep_by(
                     TokenKind::Lparen,
                     TokenKind::Rparen,
                     TokenKind::Comma,
                     |p| p.expr(),
                 );
+                self.finish_node();
                 let end_span = self.prev().span.clone();
+                self.finish_node();
 
                 result = self.mk_expr(
                     start_span.clone() + end_span,
@@ -71,6 +90,7 @@ impl<'s> Parser<'s> {
     /// If expression parsing
     fn if_expr(&mut self) -> Expr {
         // Bumping `if`
+        self.start_node(SyntaxKind::IfExpr);
         let start_span = self.peek().span.clone();
         self.bump();
 
@@ -93,12 +113,14 @@ impl<'s> Parser<'s> {
             };
 
             let end_span = self.prev().span.clone();
+            self.finish_node();
             self.mk_expr(
                 start_span + end_span,
                 ExprKind::If(Box::new(expr), Box::new(block), Some(Box::new(branch))),
             )
         } else {
             let end_span = self.prev().span.clone();
+            self.finish_node();
             self.mk_expr(
                 start_span + end_span,
                 ExprKind::If(Box::new(expr), Box::new(block), None),
@@ -108,17 +130,21 @@ impl<'s> Parser<'s> {
 
     /// Closure expression parsing
     fn closure_expr(&mut self) -> Expr {
+        self.start_node(SyntaxKind::ClosureExpr);
         let start_span = self.peek().span.clone();
 
         // If arguments presented
         if self.check(TokenKind::Bar) {
             // Collecting params
+            self.start_node(SyntaxKind::ClosureParams);
             let params = self.sep_by(TokenKind::Bar, TokenKind::Bar, TokenKind::Comma, |p| {
                 p.expect(TokenKind::Id).lexeme
             });
+            self.finish_node();
 
             let body = self.expr();
             let end_span = self.prev().span.clone();
+            self.finish_node();
 
             self.mk_expr(
                 start_span + end_span,
@@ -130,6 +156,7 @@ impl<'s> Parser<'s> {
 
             let body = self.expr();
             let end_span = self.prev().span.clone();
+            self.finish_node();
 
             self.mk_expr(
                 start_span + end_span,
@@ -138,26 +165,67 @@ impl<'s> Parser<'s> {
         }
     }
 
+    /// Interpolated string parsing. Literal parts and values
+    /// are separated by the lexer: `StrStart`, value, `StrMid`,
+    /// value, .., `StrEnd`. Empty literal parts are omitted
+    fn interp_expr(&mut self) -> Expr {
+        // Bumping string start
+        self.start_node(SyntaxKind::InterpExpr);
+        let start = self.bump();
+        let mut parts = Vec::new();
+        if start.lexeme != sym::EMPTY {
+            parts.push(InterpPart::Lit(start.lexeme.to_string()));
+        }
+
+        // Parsing values and literal parts between them
+        loop {
+            parts.push(InterpPart::Expr(self.expr()));
+            let tk = if self.check(TokenKind::StrMid) {
+                self.bump()
+            } else {
+                self.expect(TokenKind::StrEnd)
+            };
+            if tk.lexeme != sym::EMPTY {
+                parts.push(InterpPart::Lit(tk.lexeme.to_string()));
+            }
+            if tk.kind == TokenKind::StrEnd {
+                break;
+            }
+        }
+
+        let end_span = self.prev().span.clone();
+        self.finish_node();
+        self.mk_expr(start.span + end_span, ExprKind::Interp(parts))
+    }
+
+    /// Bumps literal token, wrapping it into the literal node
+    fn literal(&mut self) {
+        self.start_node(SyntaxKind::LitExpr);
+        self.bump();
+        self.finish_node();
+    }
+
     /// Atom expression parsing
     fn atom(&mut self) -> Expr {
         let tk = self.peek().clone();
         match tk.kind {
             TokenKind::Lparen => self.group(),
             TokenKind::Number => {
-                self.bump();
-                self.mk_expr(tk.span, ExprKind::Lit(Lit::Number(tk.lexeme)))
+                self.literal();
+                self.mk_expr(tk.span, ExprKind::Lit(Lit::Number(tk.lexeme.to_string())))
             }
             TokenKind::String => {
-                self.bump();
-                self.mk_expr(tk.span, ExprKind::Lit(Lit::String(tk.lexeme)))
+                self.literal();
+                self.mk_expr(tk.span, ExprKind::Lit(Lit::String(tk.lexeme.to_string())))
             }
+            TokenKind::StrStart => self.interp_expr(),
             TokenKind::Bool => {
-                self.bump();
+                self.literal();
                 self.mk_expr(
                     tk.span,
-                    ExprKind::Lit(Lit::Bool(match tk.lexeme.as_str() {
-                        "true" => true,
-                        "false" => false,
+                    ExprKind::Lit(Lit::Bool(match tk.lexeme {
+                        kw::TRUE => true,
+                        kw::FALSE => false,
                         _ => bug!("non-bool value in bool literal"),
                     })),
                 )
@@ -180,6 +248,7 @@ impl<'s> Parser<'s> {
             || self.check(TokenKind::Ampersand)
             || self.check(TokenKind::Star)
         {
+            self.start_node(SyntaxKind::UnaryExpr);
             let start_span = self.peek().span.clone();
 
             let op = match self.bump().kind {
@@ -191,6 +260,7 @@ impl<'s> Parser<'s> {
 
             let value = self.unary_expr();
             let end_span = self.prev().span.clone();
+            self.finish_node();
 
             return self.mk_expr(start_span + end_span, ExprKind::Unary(op, Box::new(value)));
         }
@@ -200,6 +270,7 @@ impl<'s> Parser<'s> {
 
     /// Factor expression parsing
     fn factor_expr(&mut self) -> Expr {
+        let checkpoint = self.checkpoint();
         let start_span = self.peek().span.clone();
         let mut left = self.unary_expr();
 
@@ -207,6 +278,7 @@ impl<'s> Parser<'s> {
             || self.check(TokenKind::Slash)
             || self.check(TokenKind::Percent)
         {
+            self.start_node_at(checkpoint, SyntaxKind::BinExpr);
             let op = match self.bump().kind {
                 TokenKind::Star => BinOp::Mul,
                 TokenKind::Slash => BinOp::Div,
@@ -217,6 +289,7 @@ impl<'s> Parser<'s> {
             let right = self.unary_expr();
             let end_span = self.prev().span.clone();
 
+            self.finish_node();
             left = self.mk_expr(
                 start_span.clone() + end_span,
                 ExprKind::Bin(op, Box::new(left), Box::new(right)),
@@ -228,10 +301,12 @@ impl<'s> Parser<'s> {
 
     /// Term expression parsing
     fn term_expr(&mut self) -> Expr {
+        let checkpoint = self.checkpoint();
         let start_span = self.peek().span.clone();
         let mut left = self.factor_expr();
 
         while self.check(TokenKind::Plus) || self.check(TokenKind::Minus) {
+            self.start_node_at(checkpoint, SyntaxKind::BinExpr);
             let op = match self.bump().kind {
                 TokenKind::Plus => BinOp::Add,
                 TokenKind::Minus => BinOp::Sub,
@@ -241,6 +316,7 @@ impl<'s> Parser<'s> {
             let right = self.factor_expr();
             let end_span = self.prev().span.clone();
 
+            self.finish_node();
             left = self.mk_expr(
                 start_span.clone() + end_span,
                 ExprKind::Bin(op, Box::new(left), Box::new(right)),
@@ -252,6 +328,7 @@ impl<'s> Parser<'s> {
 
     /// Compare expression parsing
     fn compare_expr(&mut self) -> Expr {
+        let checkpoint = self.checkpoint();
         let start_span = self.peek().span.clone();
         let mut left = self.term_expr();
 
@@ -260,6 +337,7 @@ impl<'s> Parser<'s> {
             || self.check(TokenKind::Le)
             || self.check(TokenKind::Lt)
         {
+            self.start_node_at(checkpoint, SyntaxKind::BinExpr);
             let op = match self.bump().kind {
                 TokenKind::Ge => BinOp::Ge,
                 TokenKind::Gt => BinOp::Gt,
@@ -271,6 +349,7 @@ impl<'s> Parser<'s> {
             let right = self.factor_expr();
             let end_span = self.prev().span.clone();
 
+            self.finish_node();
             left = self.mk_expr(
                 start_span.clone() + end_span,
                 ExprKind::Bin(op, Box::new(left), Box::new(right)),
@@ -282,10 +361,12 @@ impl<'s> Parser<'s> {
 
     /// Equality expression parsing
     fn equality_expr(&mut self) -> Expr {
+        let checkpoint = self.checkpoint();
         let start_span = self.peek().span.clone();
         let mut left = self.compare_expr();
 
         while self.check(TokenKind::DoubleEq) || self.check(TokenKind::BangEq) {
+            self.start_node_at(checkpoint, SyntaxKind::BinExpr);
             let op = match self.bump().kind {
                 TokenKind::DoubleEq => BinOp::Eq,
                 TokenKind::BangEq => BinOp::Ne,
@@ -295,6 +376,7 @@ impl<'s> Parser<'s> {
             let right = self.compare_expr();
             let end_span = self.prev().span.clone();
 
+            self.finish_node();
             left = self.mk_expr(
                 start_span.clone() + end_span,
                 ExprKind::Bin(op, Box::new(left), Box::new(right)),
@@ -306,15 +388,18 @@ impl<'s> Parser<'s> {
 
     /// `Bitwise and` expression parsing
     fn bitwise_and_expr(&mut self) -> Expr {
+        let checkpoint = self.checkpoint();
         let start_span = self.peek().span.clone();
         let mut left = self.equality_expr();
 
         while self.check(TokenKind::Ampersand) {
+            self.start_node_at(checkpoint, SyntaxKind::BinExpr);
             self.bump();
 
             let right = self.equality_expr();
             let end_span = self.prev().span.clone();
 
+            self.finish_node();
             left = self.mk_expr(
                 start_span.clone() + end_span,
                 ExprKind::Bin(BinOp::BitAnd, Box::new(left), Box::new(right)),
@@ -326,15 +411,18 @@ impl<'s> Parser<'s> {
 
     /// `Bitwise xor` expression parsing
     fn bitwise_xor_expr(&mut self) -> Expr {
+        let checkpoint = self.checkpoint();
         let start_span = self.peek().span.clone();
         let mut left = self.bitwise_and_expr();
 
         while self.check(TokenKind::Caret) {
+            self.start_node_at(checkpoint, SyntaxKind::BinExpr);
             self.bump();
 
             let right = self.bitwise_and_expr();
             let end_span = self.prev().span.clone();
 
+            self.finish_node();
             left = self.mk_expr(
                 start_span.clone() + end_span,
                 ExprKind::Bin(BinOp::Xor, Box::new(left), Box::new(right)),
@@ -346,15 +434,18 @@ impl<'s> Parser<'s> {
 
     /// `Bitwise or` expression parsing
     fn bitwise_or_expr(&mut self) -> Expr {
+        let checkpoint = self.checkpoint();
         let start_span = self.peek().span.clone();
         let mut left = self.bitwise_xor_expr();
 
         while self.check(TokenKind::Bar) {
+            self.start_node_at(checkpoint, SyntaxKind::BinExpr);
             self.bump();
 
             let right = self.bitwise_xor_expr();
             let end_span = self.prev().span.clone();
 
+            self.finish_node();
             left = self.mk_expr(
                 start_span.clone() + end_span,
                 ExprKind::Bin(BinOp::BitOr, Box::new(left), Box::new(right)),
@@ -366,15 +457,18 @@ impl<'s> Parser<'s> {
 
     /// `Logical and` expression parsing
     fn logical_and_expr(&mut self) -> Expr {
+        let checkpoint = self.checkpoint();
         let start_span = self.peek().span.clone();
         let mut left = self.bitwise_or_expr();
 
         while self.check(TokenKind::DoubleAmp) {
+            self.start_node_at(checkpoint, SyntaxKind::BinExpr);
             self.bump();
 
             let right = self.bitwise_or_expr();
             let end_span = self.prev().span.clone();
 
+            self.finish_node();
             left = self.mk_expr(
                 start_span.clone() + end_span,
                 ExprKind::Bin(BinOp::And, Box::new(left), Box::new(right)),
@@ -386,15 +480,18 @@ impl<'s> Parser<'s> {
 
     /// `Logical or` expression parsing
     fn logical_or_expr(&mut self) -> Expr {
+        let checkpoint = self.checkpoint();
         let start_span = self.peek().span.clone();
         let mut left = self.logical_and_expr();
 
         while self.check(TokenKind::DoubleBar) {
+            self.start_node_at(checkpoint, SyntaxKind::BinExpr);
             self.bump();
 
             let right = self.logical_and_expr();
             let end_span = self.prev().span.clone();
 
+            self.finish_node();
             left = self.mk_expr(
                 start_span.clone() + end_span,
                 ExprKind::Bin(BinOp::Or, Box::new(left), Box::new(right)),
@@ -406,15 +503,18 @@ impl<'s> Parser<'s> {
 
     /// `Assign` expression parsing
     fn assign_expr(&mut self) -> Expr {
+        let checkpoint = self.checkpoint();
         let start_span = self.peek().span.clone();
         let mut left = self.logical_or_expr();
 
         while self.check(TokenKind::Eq) {
+            self.start_node_at(checkpoint, SyntaxKind::AssignExpr);
             self.bump();
 
             let right = self.logical_or_expr();
             let end_span = self.prev().span.clone();
 
+            self.finish_node();
             left = self.mk_expr(
                 start_span.clone() + end_span,
                 ExprKind::Assign(Box::new(left), Box::new(right)),
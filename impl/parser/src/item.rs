@@ -2,22 +2,30 @@
 use crate::{Parser, errors::ParseError};
 use ast::{
     atom::{Publicity, TypeHint},
-    item::{Enum, Field, Function, Item, ItemKind, Struct, Use, UseKind, Variant},
+    item::{
+        Attribute, AttributeArg, Enum, Field, Function, Item, ItemKind, Struct, Use, UseKind,
+        Variant,
+    },
 };
 use common::token::TokenKind;
+use cst::kind::SyntaxKind;
 use macros::bail;
 
 /// Item parsing implementation
 impl<'s> Parser<'s> {
     // Parses struct field
     fn struct_field(&mut self) -> Field {
+        let docs = self.take_docs();
+        self.start_node(SyntaxKind::Field);
         let start_span = self.peek().span.clone();
         let name = self.expect(TokenKind::Id).lexeme;
         self.expect(TokenKind::Colon);
         let hint = self.type_hint();
         let end_span = self.prev().span.clone();
+        self.finish_node();
 
         Field {
+            docs,
             span: start_span + end_span,
             name,
             hint,
@@ -27,6 +35,7 @@ impl<'s> Parser<'s> {
     // Parses struct
     fn struct_item_kind(&mut self) -> ItemKind {
         // Bumping `struct`
+        self.start_node(SyntaxKind::Struct);
         self.bump();
 
         // Parsing signature
@@ -40,6 +49,7 @@ impl<'s> Parser<'s> {
             TokenKind::Comma,
             |p| p.struct_field(),
         );
+        self.finish_node();
 
         ItemKind::Struct(Struct {
             name,
@@ -50,6 +60,8 @@ impl<'s> Parser<'s> {
 
     // Parses enum variant
     fn enum_variant(&mut self) -> Variant {
+        let docs = self.take_docs();
+        self.start_node(SyntaxKind::Variant);
         let start_span = self.peek().span.clone();
         let name = self.expect(TokenKind::Id).lexeme;
         let params = if self.check(TokenKind::Lparen) {
@@ -63,8 +75,10 @@ impl<'s> Parser<'s> {
             Vec::new()
         };
         let end_span = self.prev().span.clone();
+        self.finish_node();
 
         Variant {
+            docs,
             span: start_span + end_span,
             name,
             params,
@@ -74,6 +88,7 @@ impl<'s> Parser<'s> {
     // Parses enum
     fn enum_item_kind(&mut self) -> ItemKind {
         // Bumping `enum`
+        self.start_node(SyntaxKind::Enum);
         self.bump();
 
         // Parsing signature
@@ -87,6 +102,7 @@ impl<'s> Parser<'s> {
             TokenKind::Comma,
             |p| p.enum_variant(),
         );
+        self.finish_node();
 
         ItemKind::Enum(Enum {
             name,
@@ -98,6 +114,7 @@ impl<'s> Parser<'s> {
     // Parses function
     fn fn_item_kind(&mut self) -> ItemKind {
         // Bumping `fn`
+        self.start_node(SyntaxKind::Function);
         self.bump();
 
         // Parsing signature
@@ -113,6 +130,7 @@ impl<'s> Parser<'s> {
 
         // Parsing body
         let block = self.block();
+        self.finish_node();
 
         ItemKind::Function(Function {
             name,
@@ -129,12 +147,12 @@ impl<'s> Parser<'s> {
         let mut module = String::new();
 
         // First id
-        module.push_str(&self.expect(TokenKind::Id).lexeme);
+        module.push_str(self.expect(TokenKind::Id).lexeme.as_str());
 
         while self.check(TokenKind::Slash) {
             self.expect(TokenKind::Slash);
             module.push('/');
-            module.push_str(&self.expect(TokenKind::Id).lexeme);
+            module.push_str(self.expect(TokenKind::Id).lexeme.as_str());
         }
 
         module
@@ -143,6 +161,7 @@ impl<'s> Parser<'s> {
     // Parses use
     fn use_item_kind(&mut self) -> ItemKind {
         // Bumping `use`
+        self.start_node(SyntaxKind::Use);
         self.bump();
 
         // Use path
@@ -162,6 +181,7 @@ impl<'s> Parser<'s> {
         } else {
             UseKind::Just
         };
+        self.finish_node();
 
         ItemKind::Use(Use { path, kind })
     }
@@ -192,13 +212,56 @@ impl<'s> Parser<'s> {
         }
     }
 
+    // Parses attribute argument
+    fn attribute_arg(&mut self) -> AttributeArg {
+        let tk = self.expect(TokenKind::Id);
+        AttributeArg {
+            span: tk.span,
+            name: tk.lexeme,
+        }
+    }
+
+    // Parses attribute: `@name(arg, ...)`
+    fn attribute(&mut self) -> Attribute {
+        self.start_node(SyntaxKind::Attribute);
+        let start_span = self.expect(TokenKind::At).span;
+        let name = self.expect(TokenKind::Id).lexeme;
+        let args = self.sep_by(
+            TokenKind::Lparen,
+            TokenKind::Rparen,
+            TokenKind::Comma,
+            |p| p.attribute_arg(),
+        );
+        let end_span = self.prev().span.clone();
+        self.finish_node();
+
+        Attribute {
+            span: start_span + end_span,
+            name,
+            args,
+        }
+    }
+
     // Parses top-level item
     pub fn item(&mut self) -> Item {
+        // Doc comment is placed before or after attributes
+        let mut docs = self.take_docs();
+        self.start_node(SyntaxKind::Item);
+        let mut attrs = Vec::new();
+        while self.check(TokenKind::At) {
+            attrs.push(self.attribute());
+        }
+        docs = docs.or_else(|| self.take_docs());
+
         let start_span = self.peek().span.clone();
         let (publicity, kind) = self.item_inner();
         let end_span = self.prev().span.clone();
+        self.finish_node();
 
         Item {
+            id: self.node_id(),
+            docs,
+            attrs,
             span: start_span + end_span,
             publicity,
             kind,
@@ -9,7 +9,15 @@ mod stmt;
 /// Imports
 use crate::errors::ParseError;
 use ast::item::Module;
-use common::token::{Token, TokenKind};
+use common::{
+    id::HirId,
+    token::{Token, TokenKind},
+};
+use cst::{
+    builder::{Checkpoint, GreenBuilder},
+    kind::SyntaxKind,
+    red::SyntaxNode,
+};
 use lexer::Lexer;
 use macros::bail;
 use miette::NamedSource;
@@ -17,6 +25,11 @@ use std::sync::Arc;
 
 /// Parser is struct that converts a stream of tokens
 /// produced by the lexer into an abstract syntax tree (AST).
+///
+/// Alongside the AST, parser builds lossless concrete syntax
+/// tree (CST), that retains every token and trivia, so the
+/// source could be reproduced byte for byte.
+///
 pub struct Parser<'s> {
     /// Named source of the file
     pub(crate) source: Arc<NamedSource<String>>,
@@ -34,6 +47,12 @@ pub struct Parser<'s> {
     /// Lookahead token
     /// (used for predictive parsing)
     next: Option<Token>,
+
+    /// Concrete syntax tree builder
+    cst: GreenBuilder,
+
+    /// Id, that will be given to the next node
+    next_id: u32,
 }
 
 /// Implementation
@@ -48,16 +67,76 @@ impl<'s> Parser<'s> {
             previous: None,
             current,
             next,
+            cst: GreenBuilder::default(),
+            next_id: 0,
         }
     }
 
     /// Parses module
     pub fn parse(&mut self) -> Module {
+        macros::ice::set_phase("parsing");
+        self.cst.start_node(SyntaxKind::Module);
         let mut items = Vec::new();
         while self.current.is_some() {
             items.push(self.item())
         }
-        Module { items }
+        self.flush_trivia();
+        self.cst.finish_node();
+        Module {
+            items,
+            idents: self.lexer.idents().to_vec(),
+        }
+    }
+
+    /// Parses module along with its concrete syntax tree
+    pub fn parse_with_cst(&mut self) -> (Module, SyntaxNode) {
+        let module = self.parse();
+        let green = std::mem::take(&mut self.cst).finish();
+        (module, SyntaxNode::new_root(green))
+    }
+
+    /// Gives out the next node id
+    pub(crate) fn node_id(&mut self) -> HirId {
+        let id = HirId(self.lexer.file(), self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    /// Adds trivia, that precedes the current token, to the concrete syntax tree
+    fn flush_trivia(&mut self) {
+        let offset = match &self.current {
+            Some(tk) => tk.span.1.start,
+            None => usize::MAX,
+        };
+        for trivia in self.lexer.take_trivia(offset) {
+            self.cst.token(
+                SyntaxKind::Trivia(trivia.kind),
+                &self.source.inner()[trivia.span.1],
+            );
+        }
+    }
+
+    /// Starts concrete syntax tree node. Preceding trivia is left outside
+    /// of the node, so every node starts with a token
+    pub(crate) fn start_node(&mut self, kind: SyntaxKind) {
+        self.flush_trivia();
+        self.cst.start_node(kind);
+    }
+
+    /// Returns concrete syntax tree checkpoint before the current token
+    pub(crate) fn checkpoint(&mut self) -> Checkpoint {
+        self.flush_trivia();
+        self.cst.checkpoint()
+    }
+
+    /// Starts concrete syntax tree node at the checkpoint
+    pub(crate) fn start_node_at(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        self.cst.start_node_at(checkpoint, kind);
+    }
+
+    /// Finishes concrete syntax tree node
+    pub(crate) fn finish_node(&mut self) {
+        self.cst.finish_node();
     }
 
     /// Sep by parsing
@@ -113,11 +192,7 @@ impl<'s> Parser<'s> {
     pub(crate) fn check(&self, tk: TokenKind) -> bool {
         match &self.current {
             Some(it) => {
-                if it.kind == tk {
-                    true
-                } else {
-                    false
-                }
+                it.kind == tk
             }
             None => false,
         }
@@ -171,8 +246,21 @@ impl<'s> Parser<'s> {
         }
     }
 
-    /// Advances current token
+    /// Takes doc comment, attached to the current token
+    pub(crate) fn take_docs(&mut self) -> Option<String> {
+        let offset = self.current.as_ref()?.span.1.start;
+        self.lexer.take_docs(offset)
+    }
+
+    /// Advances current token, adding it to the concrete syntax tree
     pub(crate) fn bump(&mut self) -> Token {
+        self.flush_trivia();
+        if let Some(tk) = &self.current {
+            self.cst.token(
+                SyntaxKind::Token(tk.kind.clone()),
+                &self.source.inner()[tk.span.1.clone()],
+            );
+        }
         self.previous = self.current.take();
         self.current = self.next.take();
         self.next = self.lexer.next();
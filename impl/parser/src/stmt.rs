@@ -5,6 +5,7 @@ use ast::{
     stmt::{Block, Stmt, StmtKind},
 };
 use common::token::{Span, TokenKind};
+use cst::kind::SyntaxKind;
 use macros::bail;
 
 /// Implementation
@@ -14,7 +15,7 @@ impl<'s> Parser<'s> {
         // Bumping `let`
         self.bump();
 
-        let name = self.expect(TokenKind::Id).lexeme;
+        let name = self.expect(TokenKind::Id);
         let hint = if self.check(TokenKind::Colon) {
             self.bump();
             self.type_hint()
@@ -25,7 +26,7 @@ impl<'s> Parser<'s> {
         self.expect(TokenKind::Eq);
         let expr = self.expr();
 
-        StmtKind::Let(name, hint, expr)
+        StmtKind::Let(name.lexeme, name.span, hint, expr)
     }
 
     /// Expression statement
@@ -78,13 +79,24 @@ impl<'s> Parser<'s> {
 
     /// Statement parsing
     fn stmt(&mut self) -> Stmt {
+        self.start_node(if self.check(TokenKind::Let) {
+            SyntaxKind::LetStmt
+        } else {
+            SyntaxKind::ExprStmt
+        });
         let (span, kind) = self.stmt_inner();
+        self.finish_node();
 
-        Stmt { span, kind }
+        Stmt {
+            id: self.node_id(),
+            span,
+            kind,
+        }
     }
 
     /// Block parsing
     pub fn block(&mut self) -> Block {
+        self.start_node(SyntaxKind::Block);
         let start_span = self.peek().span.clone();
         let mut stmts = Vec::new();
 
@@ -94,6 +106,7 @@ impl<'s> Parser<'s> {
         }
         self.expect(TokenKind::Rbrace);
         let end_span = self.prev().span.clone();
+        self.finish_node();
 
         Block {
             span: start_span + end_span,
@@ -0,0 +1,133 @@
+/// Imports
+use common::{source::SourceMap, token::TokenKind};
+use cst::{kind::SyntaxKind, red::SyntaxNode};
+use lexer::Lexer;
+use parser::Parser;
+
+/// Parses source, returns its concrete syntax tree
+fn cst(src: &str) -> SyntaxNode {
+    let mut sources = SourceMap::default();
+    let id = sources.add("test.bit", src.to_string());
+    let file = sources.get(id);
+    Parser::new(file.source(), Lexer::new(file))
+        .parse_with_cst()
+        .1
+}
+
+#[test]
+fn cst_reproduces_source() {
+    let src = r####"# Leading comment
+use io as console
+
+/// A point
+@allow(dead_code)
+struct Point<T> {
+    /// Horizontal
+    x: T,   # trailing comment
+    y: T
+}
+
+enum Shape {
+    Dot(Point<Int>),
+    Empty
+}
+
+fn area(a: Int, b: Int) -> Int {
+    let f = |x, y| x * (y + 1);
+    let g = || 0;
+    if a >= b && !false {
+        f(a, b)
+    } else {
+        -g() | 3 ^ 1
+    }
+}
+
+fn main() {
+    let name = "world";
+    let raw = r#"C:\dir\{x} "quoted""#;
+    let text = """
+        Hello, {name}!
+          {{indented}} {1 + 2}
+        """;
+    let s = "{name}: {area(1, 2)}, {3.5}";
+    let p = Point(1, 2);
+    p.x = 'c';
+}
+"####;
+    let root = cst(src);
+    assert_eq!(root.to_string(), src);
+    assert_eq!(root.range(), 0..src.len());
+}
+
+#[test]
+fn cst_reproduces_trivia_only_sources() {
+    for src in ["", "   \n# only comment", "fn main() {}\n\n# eof comment"] {
+        assert_eq!(cst(src).to_string(), src);
+    }
+}
+
+#[test]
+fn cst_nodes_cover_their_tokens() {
+    let src = "fn main() {\n    let a = 1 + 2 * 3; # math\n    a.b(4);\n}\n";
+    let root = cst(src);
+    assert_eq!(*root.kind(), SyntaxKind::Module);
+
+    let kinds = root
+        .descendants()
+        .iter()
+        .map(|node| node.kind().clone())
+        .filter(|kind| {
+            matches!(
+                kind,
+                SyntaxKind::BinExpr | SyntaxKind::CallExpr | SyntaxKind::FieldExpr
+            )
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        kinds,
+        [
+            SyntaxKind::BinExpr,
+            SyntaxKind::BinExpr,
+            SyntaxKind::CallExpr,
+            SyntaxKind::FieldExpr
+        ]
+    );
+
+    for node in root.descendants() {
+        assert_eq!(&src[node.range()], node.text());
+        for child in node.children() {
+            assert_eq!(child.parent().map(|it| it.range()), Some(node.range()));
+        }
+    }
+
+    let bin = root
+        .descendants()
+        .into_iter()
+        .find(|node| *node.kind() == SyntaxKind::BinExpr)
+        .unwrap();
+    assert_eq!(bin.text(), "1 + 2 * 3");
+
+    let call = root
+        .descendants()
+        .into_iter()
+        .find(|node| *node.kind() == SyntaxKind::CallExpr)
+        .unwrap();
+    assert_eq!(call.text(), "a.b(4)");
+}
+
+#[test]
+fn tokens_are_found_by_offset() {
+    let src = "fn main() {\n    let a = 1; # note\n}";
+    let root = cst(src);
+
+    let token = root.token_at_offset(src.find("a =").unwrap()).unwrap();
+    assert_eq!(*token.kind(), SyntaxKind::Token(TokenKind::Id));
+    assert_eq!(token.text(), "a");
+    assert_eq!(*token.parent().kind(), SyntaxKind::LetStmt);
+
+    let comment = root.token_at_offset(src.find("note").unwrap()).unwrap();
+    assert!(comment.is_trivia());
+    assert_eq!(comment.text(), "# note");
+
+    assert!(root.token_at_offset(src.len()).is_none());
+}
@@ -1,11 +1,11 @@
 /// Imports
 use crate::ty::Ty;
-use common::token::Span;
+use common::{symbol::Symbol, token::Span};
 
 /// Function param
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Param {
     pub span: Span,
-    pub name: String,
+    pub name: Symbol,
     pub ty: Ty,
 }
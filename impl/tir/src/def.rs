@@ -0,0 +1,174 @@
+/// Imports
+use crate::ty::Ty;
+use ast::atom::Publicity;
+use common::{source::FileId, symbol::Symbol, token::Span};
+use id_arena::Id;
+use macros::bug;
+use std::collections::HashMap;
+
+/// Represents structure field
+#[derive(Clone)]
+pub struct FieldDef {
+    /// Doc comment of the field
+    pub docs: Option<String>,
+
+    /// Span of the field definition
+    pub span: Span,
+
+    /// Field name
+    pub name: Symbol,
+
+    /// Non-instantiated field type
+    pub ty: Ty,
+}
+
+/// Represents structure type
+#[derive(Clone)]
+pub struct StructDef {
+    /// Doc comment of the structure
+    pub docs: Option<String>,
+
+    /// Span of the structure definition
+    pub span: Span,
+
+    /// Structure name
+    pub name: Symbol,
+
+    /// Structure generics
+    pub generics: Vec<Symbol>,
+
+    /// Structure fields
+    pub fields: Vec<FieldDef>,
+}
+
+/// Defines enum variant
+#[derive(Clone)]
+pub struct VariantDef {
+    /// Doc comment of the variant
+    pub docs: Option<String>,
+
+    /// Span of the variant definition
+    pub span: Span,
+
+    /// Variant name
+    pub name: Symbol,
+
+    /// Non-instantiated variant params
+    pub fields: Vec<Ty>,
+}
+
+/// Represents enum definition in types context
+#[derive(Clone)]
+pub struct EnumDef {
+    /// Doc comment of the enum
+    pub docs: Option<String>,
+
+    /// Span of the enum definition
+    pub span: Span,
+
+    /// Enum name
+    pub name: Symbol,
+
+    /// Enum generics
+    pub generics: Vec<Symbol>,
+
+    /// Enum variants
+    pub variants: Vec<VariantDef>,
+}
+
+/// Represents adt definition
+#[derive(Clone)]
+pub enum AdtDef {
+    Struct(StructDef),
+    Enum(EnumDef),
+}
+
+/// Implementation
+impl AdtDef {
+    // Returns ADT name
+    pub fn name(&self) -> Symbol {
+        match self {
+            AdtDef::Struct(s) => s.name,
+            AdtDef::Enum(e) => e.name,
+        }
+    }
+
+    // Returns ADT doc comment
+    pub fn docs(&self) -> Option<&str> {
+        match self {
+            AdtDef::Struct(s) => s.docs.as_deref(),
+            AdtDef::Enum(e) => e.docs.as_deref(),
+        }
+    }
+
+    // Returns file of the module, where ADT is defined
+    pub fn file(&self) -> FileId {
+        match self {
+            AdtDef::Struct(s) => s.span.0,
+            AdtDef::Enum(e) => e.span.0,
+        }
+    }
+
+    // Returns ADT as StructDef if it is, else emits bug
+    pub fn as_struct(&self) -> &StructDef {
+        match self {
+            AdtDef::Enum(e) => bug!("expected struct, got enum `{}`", e.name),
+            AdtDef::Struct(s) => s,
+        }
+    }
+
+    // Returns ADT as EnumDef if it is, else emits bug
+    pub fn as_enum(&self) -> &EnumDef {
+        match self {
+            AdtDef::Struct(s) => bug!("expected enum, got struct `{}`", s.name),
+            AdtDef::Enum(e) => e,
+        }
+    }
+}
+
+/// Represents function definition in types context
+pub struct FnDef {
+    /// Doc comment of the function
+    pub docs: Option<String>,
+
+    /// Span of the enum definition
+    pub span: Span,
+
+    /// Function name
+    pub name: Symbol,
+
+    /// Function generics
+    pub generics: Vec<Symbol>,
+
+    /// Function non-instantiated params
+    pub params: Vec<Ty>,
+
+    /// Spans of the params declarations
+    pub param_spans: Vec<Span>,
+
+    /// Function non-instantiated return type
+    pub ret: Ty,
+}
+
+/// Definition kind
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ItemDefKind {
+    /// ADT definition
+    Adt(Id<AdtDef>),
+
+    /// Function definition
+    Fn(Id<FnDef>),
+}
+
+/// Item definition
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ItemDef {
+    pub publicity: Publicity,
+    pub kind: ItemDefKind,
+}
+
+/// Represents module
+pub struct ModDef {
+    pub file: FileId,
+    pub defs: HashMap<Symbol, ItemDef>,
+}
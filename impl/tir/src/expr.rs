@@ -1,7 +1,17 @@
 /// Imports
 use crate::{atom::Param, stmt::Block, ty::Ty};
 use ast::expr::{BinOp, Lit, UnOp};
-use common::token::Span;
+use common::{id::HirId, symbol::Symbol, token::Span};
+
+/// Part of the interpolated string
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum InterpPart {
+    /// Literal text
+    Lit(String),
+
+    /// Interpolated value
+    Expr(Expr),
+}
 
 /// Expression kind
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -22,10 +32,10 @@ pub enum ExprKind {
     Call(Box<Expr>, Vec<Expr>),
 
     /// Id expr (e.g, `foo`)
-    Id(String),
+    Id(Symbol),
 
     /// Field expr (e.g, `wibble.wobble`)
-    Field(Box<Expr>, String),
+    Field(Box<Expr>, Symbol),
 
     /// Cast expr (e.g. `foo as f64`)
     Cast(Box<Expr>, Ty),
@@ -38,11 +48,15 @@ pub enum ExprKind {
 
     /// Block
     Block(Box<Block>),
+
+    /// Interpolated string (e.g `"hello, {name}"`)
+    Interp(Vec<InterpPart>),
 }
 
 /// Expression
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Expr {
+    pub id: HirId,
     pub kind: ExprKind,
     pub span: Span,
     pub ty: Ty,
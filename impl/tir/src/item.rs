@@ -1,45 +1,47 @@
 /// Imports
 use crate::{atom::Param, stmt::Block, ty::Ty};
 use ast::atom::Publicity;
-use common::token::Span;
+use common::{id::HirId, symbol::Symbol, token::Span};
 
 /// Represents struct field
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Field {
+    pub docs: Option<String>,
     pub span: Span,
-    pub name: String,
+    pub name: Symbol,
     pub ty: Ty,
 }
 
 /// Represents struct top-level item
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Struct {
-    pub name: String,
-    pub generics: Vec<String>,
+    pub name: Symbol,
+    pub generics: Vec<Symbol>,
     pub fields: Vec<Field>,
 }
 
 /// Represents enum variant
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Variant {
+    pub docs: Option<String>,
     pub span: Span,
-    pub name: String,
+    pub name: Symbol,
     pub params: Vec<Ty>,
 }
 
 /// Represents enum top-level item
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Enum {
-    pub name: String,
-    pub generics: Vec<String>,
+    pub name: Symbol,
+    pub generics: Vec<Symbol>,
     pub variants: Vec<Variant>,
 }
 
 /// Function top-level item
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Function {
-    pub name: String,
-    pub generics: Vec<String>,
+    pub name: Symbol,
+    pub generics: Vec<Symbol>,
     pub params: Vec<Param>,
     pub ty: Ty,
     pub block: Block,
@@ -49,10 +51,10 @@ pub struct Function {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum UseKind {
     /// `as $name`
-    As(String),
+    As(Symbol),
 
     /// `for ...`
-    For(Vec<String>),
+    For(Vec<Symbol>),
 
     /// Just import
     Just,
@@ -84,6 +86,8 @@ pub enum ItemKind {
 /// Top-level item
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Item {
+    pub id: HirId,
+    pub docs: Option<String>,
     pub publicity: Publicity,
     pub kind: ItemKind,
     pub span: Span,
@@ -1,6 +1,9 @@
 /// Modules
 pub mod atom;
+pub mod def;
 pub mod expr;
 pub mod item;
+pub mod results;
 pub mod stmt;
 pub mod ty;
+pub mod visit;
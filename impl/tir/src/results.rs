@@ -0,0 +1,77 @@
+/// Imports
+use crate::{
+    def::{AdtDef, ItemDef, ModDef},
+    ty::{GenericArgs, Ty},
+};
+use common::{id::HirId, symbol::Symbol, token::Span};
+use id_arena::Id;
+use std::collections::HashMap;
+
+/// Definition, that the name or field expression refers to
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Resolution {
+    /// Top-level item
+    Item(ItemDef),
+
+    /// Imported module
+    Mod(Id<ModDef>),
+
+    /// Local binding or param, defined at the span
+    Local(Span),
+
+    /// Structure field (struct, field name)
+    Field(Id<AdtDef>, Symbol),
+
+    /// Enum variant (enum, variant name)
+    Variant(Id<AdtDef>, Symbol),
+}
+
+/// Adjustment, applied to the expression by the type checker
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Adjustment {
+    /// Polymorphic definition is instantiated with the generic args:
+    /// generic function or constructor, or generalized local
+    Instantiate(GenericArgs),
+}
+
+/// Results of the type checking, keyed by node ids.
+///
+/// Filled by the type checker for every checked module, so tooling
+/// can look up the type or the definition of the node by its id.
+///
+#[derive(Debug, Default)]
+pub struct TypeckResults {
+    /// Zonked types of expressions and statements
+    pub node_types: HashMap<HirId, Ty>,
+
+    /// Definitions of name and field expressions
+    pub resolutions: HashMap<HirId, Resolution>,
+
+    /// Adjustments of expressions
+    pub adjustments: HashMap<HirId, Adjustment>,
+}
+
+/// Implementation
+impl TypeckResults {
+    /// Returns type of the node
+    pub fn node_type(&self, id: HirId) -> Option<Ty> {
+        self.node_types.get(&id).copied()
+    }
+
+    /// Returns definition, that the node refers to
+    pub fn resolution(&self, id: HirId) -> Option<&Resolution> {
+        self.resolutions.get(&id)
+    }
+
+    /// Returns adjustment of the node
+    pub fn adjustment(&self, id: HirId) -> Option<&Adjustment> {
+        self.adjustments.get(&id)
+    }
+
+    /// Moves results of the other module into these results
+    pub fn extend(&mut self, other: TypeckResults) {
+        self.node_types.extend(other.node_types);
+        self.resolutions.extend(other.resolutions);
+        self.adjustments.extend(other.adjustments);
+    }
+}
@@ -1,12 +1,12 @@
 /// Imports
 use crate::{expr::Expr, ty::Ty};
-use common::token::Span;
+use common::{id::HirId, symbol::Symbol, token::Span};
 
 /// Statement kind
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum StmtKind {
-    /// Let definition
-    Let(String, Ty, Expr),
+    /// Let definition: name, its span, type and value
+    Let(Symbol, Span, Ty, Expr),
 
     /// Expr without trailing semi-colon
     Expr(Expr),
@@ -18,6 +18,7 @@ pub enum StmtKind {
 /// Represents statement
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Stmt {
+    pub id: HirId,
     pub kind: StmtKind,
     pub span: Span,
     pub ty: Ty,
@@ -1,150 +1,14 @@
 /// Imports
-use ast::atom::Publicity;
-use common::token::Span;
+use crate::def::{AdtDef, FnDef, ModDef};
+use common::symbol::Symbol;
 use id_arena::Id;
-use macros::bug;
-use miette::NamedSource;
-use std::fmt::Debug;
-use std::{collections::HashMap, sync::Arc};
-
-/// Represents structure field
-#[derive(Clone)]
-pub struct FieldDef {
-    /// Span of the field definition
-    pub span: Span,
-
-    /// Field name
-    pub name: String,
-
-    /// Non-instantiated field type
-    pub ty: Ty,
-}
-
-/// Represents structure type
-#[derive(Clone)]
-pub struct StructDef {
-    /// Span of the structure definition
-    pub span: Span,
-
-    /// Structure name
-    pub name: String,
-
-    /// Structure generics
-    pub generics: Vec<String>,
-
-    /// Structure fields
-    pub fields: Vec<FieldDef>,
-}
-
-/// Defines enum variant
-#[derive(Clone)]
-pub struct VariantDef {
-    /// Span of the variant definition
-    pub span: Span,
-
-    /// Variant name
-    pub name: String,
-
-    /// Non-instantiated variant params
-    pub fields: Vec<Ty>,
-}
-
-/// Represents enum definition in types context
-#[derive(Clone)]
-pub struct EnumDef {
-    /// Span of the enum definition
-    pub span: Span,
-
-    /// Enum name
-    pub name: String,
-
-    /// Enum generics
-    pub generics: Vec<String>,
-
-    /// Enum variants
-    pub variants: Vec<VariantDef>,
-}
-
-/// Represents adt definition
-#[derive(Clone)]
-pub enum AdtDef {
-    Struct(StructDef),
-    Enum(EnumDef),
-}
-
-/// Implementation
-impl AdtDef {
-    // Returns ADT name
-    pub fn name(&self) -> String {
-        match self {
-            AdtDef::Struct(s) => s.name.clone(),
-            AdtDef::Enum(e) => e.name.clone(),
-        }
-    }
-
-    // Returns ADT as StructDef if it is, else emits bug
-    pub fn as_struct(&self) -> &StructDef {
-        match self {
-            AdtDef::Enum(_) => bug!("expected struct, got enum by id"),
-            AdtDef::Struct(s) => s,
-        }
-    }
-
-    // Returns ADT as EnumDef if it is, else emits bug
-    pub fn as_enum(&self) -> &EnumDef {
-        match self {
-            AdtDef::Struct(_) => bug!("expected struct, got enum by id"),
-            AdtDef::Enum(e) => e,
-        }
-    }
-}
-
-/// Represents function definition in types context
-pub struct FnDef {
-    /// Span of the enum definition
-    pub span: Span,
-
-    /// Function name
-    pub name: String,
-
-    /// Function generics
-    pub generics: Vec<String>,
-
-    /// Function non-instantiated params
-    pub params: Vec<Ty>,
-
-    /// Function non-instantiated return type
-    pub ret: Ty,
-}
-
-/// Definition kind
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum ItemDefKind {
-    /// ADT definition
-    Adt(Id<AdtDef>),
-
-    /// Function definition
-    Fn(Id<FnDef>),
-}
-
-/// Item definition
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub struct ItemDef {
-    pub publicity: Publicity,
-    pub kind: ItemDefKind,
-}
-
-/// Represents module
-pub struct ModDef {
-    pub source: Arc<NamedSource<String>>,
-    pub defs: HashMap<String, ItemDef>,
-}
 
 /// Defines type variable
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum TyVar {
-    /// Unbound type variable
-    Unbound,
+    /// Unbound type variable, carrying the binding level
+    /// it was created at (used by let-generalization)
+    Unbound(usize),
 
     /// Bound type variable
     Bound(Ty),
@@ -165,7 +29,7 @@ pub struct FnSig {
 
 /// Defines meta type
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum MetaTy {
+pub enum TyMeta {
     /// Module type
     Module(Id<ModDef>),
 
@@ -173,13 +37,17 @@ pub enum MetaTy {
     Adt(Id<AdtDef>),
 
     /// Variant meta type
-    Variant(Id<AdtDef>, String),
+    Variant(Id<AdtDef>, Symbol),
 }
 
-/// Defines the type used by type system
-/// and typed intermediate representation (TIR)
+/// Defines kind of the type used by type system
+/// and typed intermediate representation (TIR).
+///
+/// Kinds are interned by the type context, nested
+/// types are referenced by [`Ty`] handles.
+///
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
-pub enum Ty {
+pub enum TyKind {
     /// A primitive signed integer type.
     Int,
 
@@ -202,17 +70,66 @@ pub enum Ty {
     FnDef(Id<FnDef>, GenericArgs),
 
     /// Function pointer type
-    FnRef(Box<FnSig>),
+    FnRef(FnSig),
 
     /// Generic parameter type `T`, `K`
     Generic(usize),
 
+    /// Parameter of a let-bound type scheme, replaces
+    /// the quantified variable after zonking
+    Param(usize),
+
     /// An inference type variable
     Var(Id<TyVar>),
 
     /// Meta type
-    Meta(MetaTy),
+    Meta(TyMeta),
 
     /// A placeholder for a type which could not be computed
     Error,
 }
+
+/// Handle to the interned type.
+///
+/// Types are hash-consed by the type context: structurally equal types
+/// share a single handle, so comparing handles compares types, and
+/// copying a handle never allocates. The kind of the type is looked up
+/// through the type context.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ty(Id<TyKind>);
+
+/// Implementation
+impl Ty {
+    /// Creates handle from the interned kind id
+    pub fn new(id: Id<TyKind>) -> Self {
+        Self(id)
+    }
+
+    /// Returns interned kind id
+    pub fn id(self) -> Id<TyKind> {
+        self.0
+    }
+}
+
+/// Defines type scheme — a type quantified over
+/// inference variables (`forall a b. ty`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Scheme {
+    /// Quantified type variables
+    pub vars: Vec<Id<TyVar>>,
+
+    /// Scheme type
+    pub ty: Ty,
+}
+
+/// Implementation
+impl Scheme {
+    /// Creates monomorphic scheme, that quantifies over nothing
+    pub fn mono(ty: Ty) -> Self {
+        Self {
+            vars: Vec::new(),
+            ty,
+        }
+    }
+}
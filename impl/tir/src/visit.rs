@@ -0,0 +1,522 @@
+/// Imports
+use crate::{
+    atom::Param,
+    expr::{Expr, ExprKind, InterpPart},
+    item::{Field, Function, Item, ItemKind, Module, Use, Variant},
+    stmt::{Block, Stmt, StmtKind},
+    ty::Ty,
+};
+
+/// TIR visitor. Every method defaults to the matching `walk_*`
+/// function, that visits children of the node, so visitors override
+/// only nodes they care about, calling `walk_*` to keep descending.
+///
+/// Types are interned handles, so [`Visitor::visit_ty`] is a leaf:
+/// it's called for every type, stored in the visited nodes.
+///
+pub trait Visitor<'tir>: Sized {
+    fn visit_module(&mut self, module: &'tir Module) {
+        walk_module(self, module)
+    }
+
+    fn visit_item(&mut self, item: &'tir Item) {
+        walk_item(self, item)
+    }
+
+    fn visit_field(&mut self, field: &'tir Field) {
+        walk_field(self, field)
+    }
+
+    fn visit_variant(&mut self, variant: &'tir Variant) {
+        walk_variant(self, variant)
+    }
+
+    fn visit_function(&mut self, function: &'tir Function) {
+        walk_function(self, function)
+    }
+
+    fn visit_use(&mut self, _use: &'tir Use) {}
+
+    fn visit_param(&mut self, param: &'tir Param) {
+        walk_param(self, param)
+    }
+
+    fn visit_block(&mut self, block: &'tir Block) {
+        walk_block(self, block)
+    }
+
+    fn visit_stmt(&mut self, stmt: &'tir Stmt) {
+        walk_stmt(self, stmt)
+    }
+
+    fn visit_expr(&mut self, expr: &'tir Expr) {
+        walk_expr(self, expr)
+    }
+
+    fn visit_ty(&mut self, _ty: Ty) {}
+}
+
+/// Visits module items
+pub fn walk_module<'tir, V: Visitor<'tir>>(v: &mut V, module: &'tir Module) {
+    module.items.iter().for_each(|item| v.visit_item(item));
+}
+
+/// Visits item kind
+pub fn walk_item<'tir, V: Visitor<'tir>>(v: &mut V, item: &'tir Item) {
+    match &item.kind {
+        ItemKind::Struct(it) => it.fields.iter().for_each(|field| v.visit_field(field)),
+        ItemKind::Enum(it) => it
+            .variants
+            .iter()
+            .for_each(|variant| v.visit_variant(variant)),
+        ItemKind::Function(it) => v.visit_function(it),
+        ItemKind::Use(it) => v.visit_use(it),
+    }
+}
+
+/// Visits field type
+pub fn walk_field<'tir, V: Visitor<'tir>>(v: &mut V, field: &'tir Field) {
+    v.visit_ty(field.ty);
+}
+
+/// Visits variant params types
+pub fn walk_variant<'tir, V: Visitor<'tir>>(v: &mut V, variant: &'tir Variant) {
+    variant.params.iter().for_each(|ty| v.visit_ty(*ty));
+}
+
+/// Visits function params, body and function type
+pub fn walk_function<'tir, V: Visitor<'tir>>(v: &mut V, function: &'tir Function) {
+    function
+        .params
+        .iter()
+        .for_each(|param| v.visit_param(param));
+    v.visit_block(&function.block);
+    v.visit_ty(function.ty);
+}
+
+/// Visits param type
+pub fn walk_param<'tir, V: Visitor<'tir>>(v: &mut V, param: &'tir Param) {
+    v.visit_ty(param.ty);
+}
+
+/// Visits block statements
+pub fn walk_block<'tir, V: Visitor<'tir>>(v: &mut V, block: &'tir Block) {
+    block.stmts.iter().for_each(|stmt| v.visit_stmt(stmt));
+}
+
+/// Visits statement kind and its type
+pub fn walk_stmt<'tir, V: Visitor<'tir>>(v: &mut V, stmt: &'tir Stmt) {
+    match &stmt.kind {
+        StmtKind::Let(_, _, ty, expr) => {
+            v.visit_ty(*ty);
+            v.visit_expr(expr);
+        }
+        StmtKind::Expr(expr) | StmtKind::Semi(expr) => v.visit_expr(expr),
+    }
+    v.visit_ty(stmt.ty);
+}
+
+/// Visits subexpressions in the evaluation order, then expression type
+pub fn walk_expr<'tir, V: Visitor<'tir>>(v: &mut V, expr: &'tir Expr) {
+    match &expr.kind {
+        ExprKind::Lit(_) | ExprKind::Id(_) => {}
+        ExprKind::Unary(_, value) | ExprKind::Field(value, _) => v.visit_expr(value),
+        ExprKind::Bin(_, lhs, rhs) | ExprKind::Assign(lhs, rhs) => {
+            v.visit_expr(lhs);
+            v.visit_expr(rhs);
+        }
+        ExprKind::If(cond, then, else_) => {
+            v.visit_expr(cond);
+            v.visit_expr(then);
+            if let Some(else_) = else_ {
+                v.visit_expr(else_);
+            }
+        }
+        ExprKind::Call(what, args) => {
+            v.visit_expr(what);
+            args.iter().for_each(|arg| v.visit_expr(arg));
+        }
+        ExprKind::Cast(what, ty) => {
+            v.visit_expr(what);
+            v.visit_ty(*ty);
+        }
+        ExprKind::Closure(params, body) => {
+            params.iter().for_each(|param| v.visit_param(param));
+            v.visit_expr(body);
+        }
+        ExprKind::Block(block) => v.visit_block(block),
+        ExprKind::Interp(parts) => {
+            for part in parts {
+                if let InterpPart::Expr(value) = part {
+                    v.visit_expr(value);
+                }
+            }
+        }
+    }
+    v.visit_ty(expr.ty);
+}
+
+/// Mutable TIR visitor, that changes nodes in place.
+/// Methods default to the matching `walk_*_mut` functions.
+pub trait VisitorMut: Sized {
+    fn visit_module_mut(&mut self, module: &mut Module) {
+        walk_module_mut(self, module)
+    }
+
+    fn visit_item_mut(&mut self, item: &mut Item) {
+        walk_item_mut(self, item)
+    }
+
+    fn visit_field_mut(&mut self, field: &mut Field) {
+        walk_field_mut(self, field)
+    }
+
+    fn visit_variant_mut(&mut self, variant: &mut Variant) {
+        walk_variant_mut(self, variant)
+    }
+
+    fn visit_function_mut(&mut self, function: &mut Function) {
+        walk_function_mut(self, function)
+    }
+
+    fn visit_use_mut(&mut self, _use: &mut Use) {}
+
+    fn visit_param_mut(&mut self, param: &mut Param) {
+        walk_param_mut(self, param)
+    }
+
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block)
+    }
+
+    fn visit_stmt_mut(&mut self, stmt: &mut Stmt) {
+        walk_stmt_mut(self, stmt)
+    }
+
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        walk_expr_mut(self, expr)
+    }
+
+    fn visit_ty_mut(&mut self, _ty: &mut Ty) {}
+}
+
+/// Visits module items
+pub fn walk_module_mut<V: VisitorMut>(v: &mut V, module: &mut Module) {
+    module
+        .items
+        .iter_mut()
+        .for_each(|item| v.visit_item_mut(item));
+}
+
+/// Visits item kind
+pub fn walk_item_mut<V: VisitorMut>(v: &mut V, item: &mut Item) {
+    match &mut item.kind {
+        ItemKind::Struct(it) => it
+            .fields
+            .iter_mut()
+            .for_each(|field| v.visit_field_mut(field)),
+        ItemKind::Enum(it) => it
+            .variants
+            .iter_mut()
+            .for_each(|variant| v.visit_variant_mut(variant)),
+        ItemKind::Function(it) => v.visit_function_mut(it),
+        ItemKind::Use(it) => v.visit_use_mut(it),
+    }
+}
+
+/// Visits field type
+pub fn walk_field_mut<V: VisitorMut>(v: &mut V, field: &mut Field) {
+    v.visit_ty_mut(&mut field.ty);
+}
+
+/// Visits variant params types
+pub fn walk_variant_mut<V: VisitorMut>(v: &mut V, variant: &mut Variant) {
+    variant.params.iter_mut().for_each(|ty| v.visit_ty_mut(ty));
+}
+
+/// Visits function params, body and function type
+pub fn walk_function_mut<V: VisitorMut>(v: &mut V, function: &mut Function) {
+    function
+        .params
+        .iter_mut()
+        .for_each(|param| v.visit_param_mut(param));
+    v.visit_block_mut(&mut function.block);
+    v.visit_ty_mut(&mut function.ty);
+}
+
+/// Visits param type
+pub fn walk_param_mut<V: VisitorMut>(v: &mut V, param: &mut Param) {
+    v.visit_ty_mut(&mut param.ty);
+}
+
+/// Visits block statements
+pub fn walk_block_mut<V: VisitorMut>(v: &mut V, block: &mut Block) {
+    block
+        .stmts
+        .iter_mut()
+        .for_each(|stmt| v.visit_stmt_mut(stmt));
+}
+
+/// Visits statement kind and its type
+pub fn walk_stmt_mut<V: VisitorMut>(v: &mut V, stmt: &mut Stmt) {
+    match &mut stmt.kind {
+        StmtKind::Let(_, _, ty, expr) => {
+            v.visit_ty_mut(ty);
+            v.visit_expr_mut(expr);
+        }
+        StmtKind::Expr(expr) | StmtKind::Semi(expr) => v.visit_expr_mut(expr),
+    }
+    v.visit_ty_mut(&mut stmt.ty);
+}
+
+/// Visits subexpressions in the evaluation order, then expression type
+pub fn walk_expr_mut<V: VisitorMut>(v: &mut V, expr: &mut Expr) {
+    match &mut expr.kind {
+        ExprKind::Lit(_) | ExprKind::Id(_) => {}
+        ExprKind::Unary(_, value) | ExprKind::Field(value, _) => v.visit_expr_mut(value),
+        ExprKind::Bin(_, lhs, rhs) | ExprKind::Assign(lhs, rhs) => {
+            v.visit_expr_mut(lhs);
+            v.visit_expr_mut(rhs);
+        }
+        ExprKind::If(cond, then, else_) => {
+            v.visit_expr_mut(cond);
+            v.visit_expr_mut(then);
+            if let Some(else_) = else_ {
+                v.visit_expr_mut(else_);
+            }
+        }
+        ExprKind::Call(what, args) => {
+            v.visit_expr_mut(what);
+            args.iter_mut().for_each(|arg| v.visit_expr_mut(arg));
+        }
+        ExprKind::Cast(what, ty) => {
+            v.visit_expr_mut(what);
+            v.visit_ty_mut(ty);
+        }
+        ExprKind::Closure(params, body) => {
+            params.iter_mut().for_each(|param| v.visit_param_mut(param));
+            v.visit_expr_mut(body);
+        }
+        ExprKind::Block(block) => v.visit_block_mut(block),
+        ExprKind::Interp(parts) => {
+            for part in parts {
+                if let InterpPart::Expr(value) = part {
+                    v.visit_expr_mut(value);
+                }
+            }
+        }
+    }
+    v.visit_ty_mut(&mut expr.ty);
+}
+
+/// TIR folder, that rebuilds nodes by value.
+/// Methods default to the matching `fold_*` functions,
+/// that fold children and rebuild the node from them.
+pub trait Folder: Sized {
+    fn fold_module(&mut self, module: Module) -> Module {
+        fold_module(self, module)
+    }
+
+    fn fold_item(&mut self, item: Item) -> Item {
+        fold_item(self, item)
+    }
+
+    fn fold_field(&mut self, field: Field) -> Field {
+        fold_field(self, field)
+    }
+
+    fn fold_variant(&mut self, variant: Variant) -> Variant {
+        fold_variant(self, variant)
+    }
+
+    fn fold_function(&mut self, function: Function) -> Function {
+        fold_function(self, function)
+    }
+
+    fn fold_use(&mut self, use_: Use) -> Use {
+        use_
+    }
+
+    fn fold_param(&mut self, param: Param) -> Param {
+        fold_param(self, param)
+    }
+
+    fn fold_block(&mut self, block: Block) -> Block {
+        fold_block(self, block)
+    }
+
+    fn fold_stmt(&mut self, stmt: Stmt) -> Stmt {
+        fold_stmt(self, stmt)
+    }
+
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        fold_expr(self, expr)
+    }
+
+    fn fold_ty(&mut self, ty: Ty) -> Ty {
+        ty
+    }
+}
+
+/// Folds module items
+pub fn fold_module<F: Folder>(f: &mut F, module: Module) -> Module {
+    Module {
+        items: module
+            .items
+            .into_iter()
+            .map(|item| f.fold_item(item))
+            .collect(),
+    }
+}
+
+/// Folds item kind
+pub fn fold_item<F: Folder>(f: &mut F, item: Item) -> Item {
+    let kind = match item.kind {
+        ItemKind::Struct(mut it) => {
+            it.fields = it
+                .fields
+                .into_iter()
+                .map(|field| f.fold_field(field))
+                .collect();
+            ItemKind::Struct(it)
+        }
+        ItemKind::Enum(mut it) => {
+            it.variants = it
+                .variants
+                .into_iter()
+                .map(|variant| f.fold_variant(variant))
+                .collect();
+            ItemKind::Enum(it)
+        }
+        ItemKind::Function(it) => ItemKind::Function(f.fold_function(it)),
+        ItemKind::Use(it) => ItemKind::Use(f.fold_use(it)),
+    };
+    Item { kind, ..item }
+}
+
+/// Folds field type
+pub fn fold_field<F: Folder>(f: &mut F, field: Field) -> Field {
+    Field {
+        ty: f.fold_ty(field.ty),
+        ..field
+    }
+}
+
+/// Folds variant params types
+pub fn fold_variant<F: Folder>(f: &mut F, variant: Variant) -> Variant {
+    Variant {
+        params: variant.params.into_iter().map(|ty| f.fold_ty(ty)).collect(),
+        ..variant
+    }
+}
+
+/// Folds function params, body and function type
+pub fn fold_function<F: Folder>(f: &mut F, function: Function) -> Function {
+    Function {
+        params: function
+            .params
+            .into_iter()
+            .map(|param| f.fold_param(param))
+            .collect(),
+        block: f.fold_block(function.block),
+        ty: f.fold_ty(function.ty),
+        ..function
+    }
+}
+
+/// Folds param type
+pub fn fold_param<F: Folder>(f: &mut F, param: Param) -> Param {
+    Param {
+        ty: f.fold_ty(param.ty),
+        ..param
+    }
+}
+
+/// Folds block statements
+pub fn fold_block<F: Folder>(f: &mut F, block: Block) -> Block {
+    Block {
+        stmts: block
+            .stmts
+            .into_iter()
+            .map(|stmt| f.fold_stmt(stmt))
+            .collect(),
+        ..block
+    }
+}
+
+/// Folds statement kind and its type
+pub fn fold_stmt<F: Folder>(f: &mut F, stmt: Stmt) -> Stmt {
+    let kind = match stmt.kind {
+        StmtKind::Let(name, span, ty, expr) => {
+            let ty = f.fold_ty(ty);
+            StmtKind::Let(name, span, ty, f.fold_expr(expr))
+        }
+        StmtKind::Expr(expr) => StmtKind::Expr(f.fold_expr(expr)),
+        StmtKind::Semi(expr) => StmtKind::Semi(f.fold_expr(expr)),
+    };
+    Stmt {
+        kind,
+        ty: f.fold_ty(stmt.ty),
+        ..stmt
+    }
+}
+
+/// Folds subexpressions in the evaluation order, then expression type
+pub fn fold_expr<F: Folder>(f: &mut F, expr: Expr) -> Expr {
+    let kind = match expr.kind {
+        ExprKind::Lit(lit) => ExprKind::Lit(lit),
+        ExprKind::Unary(op, value) => ExprKind::Unary(op, Box::new(f.fold_expr(*value))),
+        ExprKind::Bin(op, lhs, rhs) => {
+            let lhs = f.fold_expr(*lhs);
+            ExprKind::Bin(op, Box::new(lhs), Box::new(f.fold_expr(*rhs)))
+        }
+        ExprKind::If(cond, then, else_) => {
+            let cond = f.fold_expr(*cond);
+            let then = f.fold_expr(*then);
+            ExprKind::If(
+                Box::new(cond),
+                Box::new(then),
+                else_.map(|it| Box::new(f.fold_expr(*it))),
+            )
+        }
+        ExprKind::Call(what, args) => {
+            let what = f.fold_expr(*what);
+            ExprKind::Call(
+                Box::new(what),
+                args.into_iter().map(|arg| f.fold_expr(arg)).collect(),
+            )
+        }
+        ExprKind::Id(name) => ExprKind::Id(name),
+        ExprKind::Field(what, name) => ExprKind::Field(Box::new(f.fold_expr(*what)), name),
+        ExprKind::Cast(what, ty) => {
+            let what = f.fold_expr(*what);
+            ExprKind::Cast(Box::new(what), f.fold_ty(ty))
+        }
+        ExprKind::Closure(params, body) => {
+            let params = params
+                .into_iter()
+                .map(|param| f.fold_param(param))
+                .collect();
+            ExprKind::Closure(params, Box::new(f.fold_expr(*body)))
+        }
+        ExprKind::Assign(what, to) => {
+            let what = f.fold_expr(*what);
+            ExprKind::Assign(Box::new(what), Box::new(f.fold_expr(*to)))
+        }
+        ExprKind::Block(block) => ExprKind::Block(Box::new(f.fold_block(*block))),
+        ExprKind::Interp(parts) => ExprKind::Interp(
+            parts
+                .into_iter()
+                .map(|part| match part {
+                    InterpPart::Lit(text) => InterpPart::Lit(text),
+                    InterpPart::Expr(value) => InterpPart::Expr(f.fold_expr(value)),
+                })
+                .collect(),
+        ),
+    };
+    Expr {
+        kind,
+        ty: f.fold_ty(expr.ty),
+        ..expr
+    }
+}
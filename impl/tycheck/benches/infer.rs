@@ -0,0 +1,174 @@
+/// Imports
+use lexer::Lexer;
+use parser::Parser;
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+use tycheck::{
+    check::ModuleTyck,
+    cx::{icx::InferCx, tcx::TyCx},
+    lint::{self, Level, LintLevels},
+};
+
+/// Generates `let` chain of generic calls: `let xN = id(xN-1)`
+fn let_chain(n: usize) -> String {
+    let mut src = String::from("fn id<T>(x: T) -> T { x }\nfn main() {\n    let x0 = 1;\n");
+    for i in 1..=n {
+        src.push_str(&format!("    let x{i} = id(x{});\n", i - 1));
+    }
+    src.push_str("}\n");
+    src
+}
+
+/// Generates nested generic calls over the closure param, whose
+/// type stays unknown until the closure is called
+fn var_chain(n: usize) -> String {
+    let mut src = String::from("fn id<T>(x: T) -> T { x }\nfn main() {\n    let f = |x| ");
+    src.push_str(&"id(".repeat(n));
+    src.push('x');
+    src.push_str(&")".repeat(n));
+    src.push_str(";\n    let r: Int = f(1);\n}\n");
+    src
+}
+
+/// Generates nested generic calls: `id(id(...id(1)...))`
+fn nested_calls(n: usize) -> String {
+    let mut src = String::from("fn id<T>(x: T) -> T { x }\nfn main() {\n    let x = ");
+    src.push_str(&"id(".repeat(n));
+    src.push('1');
+    src.push_str(&")".repeat(n));
+    src.push_str(";\n}\n");
+    src
+}
+
+/// Generates chain of closures, passing value through the previous one
+fn closure_chain(n: usize) -> String {
+    let mut src = String::from("fn main() {\n    let f0 = |x| x;\n");
+    for i in 1..=n {
+        src.push_str(&format!("    let f{i} = |x| f{}(x);\n", i - 1));
+    }
+    src.push_str(&format!("    let r: Int = f{n}(1);\n}}\n"));
+    src
+}
+
+/// Generates large file of structures and functions, that
+/// read their fields: mostly identifiers and field lookups
+fn large_file(n: usize) -> String {
+    let mut src = String::from("fn main() {\n    let _r = f0(P0(1, 2, 3, 4, 5, 6, 7, 8), 1);\n}\n");
+    for i in 0..n {
+        src.push_str(&format!(
+            "\nstruct P{i} {{\n    alpha: Int,\n    beta: Int,\n    gamma: Int,\n    delta: Int,\n    \
+             epsilon: Int,\n    zeta: Int,\n    eta: Int,\n    theta{i}: Int\n}}\n"
+        ));
+        let next = if i + 1 < n {
+            format!(
+                "f{}(P{}(alpha, beta, gamma, delta, epsilon, zeta, eta, theta), scale)",
+                i + 1,
+                i + 1
+            )
+        } else {
+            "theta".to_string()
+        };
+        src.push_str(&format!(
+            "\nfn f{i}(point: P{i}, scale: Int) -> Int {{\n    \
+             let alpha = point.alpha * scale;\n    let beta = point.beta + alpha;\n    \
+             let gamma = point.gamma - beta;\n    let delta = point.delta * gamma;\n    \
+             let epsilon = point.epsilon + delta;\n    let zeta = point.zeta - epsilon;\n    \
+             let eta = point.eta * zeta;\n    let theta = point.theta{i} + eta;\n    \
+             if theta > scale {{ {next} }} else {{ theta }}\n}}\n"
+        ));
+    }
+    src
+}
+
+/// Lexes source, returns amount of tokens
+fn lex(src: &str) -> usize {
+    let mut tcx = TyCx::default();
+    let file = tcx.sources.add("bench.bit", src.to_string());
+    Lexer::new(tcx.sources.get(file)).map(black_box).count()
+}
+
+/// Parses and type-checks source, returns amount of diagnostics.
+/// Generated sources leave their last bindings unused, so warnings are allowed
+fn check(src: &str) -> usize {
+    let mut tcx = TyCx::default();
+    let file = tcx.sources.add("bench.bit", src.to_string());
+    let file = tcx.sources.get(file);
+    let mut parser = Parser::new(file.source(), Lexer::new(file));
+    let module = parser.parse();
+    let mut icx = InferCx::new(&mut tcx);
+    let mut levels = LintLevels::default();
+    levels.set(lint::WARNINGS, Level::Allow);
+    let mut tyck = ModuleTyck::new(&mut icx).with_lint_levels(levels);
+    black_box(tyck.check_module(module));
+    tyck.take_diagnostics().len()
+}
+
+/// Growth of the time, when input size is doubled, that is
+/// still considered linear. Quadratic checking gives about `4.0`
+const MAX_GROWTH: f64 = 3.0;
+
+/// Runs `run` several times, prints and returns the best time
+fn measure(name: &str, mut run: impl FnMut()) -> Duration {
+    const RUNS: usize = 5;
+    let best = (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap_or_default();
+    println!("{name:<24} {best:>12.3?}");
+    best
+}
+
+/// Runs benchmark of the checking, prints and returns the best time of several runs
+fn bench(name: &str, src: &str) -> Duration {
+    measure(name, || {
+        assert_eq!(check(black_box(src)), 0, "{name}: unexpected diagnostics");
+    })
+}
+
+/// Runs `run` over sizes `n`, `2n` and `4n`, prints the growth of
+/// the time per doubling and checks that it stays close to linear
+fn scaling(name: &str, n: usize, full: bool, mut run: impl FnMut(&str, usize) -> Duration) {
+    // `cargo test` runs benchmarks without `--bench` in debug mode,
+    // so only the smallest input is checked there
+    if !full {
+        run(&format!("{name}/{n}"), n);
+        return;
+    }
+    let times = [n, 2 * n, 4 * n].map(|size| run(&format!("{name}/{size}"), size));
+    for pair in times.windows(2) {
+        let growth = pair[1].as_secs_f64() / pair[0].as_secs_f64().max(f64::EPSILON);
+        println!("{:<24} {growth:>11.2}x", format!("{name}/growth"));
+        assert!(
+            growth < MAX_GROWTH,
+            "{name}: time grows {growth:.2}x per doubling of the input"
+        );
+    }
+}
+
+fn main() {
+    let full = std::env::args().any(|arg| arg == "--bench");
+
+    scaling("let_chain", 500, full, |name, n| bench(name, &let_chain(n)));
+    scaling("var_chain", 500, full, |name, n| bench(name, &var_chain(n)));
+    scaling("nested_calls", 100, full, |name, n| {
+        bench(name, &nested_calls(n))
+    });
+    scaling("closure_chain", 100, full, |name, n| {
+        bench(name, &closure_chain(n))
+    });
+    scaling("lex_large", 2000, full, |name, n| {
+        let src = large_file(n);
+        measure(name, || {
+            black_box(lex(black_box(&src)));
+        })
+    });
+    scaling("check_large", 2000, full, |name, n| {
+        bench(name, &large_file(n))
+    });
+}
@@ -2,30 +2,112 @@
 use crate::{
     cx::icx::InferCx,
     errors::{IntoDiagnostic, TypeckError},
+    graph::CallGraph,
+    lint::LintLevels,
     res::{Res, Resolver},
 };
 use ast::{
     atom::TypeHint,
     expr::{BinOp, UnOp},
 };
-use common::token::Span;
+use common::{id::HirId, symbol::Symbol, token::Span};
+use id_arena::Id;
+use std::collections::HashMap;
 use tir::{
-    def::{AdtDef, ItemDefKind},
-    expr::{Expr, ExprKind},
+    atom::Param,
+    def::{AdtDef, FnDef, ItemDefKind},
+    expr::{Expr, ExprKind, InterpPart},
+    results::{Adjustment, Resolution, TypeckResults},
     stmt::{Block, Stmt, StmtKind},
-    ty::{TyMeta, Ty},
+    ty::{FnSig, GenericArgs, Scheme, Ty, TyKind, TyMeta, TyVar},
 };
 
+/// Origin of the expected type, explained by mismatch diagnostics
+#[derive(Debug, Clone)]
+pub enum Origin {
+    /// Nothing to explain
+    None,
+
+    /// Parameter of the called function, with the
+    /// declaration span, if callee is known
+    Param(Option<Span>),
+
+    /// Field of the constructed struct
+    Field(Span),
+
+    /// Parameter of the constructed enum variant,
+    /// with the variant declaration span
+    Variant(Span),
+
+    /// Type annotation of the let binding
+    LetHint(Span),
+
+    /// Return type annotation of the function
+    Return(Span),
+
+    /// Other branch of the `if` expression
+    Branch(Span),
+}
+
+/// Implementation
+impl Origin {
+    /// Explains why `expected` type is expected
+    pub fn explain(&self, expected: &str) -> Option<String> {
+        match self {
+            Origin::None => None,
+            Origin::Param(_) => Some(format!("expected {expected} because of the parameter type")),
+            Origin::Field(_) => Some(format!("expected {expected} because of the field type")),
+            Origin::Variant(_) => Some(format!(
+                "expected {expected} because of the variant parameter type"
+            )),
+            Origin::LetHint(_) => Some(format!(
+                "expected {expected} because of the type annotation"
+            )),
+            Origin::Return(_) => Some(format!("expected {expected} because of the return type")),
+            Origin::Branch(_) => Some(format!("expected {expected} because of the other branch")),
+        }
+    }
+
+    /// Returns span and label of the place, where expected type came from
+    pub fn label(&self) -> Option<(&Span, &'static str)> {
+        match self {
+            Origin::None | Origin::Param(None) => None,
+            Origin::Param(Some(span)) => Some((span, "parameter declared here")),
+            Origin::Field(span) => Some((span, "field declared here")),
+            Origin::Variant(span) => Some((span, "variant declared here")),
+            Origin::LetHint(span) => Some((span, "expected due to this annotation")),
+            Origin::Return(span) => Some((span, "expected due to this return type")),
+            Origin::Branch(span) => Some((span, "expected due to this branch")),
+        }
+    }
+}
+
 /// Represents Module Typechecker
 pub struct ModuleTyck<'tcx, 'icx> {
     /// Inference context reference
-    icx: &'icx mut InferCx<'tcx>,
+    pub(crate) icx: &'icx mut InferCx<'tcx>,
 
     /// Current module resolver
-    resolver: Resolver,
+    pub(crate) resolver: Resolver,
 
     /// Diagnostics vector
-    diagnostics: Vec<TypeckError>,
+    pub(crate) diagnostics: Vec<TypeckError>,
+
+    /// Lint levels in the current position
+    pub(crate) lints: LintLevels,
+
+    /// Return types of the currently checked functions group,
+    /// that are still being inferred, by function name
+    returns: HashMap<Id<TyVar>, Symbol>,
+
+    /// Reported `ReturnTypeNeeded` diagnostics of the currently checked
+    /// functions group with the needed return types, their help is
+    /// completed when the group is inferred
+    returns_needed: Vec<(usize, Ty)>,
+
+    /// Results of the module checking, moved
+    /// to the types context, when module is checked
+    pub(crate) results: TypeckResults,
 }
 
 /// Implementation
@@ -36,23 +118,67 @@ impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
             icx,
             resolver: Resolver::default(),
             diagnostics: Vec::new(),
+            lints: LintLevels::default(),
+            returns: HashMap::new(),
+            returns_needed: Vec::new(),
+            results: TypeckResults::default(),
         }
     }
 
-    /// Performs coercion, reports diagnostic on error and returns unified type
-    pub fn coerce(&mut self, span: &Span, expected: Ty, got: Ty) -> Ty {
-        match self.icx.unify(expected.clone(), got) {
+    /// Sets lint levels, requested outside of the module
+    pub fn with_lint_levels(mut self, lints: LintLevels) -> Self {
+        self.lints = lints;
+        self
+    }
+
+    /// Performs coercion, reports diagnostic on error and returns unified type.
+    /// Mismatch diagnostic explains where the expected type came from using `origin`
+    pub fn coerce(&mut self, span: &Span, expected: Ty, got: Ty, origin: &Origin) -> Ty {
+        match self.icx.unify(expected, got) {
             Ok(()) => self.icx.apply(expected), // To not forget about apply
             Err(err) => {
-                self.diagnostics
-                    .push(err.into_diag(&self.icx, span.clone()));
-                Ty::Error
+                // Sharing one printer, so variables are named consistently
+                let expected = self.icx.apply(expected);
+                let mut tys = err.tys();
+                tys.push(expected);
+                let mut printer = self.icx.printer(&tys);
+                let mut diag = err.into_diag(&mut printer, span.clone());
+                if let TypeckError::TypeMismatch {
+                    reason,
+                    origin: origin_span,
+                    note,
+                    ..
+                } = &mut diag
+                {
+                    *reason = origin.explain(&printer.describe(expected));
+                    // Origin could be declared in the other module,
+                    // that can't be labeled in this diagnostic
+                    if let Some((at, label)) = origin.label()
+                        && at.0 == span.0
+                    {
+                        *origin_span = Some(at.1.clone().into());
+                        *note = label.to_string();
+                    }
+                }
+                self.diagnostics.push(diag);
+                self.icx.tcx.types.error
             }
         }
     }
 
+    /// Returns primitive type by its name
+    fn primitive_ty(&self, name: Symbol) -> Option<Ty> {
+        self.icx
+            .tcx
+            .types
+            .primitives()
+            .into_iter()
+            .find(|(it, _)| *it == name)
+            .map(|(_, ty)| ty)
+    }
+
     /// Infers type hint
-    fn infer_type_hint(&mut self, hint: TypeHint) -> Ty {
+    pub(crate) fn infer_type_hint(&mut self, hint: TypeHint) -> Ty {
         /// Ensures generics arity
         fn ensure_arity<'a, 'b, F>(
             s: &mut ModuleTyck<'a, 'b>,
@@ -68,126 +194,138 @@ impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
                 then(s)
             } else {
                 s.diagnostics.push(TypeckError::ArityMissmatch {
-                    src: span.0.clone(),
+                    src: s.icx.tcx.source(span),
                     span: span.1.clone().into(),
                     expected,
                     got,
                 });
-                Ty::Error
+                s.icx.tcx.types.error
             }
         }
 
         // Matching hint
         match hint {
             // Local type hint
-            TypeHint::Local { span, name, args } => match self.resolver.lookup_adt(&name) {
-                Some(id) => match self.icx.tcx.adt(id) {
-                    AdtDef::Struct(s) => {
-                        let params = s.generics.len();
-                        ensure_arity(self, params, args.len(), &span, |c| {
-                            Ty::Adt(id, c.icx.fresh_generics(params))
-                        })
-                    }
-                    AdtDef::Enum(e) => {
-                        let params = e.generics.len();
-                        ensure_arity(self, params, args.len(), &span, |c| {
-                            Ty::Adt(id, c.icx.fresh_generics(params))
-                        })
-                    }
-                },
-                None => match self.icx.generics.lookup(&name) {
-                    Some(idx) => ensure_arity(self, 0, args.len(), &span, |_| Ty::Generic(idx)),
-                    None => {
-                        self.diagnostics.push(TypeckError::UnresolvedType {
-                            src: span.0.clone(),
-                            span: span.1.clone().into(),
-                            name,
-                        });
-                        Ty::Error
+            TypeHint::Local { span, name, args } => match self.resolver.lookup_type(name) {
+                Some(id) => {
+                    let params = match self.icx.tcx.adt(id) {
+                        AdtDef::Struct(s) => s.generics.len(),
+                        AdtDef::Enum(e) => e.generics.len(),
+                    };
+                    ensure_arity(self, params, args.len(), &span, |c| {
+                        let args = args.into_iter().map(|arg| c.infer_type_hint(arg)).collect();
+                        c.icx.tcx.mk_adt(id, args)
+                    })
+                }
+                None => match self.icx.generics.lookup(name) {
+                    Some(idx) => {
+                        ensure_arity(self, 0, args.len(), &span, |c| c.icx.tcx.mk_generic(idx))
                     }
+                    None => match self.primitive_ty(name) {
+                        Some(ty) => ensure_arity(self, 0, args.len(), &span, |_| ty),
+                        None => {
+                            self.diagnostics.push(TypeckError::UnresolvedType {
+                                src: self.icx.tcx.source(&span),
+                                span: span.1.clone().into(),
+                                help: self.suggest_type(&span, name),
+                                name: name.to_string(),
+                            });
+                            self.icx.tcx.types.error
+                        }
+                    },
                 },
             },
-            TypeHint::Module {
-                span,
-                module,
-                name,
-                args,
-            } => todo!(),
-            TypeHint::Function { span, params, ret } => todo!(),
-            TypeHint::Unit(_) => Ty::Unit,
-            TypeHint::Infer => Ty::Var(self.icx.fresh()),
+            TypeHint::Module { .. } => todo!(),
+            TypeHint::Function { params, ret, .. } => {
+                let params = params
+                    .into_iter()
+                    .map(|param| self.infer_type_hint(param))
+                    .collect();
+                let ret = self.infer_type_hint(*ret);
+                self.icx.tcx.mk_fn_ref(params, ret)
+            }
+            TypeHint::Unit(_) => self.icx.tcx.types.unit,
+            TypeHint::Infer => self.icx.fresh_ty(),
         }
     }
 
     /// Infers literal expression
-    fn infer_lit(&mut self, span: Span, lit: ast::expr::Lit) -> Expr {
+    fn infer_lit(&mut self, id: HirId, span: Span, lit: ast::expr::Lit) -> Expr {
+        let types = self.icx.tcx.types;
         match &lit {
             ast::expr::Lit::Number(num) => {
                 if num.contains(".") {
                     Expr {
+                        id,
                         kind: ExprKind::Lit(lit),
                         span,
-                        ty: Ty::Float,
+                        ty: types.float,
                     }
                 } else {
                     Expr {
+                        id,
                         kind: ExprKind::Lit(lit),
                         span,
-                        ty: Ty::Int,
+                        ty: types.int,
                     }
                 }
             }
             ast::expr::Lit::String(_) => Expr {
+                id,
                 kind: ExprKind::Lit(lit),
                 span,
-                ty: Ty::String,
+                ty: types.string,
             },
             ast::expr::Lit::Bool(_) => Expr {
+                id,
                 kind: ExprKind::Lit(lit),
                 span,
-                ty: Ty::Bool,
+                ty: types.bool,
             },
         }
     }
 
     /// Infers negation operator
-    fn infer_neg(&mut self, span: &Span, ty: &Ty) -> Ty {
+    fn infer_neg(&mut self, span: &Span, ty: Ty) -> Ty {
         // Numeric type - negation is allowed
         if self.icx.is_numeric_ty(ty) {
-            return ty.clone();
+            return ty;
         }
 
         // Any other type - negation is not allowed
         self.diagnostics.push(TypeckError::InvalidUnaryOp {
-            src: span.0.clone(),
+            src: self.icx.tcx.source(span),
             span: span.1.clone().into(),
             op: UnOp::Neg,
-            ty: self.icx.pretty(ty),
+            ty: self.icx.describe(ty),
         });
-        Ty::Error
+        self.icx.tcx.types.error
     }
 
     /// Infers unary expression
-    fn infer_unary(&mut self, span: Span, un_op: UnOp, expr: ast::expr::Expr) -> Expr {
+    fn infer_unary(&mut self, id: HirId, span: Span, un_op: UnOp, expr: ast::expr::Expr) -> Expr {
         // Inferring the expr
         let expr = self.infer_expr(expr);
 
         // Calculating type
-        let ty = match (&un_op, &expr.ty) {
-            (UnOp::Neg, ty) => self.infer_neg(&span, ty),
-            (UnOp::Bang, Ty::Bool) => Ty::Bool,
-            (op, ty) => {
+        let types = self.icx.tcx.types;
+        let ty = match (&un_op, self.icx.tcx.kind(expr.ty)) {
+            (_, TyKind::Error) => types.error,
+            (UnOp::Neg, _) => self.infer_neg(&span, expr.ty),
+            (UnOp::Bang, TyKind::Bool) => types.bool,
+            (op, _) => {
                 self.diagnostics.push(TypeckError::InvalidUnaryOp {
-                    src: span.0.clone(),
+                    src: self.icx.tcx.source(&span),
                     span: span.1.clone().into(),
                     op: op.clone(),
-                    ty: self.icx.pretty(ty),
+                    ty: self.icx.describe(expr.ty),
                 });
-                Ty::Error
+                types.error
             }
         };
 
         Expr {
+            id,
             span,
             kind: ExprKind::Unary(un_op, Box::new(expr)),
             ty,
@@ -197,6 +335,7 @@ impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
     /// Infers binary expression
     fn infer_binary(
         &mut self,
+        id: HirId,
         span: Span,
         bin_op: BinOp,
         lhs: ast::expr::Expr,
@@ -205,61 +344,75 @@ impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
         // Inferring lhs and rhs expressions
         let lhs = self.infer_expr(lhs);
         let rhs = self.infer_expr(rhs);
+        let types = self.icx.tcx.types;
+
+        // Erroneous operands are already reported
+        let erroneous = self.icx.is_error_ty(lhs.ty) || self.icx.is_error_ty(rhs.ty);
 
         // Invalid binary operation error
         let mut invalid_bin_op = || {
+            let mut printer = self.icx.printer(&[lhs.ty, rhs.ty]);
             self.diagnostics.push(TypeckError::InvalidBinOp {
-                src: span.0.clone(),
+                src: self.icx.tcx.source(&span),
                 span: span.1.clone().into(),
                 op: bin_op.clone(),
-                t1: self.icx.pretty(&lhs.ty),
-                t2: self.icx.pretty(&rhs.ty),
+                t1: printer.describe(lhs.ty),
+                t2: printer.describe(rhs.ty),
             });
-            Ty::Error
+            types.error
         };
 
         // Calculating type
         let ty = match bin_op {
+            _ if erroneous => types.error,
             BinOp::Add | BinOp::Sub | BinOp::Mul | BinOp::Div | BinOp::Mod => {
-                if self.icx.is_numeric_ty(&lhs.ty) && self.icx.is_numeric_ty(&rhs.ty) {
-                    self.coerce(&span, lhs.ty.clone(), rhs.ty.clone())
+                if self.icx.is_numeric_ty(lhs.ty) && self.icx.is_numeric_ty(rhs.ty) {
+                    self.coerce(&span, lhs.ty, rhs.ty, &Origin::None)
                 } else {
                     invalid_bin_op()
                 }
             }
             BinOp::BitAnd | BinOp::BitOr => {
-                if self.icx.is_int_ty(&lhs.ty) && self.icx.is_int_ty(&rhs.ty) {
-                    self.coerce(&span, lhs.ty.clone(), rhs.ty.clone())
+                if self.icx.is_int_ty(lhs.ty) && self.icx.is_int_ty(rhs.ty) {
+                    self.coerce(&span, lhs.ty, rhs.ty, &Origin::None)
                 } else {
                     invalid_bin_op()
                 }
             }
             BinOp::And | BinOp::Or => {
-                if self.icx.is_bool_ty(&lhs.ty) && self.icx.is_bool_ty(&rhs.ty) {
-                    Ty::Bool
+                if self.icx.is_bool_ty(lhs.ty) && self.icx.is_bool_ty(rhs.ty) {
+                    types.bool
                 } else {
                     invalid_bin_op()
                 }
             }
             BinOp::Xor => {
-                if self.icx.is_bool_ty(&lhs.ty) && self.icx.is_bool_ty(&rhs.ty) {
-                    lhs.ty.clone()
-                } else if self.icx.is_int_ty(&lhs.ty) && self.icx.is_int_ty(&rhs.ty) {
-                    self.coerce(&span, lhs.ty.clone(), rhs.ty.clone())
+                if self.icx.is_bool_ty(lhs.ty) && self.icx.is_bool_ty(rhs.ty) {
+                    lhs.ty
+                } else if self.icx.is_int_ty(lhs.ty) && self.icx.is_int_ty(rhs.ty) {
+                    self.coerce(&span, lhs.ty, rhs.ty, &Origin::None)
                 } else {
                     invalid_bin_op()
                 }
             }
             BinOp::Eq | BinOp::Ne => {
-                let ty = self.coerce(&span, lhs.ty.clone(), rhs.ty.clone());
-                if ty != Ty::Error { Ty::Bool } else { Ty::Error }
+                let ty = self.coerce(&span, lhs.ty, rhs.ty, &Origin::None);
+                if ty != types.error {
+                    types.bool
+                } else {
+                    types.error
+                }
             }
             BinOp::Ge | BinOp::Le | BinOp::Gt | BinOp::Lt => {
-                if self.icx.is_bool_ty(&lhs.ty) && self.icx.is_bool_ty(&rhs.ty) {
-                    lhs.ty.clone()
-                } else if self.icx.is_numeric_ty(&lhs.ty) && self.icx.is_numeric_ty(&rhs.ty) {
-                    let ty = self.coerce(&span, lhs.ty.clone(), rhs.ty.clone());
-                    if ty != Ty::Error { Ty::Bool } else { Ty::Error }
+                if self.icx.is_bool_ty(lhs.ty) && self.icx.is_bool_ty(rhs.ty) {
+                    lhs.ty
+                } else if self.icx.is_numeric_ty(lhs.ty) && self.icx.is_numeric_ty(rhs.ty) {
+                    let ty = self.coerce(&span, lhs.ty, rhs.ty, &Origin::None);
+                    if ty != types.error {
+                        types.bool
+                    } else {
+                        types.error
+                    }
                 } else {
                     invalid_bin_op()
                 }
@@ -267,6 +420,7 @@ impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
         };
 
         Expr {
+            id,
             span,
             kind: ExprKind::Bin(bin_op, Box::new(lhs), Box::new(rhs)),
             ty,
@@ -276,64 +430,98 @@ impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
     /// Infers if epxression
     fn infer_if(
         &mut self,
+        id: HirId,
         span: Span,
         cond: ast::expr::Expr,
         then: ast::expr::Expr,
         else_: Option<ast::expr::Expr>,
     ) -> Expr {
-        // Inferring condition
-        let cond = self.infer_expr(cond);
-        self.coerce(&span, Ty::Bool, cond.ty.clone());
+        // Checking condition
+        let cond = self.check_expr(cond, self.icx.tcx.types.bool, &Origin::None);
 
         // Inferring then
         let then = self.infer_expr(then);
 
-        // Inferring else
+        // Checking else against then
         match else_ {
             Some(else_) => {
-                let else_ = self.infer_expr(else_);
-                self.coerce(&span, then.ty.clone(), else_.ty.clone());
+                let else_ = self.check_expr(else_, then.ty, &Origin::Branch(then.span.clone()));
 
-                let ty = then.ty.clone();
+                let ty = then.ty;
                 Expr {
+                    id,
                     span,
                     kind: ExprKind::If(Box::new(cond), Box::new(then), Some(Box::new(else_))),
                     ty,
                 }
             }
             None => Expr {
+                id,
                 span,
                 kind: ExprKind::If(Box::new(cond), Box::new(then), None),
-                ty: Ty::Unit,
+                ty: self.icx.tcx.types.unit,
             },
         }
     }
 
+    /// Records instantiation of the polymorphic definition at the node
+    fn record_instantiation(&mut self, id: HirId, args: &GenericArgs) {
+        if !args.is_empty() {
+            self.results
+                .adjustments
+                .insert(id, Adjustment::Instantiate(args.clone()));
+        }
+    }
+
+    /// Returns type of the item definition reference at the node `id`.
+    /// Function references are instantiated with fresh generic arguments
+    fn item_def_ty(&mut self, id: HirId, kind: ItemDefKind) -> Ty {
+        match kind {
+            ItemDefKind::Adt(adt) => self.icx.tcx.mk_meta(TyMeta::Adt(adt)),
+            ItemDefKind::Fn(function) => {
+                let amount = self.icx.tcx._fn(function).generics.len();
+                let generics = self.icx.fresh_generics(amount);
+                self.record_instantiation(id, &generics);
+                self.icx.tcx.mk_fn_def(function, generics)
+            }
+        }
+    }
+
     /// Infers id expression
-    fn infer_id(&mut self, span: Span, name: String) -> Expr {
-        let ty = match self.resolver.lookup(&name) {
-            Some(res) => match res {
-                Res::Item(def) => match def.kind {
-                    ItemDefKind::Adt(id) => Ty::Meta(TyMeta::Adt(id)),
-                    ItemDefKind::Fn(id) => Ty::FnDef(
-                        id,
-                        self.icx.fresh_generics(self.icx.tcx._fn(id).generics.len()),
+    fn infer_id(&mut self, id: HirId, span: Span, name: Symbol) -> Expr {
+        let ty = match self.resolver.lookup(name) {
+            Some(res) => {
+                let (ty, resolution) = match res {
+                    Res::Item(def) => (
+                        self.item_def_ty(id, def.kind.clone()),
+                        Resolution::Item(def),
                     ),
-                },
-                Res::Mod(id) => Ty::Meta(TyMeta::Module(id)),
-                Res::Local(local) => local,
-            },
+                    Res::Mod(module) => (
+                        self.icx.tcx.mk_meta(TyMeta::Module(module)),
+                        Resolution::Mod(module),
+                    ),
+                    Res::Local(scheme, at) => {
+                        let (ty, args) = self.icx.instantiate_scheme(&scheme);
+                        self.record_instantiation(id, &args);
+                        (ty, Resolution::Local(at))
+                    }
+                };
+                self.results.resolutions.insert(id, resolution);
+                ty
+            }
             None => {
                 self.diagnostics.push(TypeckError::UnresolvedName {
-                    src: span.0.clone(),
+                    src: self.icx.tcx.source(&span),
                     span: span.1.clone().into(),
-                    name: name.clone(),
+                    name: name.to_string(),
+                    help: self.suggest_name(&span, name),
                 });
-                Ty::Error
+                self.icx.tcx.types.error
             }
         };
 
         Expr {
+            id,
             kind: ExprKind::Id(name),
             span,
             ty,
@@ -341,65 +529,121 @@ impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
     }
 
     /// Infers field expression
-    fn infer_field(&mut self, span: Span, what: ast::expr::Expr, name: String) -> Expr {
+    fn infer_field(&mut self, id: HirId, span: Span, what: ast::expr::Expr, name: Symbol) -> Expr {
         let what = self.infer_expr(what);
-        let mut error = || {
-            self.diagnostics.push(TypeckError::UnresolvedField {
-                src: span.0.clone(),
-                span: span.1.clone().into(),
-                name: name.clone(),
-            });
-            Ty::Error
-        };
-
-        let ty = match &what.ty {
-            Ty::Meta(TyMeta::Module(id)) => match self.icx.tcx._mod(*id).defs.get(&name) {
-                Some(def) => match def.kind {
-                    ItemDefKind::Adt(id) => Ty::Meta(TyMeta::Adt(id)),
-                    ItemDefKind::Fn(id) => Ty::FnDef(
-                        id,
-                        self.icx.fresh_generics(self.icx.tcx._fn(id).generics.len()),
-                    ),
-                },
-                None => error(),
-            },
-            Ty::Meta(TyMeta::Adt(id)) => match self.icx.tcx.adt(*id) {
-                AdtDef::Enum(en) => match en.variants.iter().find(|f| f.name == name) {
-                    Some(variant) => Ty::Meta(TyMeta::Variant(*id, variant.name.clone())),
-                    None => error(),
-                },
-                _ => error(),
+        let error = self.icx.tcx.types.error;
+        let ty = match self.icx.tcx.kind(what.ty).clone() {
+            TyKind::Meta(TyMeta::Module(module)) => {
+                let def = self.icx.tcx._mod(module).defs.get(&name).cloned();
+                def.map(|def| {
+                    let ty = self.item_def_ty(id, def.kind.clone());
+                    self.results.resolutions.insert(id, Resolution::Item(def));
+                    ty
+                })
+            }
+            TyKind::Meta(TyMeta::Adt(adt)) => match self.icx.tcx.adt(adt) {
+                AdtDef::Enum(en) => en
+                    .variants
+                    .iter()
+                    .find(|f| f.name == name)
+                    .map(|variant| TyMeta::Variant(adt, variant.name))
+                    .map(|meta| {
+                        let resolution = Resolution::Variant(adt, name);
+                        self.results.resolutions.insert(id, resolution);
+                        self.icx.tcx.mk_meta(meta)
+                    }),
+                _ => None,
             },
-            Ty::Adt(id, args) => match self.icx.tcx.adt(*id) {
-                AdtDef::Struct(s) => match s.fields.iter().find(|f| f.name == name) {
-                    Some(field) => self.icx.instantiate(field.ty.clone(), &args),
-                    None => error(),
-                },
-                _ => error(),
+            TyKind::Adt(adt, args) => match self.icx.tcx.adt(adt) {
+                AdtDef::Struct(s) => s
+                    .fields
+                    .iter()
+                    .find(|f| f.name == name)
+                    .map(|field| field.ty)
+                    .map(|ty| {
+                        let resolution = Resolution::Field(adt, name);
+                        self.results.resolutions.insert(id, resolution);
+                        self.icx.instantiate(ty, &args)
+                    }),
+                _ => None,
             },
-            Ty::Error => Ty::Error,
-            _ => {
-                self.diagnostics.push(TypeckError::UnresolvedField {
-                    src: span.0.clone(),
-                    span: span.1.clone().into(),
-                    name: name.clone(),
-                });
-                Ty::Error
+            TyKind::Error => Some(error),
+            TyKind::Var(_) if self.pending_return(what.ty).is_some() => {
+                Some(self.return_type_needed(&what.span, what.ty))
             }
+            _ => None,
         };
+        let ty = ty.unwrap_or_else(|| {
+            self.diagnostics.push(TypeckError::UnresolvedField {
+                src: self.icx.tcx.source(&span),
+                span: span.1.clone().into(),
+                name: name.to_string(),
+                help: self.suggest_field(what.ty, name),
+            });
+            error
+        });
 
         Expr {
+            id,
             span,
             kind: ExprKind::Field(Box::new(what), name),
             ty,
         }
     }
 
+    /// Returns name of the function, whose return type is still
+    /// being inferred and is represented by `ty`
+    fn pending_return(&self, ty: Ty) -> Option<Symbol> {
+        let TyKind::Var(var) = self.icx.tcx.kind(self.icx.shallow_resolve(ty)) else {
+            return None;
+        };
+        let root = self.icx.root(*var);
+        self.returns
+            .iter()
+            .find(|(var, _)| self.icx.root(**var) == root)
+            .map(|(_, name)| *name)
+    }
+
+    /// Reports, that type of the value depends on
+    /// the return type that is not inferred yet
+    fn return_type_needed(&mut self, span: &Span, ty: Ty) -> Ty {
+        if let Some(name) = self.pending_return(ty) {
+            self.returns_needed.push((self.diagnostics.len(), ty));
+            self.diagnostics.push(TypeckError::ReturnTypeNeeded {
+                src: self.icx.tcx.source(span),
+                span: span.1.clone().into(),
+                name: name.to_string(),
+                help: String::new(),
+            });
+        }
+        self.icx.tcx.types.error
+    }
+
+    /// Completes help of the reported `ReturnTypeNeeded` diagnostics,
+    /// suggesting the return type, if it's inferred from the body
+    fn complete_returns_needed(&mut self) {
+        for (idx, ty) in std::mem::take(&mut self.returns_needed) {
+            let ty = self.icx.apply(ty);
+            let inferred = self.icx.unresolved_vars(ty).is_empty()
+                && !matches!(self.icx.tcx.kind(ty), TyKind::Error);
+            let pretty = self.icx.pretty(ty);
+            if let Some(TypeckError::ReturnTypeNeeded { name, help, .. }) =
+                self.diagnostics.get_mut(idx)
+            {
+                *help = if inferred {
+                    format!("add return type annotation: `fn {name}(..) -> {pretty}`")
+                } else {
+                    format!("add return type annotation to `{name}`")
+                };
+            }
+        }
+    }
+
     /// Checks arity
     fn check_arity(&mut self, span: &Span, expected: usize, got: usize) {
         if expected != got {
             self.diagnostics.push(TypeckError::ArityMissmatch {
-                src: span.0.clone(),
+                src: self.icx.tcx.source(span),
                 span: span.1.clone().into(),
                 expected,
                 got,
@@ -408,156 +652,268 @@ impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
     }
 
     /// Infers assignment
-    fn infer_assign(&mut self, span: Span, what: ast::expr::Expr, to: ast::expr::Expr) -> Expr {
+    fn infer_assign(
+        &mut self,
+        id: HirId,
+        span: Span,
+        what: ast::expr::Expr,
+        to: ast::expr::Expr,
+    ) -> Expr {
         let what = self.infer_expr(what);
         let to = self.infer_expr(to);
-        let ty = what.ty.clone();
-        self.coerce(&span, what.ty.clone(), to.ty.clone());
+        let ty = what.ty;
+        self.coerce(&span, what.ty, to.ty, &Origin::None);
 
         Expr {
+            id,
             span,
             kind: ExprKind::Assign(Box::new(what), Box::new(to)),
             ty,
         }
     }
 
-    /// Infers call expression
+    /// Infers call expression. Arguments are checked
+    /// against the parameter types of the callee
     fn infer_call(
         &mut self,
+        id: HirId,
         span: Span,
         what: ast::expr::Expr,
         args: Vec<ast::expr::Expr>,
     ) -> Expr {
         // Inferring callee
         let what = self.infer_expr(what);
-        let args = args
-            .into_iter()
-            .map(|arg| self.infer_expr(arg))
-            .collect::<Vec<Expr>>();
-        let mut error = |ty: &Ty| {
-            self.diagnostics.push(TypeckError::CanNotCall {
-                src: span.0.clone(),
-                span: span.1.clone().into(),
-                ty: self.icx.pretty(ty),
-            });
-            Ty::Error
-        };
 
-        // Inferring type
-        let ty = match what.ty.clone() {
+        // Callee depends on the return type, that is not inferred yet
+        if self.pending_return(what.ty).is_some() {
+            let args = args
+                .into_iter()
+                .map(|arg| self.infer_expr(arg))
+                .collect::<Vec<Expr>>();
+            let ty = self.return_type_needed(&what.span, what.ty);
+            return Expr {
+                id,
+                span,
+                kind: ExprKind::Call(Box::new(what), args),
+                ty,
+            };
+        }
+
+        // Retrieving callee signature
+        let sig = match self.icx.tcx.kind(what.ty).clone() {
             // Call to function definition
-            Ty::FnDef(id, generics) => {
+            TyKind::FnDef(id, generics) => {
                 let _fn = self.icx.tcx._fn(id);
                 let params = _fn.params.clone();
-                let ret = _fn.ret.clone();
-
-                self.check_arity(&span, params.len(), args.len());
-                params.iter().zip(&args).for_each(|(p, a)| {
-                    self.coerce(
-                        &span,
-                        self.icx.instantiate(p.clone(), &generics),
-                        a.ty.clone(),
-                    );
-                });
+                let origins: Vec<Origin> = _fn
+                    .param_spans
+                    .iter()
+                    .map(|span| Origin::Param(Some(span.clone())))
+                    .collect();
+                let ret = _fn.ret;
 
-                self.icx.instantiate(ret, &generics)
+                Some((
+                    params
+                        .into_iter()
+                        .map(|p| self.icx.instantiate(p, &generics))
+                        .collect::<Vec<Ty>>(),
+                    self.icx.instantiate(ret, &generics),
+                    origins,
+                ))
             }
 
             // Call to function reference
-            Ty::FnRef(sig) => {
-                self.check_arity(&span, sig.params.len(), args.len());
+            TyKind::FnRef(sig) => {
+                let origins = sig.params.iter().map(|_| Origin::Param(None)).collect();
+                Some((sig.params, sig.ret, origins))
+            }
 
-                sig.params.clone().iter().zip(&args).for_each(|(p, a)| {
-                    self.coerce(&span, p.clone(), a.ty.clone());
-                });
+            // Struct initialization
+            TyKind::Meta(TyMeta::Adt(id)) => match self.icx.tcx.adt(id).clone() {
+                AdtDef::Struct(s) => {
+                    let generics = self.icx.fresh_generics(s.generics.len());
+                    self.record_instantiation(what.id, &generics);
+                    Some((
+                        s.fields
+                            .iter()
+                            .map(|f| self.icx.instantiate(f.ty, &generics))
+                            .collect(),
+                        self.icx.tcx.mk_adt(id, generics),
+                        s.fields
+                            .iter()
+                            .map(|f| Origin::Field(f.span.clone()))
+                            .collect(),
+                    ))
+                }
+                _ => None,
+            },
 
-                sig.ret.clone()
+            // Variant initialization
+            TyKind::Meta(TyMeta::Variant(id, variant)) => {
+                // Retrieving enum and variant
+                let en = self.icx.tcx.adt(id).as_enum().clone();
+                let variant = en.variants.iter().find(|v| v.name == variant).unwrap();
+                let generics = self.icx.fresh_generics(en.generics.len());
+                self.record_instantiation(what.id, &generics);
+
+                Some((
+                    variant
+                        .fields
+                        .iter()
+                        .map(|f| self.icx.instantiate(*f, &generics))
+                        .collect(),
+                    self.icx.tcx.mk_adt(id, generics),
+                    variant
+                        .fields
+                        .iter()
+                        .map(|_| Origin::Variant(variant.span.clone()))
+                        .collect(),
+                ))
             }
 
-            // Call to meta type
-            Ty::Meta(meta) => match meta {
-                // Struct initialization
-                TyMeta::Adt(id) => match self.icx.tcx.adt(id).clone() {
-                    AdtDef::Struct(s) => {
-                        // Checking arity of fields and args
-                        self.check_arity(&span, s.fields.len(), args.len());
-                        let generics = self.icx.fresh_generics(s.generics.len());
+            _ => None,
+        };
 
-                        // Coercing
-                        s.fields.iter().zip(&args).for_each(|(f, a)| {
-                            self.coerce(
-                                &span,
-                                self.icx.instantiate(f.ty.clone(), &generics),
-                                a.ty.clone(),
-                            );
-                        });
+        // Checking args
+        let (args, ty) = match sig {
+            Some((params, ret, origins)) => {
+                self.check_arity(&span, params.len(), args.len());
 
-                        Ty::Adt(id, generics)
-                    }
-                    _ => error(&Ty::Meta(TyMeta::Adt(id))),
-                },
+                let mut params = params.into_iter().zip(origins);
+                let args = args
+                    .into_iter()
+                    .map(|arg| match params.next() {
+                        Some((param, origin)) => self.check_expr(arg, param, &origin),
+                        None => self.infer_expr(arg),
+                    })
+                    .collect::<Vec<Expr>>();
 
-                // Variant initialization
-                TyMeta::Variant(id, variant) => {
-                    // Retrieving enum and variant
-                    let en = self.icx.tcx.adt(id).as_enum().clone();
-                    let variant = en.variants.iter().find(|v| v.name == variant).unwrap();
-
-                    // Checking arity of fields and args
-                    self.check_arity(&span, variant.fields.len(), args.len());
-                    let generics = self.icx.fresh_generics(en.generics.len());
-
-                    // Coercing
-                    variant.fields.iter().zip(&args).for_each(|(f, a)| {
-                        self.coerce(
-                            &span,
-                            self.icx.instantiate(f.clone(), &generics),
-                            a.ty.clone(),
-                        );
-                    });
+                (args, ret)
+            }
+            None => {
+                let args = args
+                    .into_iter()
+                    .map(|arg| self.infer_expr(arg))
+                    .collect::<Vec<Expr>>();
 
-                    Ty::Adt(id, generics)
+                // Erroneous callee is already reported
+                if !self.icx.is_error_ty(what.ty) {
+                    self.diagnostics.push(TypeckError::CanNotCall {
+                        src: self.icx.tcx.source(&span),
+                        span: span.1.clone().into(),
+                        ty: self.icx.describe(what.ty),
+                    });
                 }
-                meta => error(&Ty::Meta(meta)),
-            },
-            ty => error(&ty),
+                (args, self.icx.tcx.types.error)
+            }
         };
 
         Expr {
+            id,
             span,
             kind: ExprKind::Call(Box::new(what), args),
             ty,
         }
     }
 
+    /// Infers interpolated string. Values are checked to be
+    /// printable by the zonking, when their types are known
+    fn infer_interp(&mut self, id: HirId, span: Span, parts: Vec<ast::expr::InterpPart>) -> Expr {
+        let parts = parts
+            .into_iter()
+            .map(|part| match part {
+                ast::expr::InterpPart::Lit(text) => InterpPart::Lit(text),
+                ast::expr::InterpPart::Expr(expr) => InterpPart::Expr(self.infer_expr(expr)),
+            })
+            .collect();
+
+        Expr {
+            id,
+            span,
+            kind: ExprKind::Interp(parts),
+            ty: self.icx.tcx.types.string,
+        }
+    }
+
+    /// Checks, that interpolated value is printable: only
+    /// primitive types, except of unit, could be printed
+    pub(crate) fn check_printable(&mut self, value: &Expr) {
+        match self.icx.tcx.kind(value.ty) {
+            TyKind::Int | TyKind::Float | TyKind::String | TyKind::Bool | TyKind::Error => {}
+            _ => self.diagnostics.push(TypeckError::NotPrintable {
+                src: self.icx.tcx.source(&value.span),
+                span: value.span.1.clone().into(),
+                ty: self.icx.describe(value.ty),
+            }),
+        }
+    }
+
+    /// Returns `true` if expression is a syntactic value, that is safe
+    /// to generalize (value restriction): literals, names, fields and closures.
+    fn is_syntactic_value(expr: &ast::expr::Expr) -> bool {
+        match &expr.kind {
+            ast::expr::ExprKind::Lit(_)
+            | ast::expr::ExprKind::Id(_)
+            | ast::expr::ExprKind::Closure(_, _) => true,
+            ast::expr::ExprKind::Field(what, _) => Self::is_syntactic_value(what),
+            _ => false,
+        }
+    }
+
     /// Infers let binding
     fn infer_let(
         &mut self,
+        id: HirId,
         span: Span,
         hint: TypeHint,
-        binding: String,
+        binding: Symbol,
+        binding_span: Span,
         expr: ast::expr::Expr,
     ) -> Stmt {
-        // Inferring expression
-        let expr = self.infer_expr(expr);
+        // Checking value restriction
+        let generalizable = Self::is_syntactic_value(&expr);
 
-        // Hint type
-        let hint_ty = self.infer_type_hint(hint);
+        // Inferring expression one level deeper,
+        // so its fresh variables could be generalized.
+        // If hint is presented, expression is checked against it
+        self.icx.enter_level();
+        let (expr, hint_ty) = match hint {
+            TypeHint::Infer => {
+                let expr = self.infer_expr(expr);
+                let ty = expr.ty;
+                (expr, ty)
+            }
+            hint => {
+                let origin = hint.span().map_or(Origin::None, Origin::LetHint);
+                let ty = self.infer_type_hint(hint);
+                let expr = self.check_expr(expr, ty, &origin);
+                (expr, self.icx.apply(ty))
+            }
+        };
+        self.icx.exit_level();
+
+        // Generalizing binding type
+        let scheme = if generalizable {
+            self.icx.generalize(hint_ty)
+        } else {
+            self.icx.monomorphize(hint_ty)
+        };
 
         // Defining local variable
-        let ty = if !self.resolver.define_local(&binding, expr.ty.clone()) {
+        let ty = if !self.resolver.define_local(binding, &binding_span, scheme) {
             self.diagnostics.push(TypeckError::AlreadyDefined {
-                src: span.0.clone(),
-                span: span.1.clone().into(),
-                binding: binding.clone(),
+                src: self.icx.tcx.source(&binding_span),
+                span: binding_span.1.clone().into(),
+                binding: binding.to_string(),
             });
-            Ty::Error
+            self.icx.tcx.types.error
         } else {
-            Ty::Unit
+            self.icx.tcx.types.unit
         };
 
         Stmt {
-            kind: StmtKind::Let(binding, hint_ty, expr),
+            id,
+            kind: StmtKind::Let(binding, binding_span, hint_ty, expr),
             span,
             ty,
         }
@@ -565,51 +921,113 @@ impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
 
     /// Infers statement
     fn infer_stmt(&mut self, stmt: ast::stmt::Stmt) -> Stmt {
-        match stmt.kind {
-            ast::stmt::StmtKind::Let(binding, hint, expr) => {
-                self.infer_let(stmt.span, hint, binding, expr)
+        let outer = macros::ice::enter_node(stmt.span.1.clone());
+        let stmt = match stmt.kind {
+            ast::stmt::StmtKind::Let(binding, binding_span, hint, expr) => {
+                self.infer_let(stmt.id, stmt.span, hint, binding, binding_span, expr)
             }
             ast::stmt::StmtKind::Expr(expr) => {
                 let expr = self.infer_expr(expr);
                 Stmt {
+                    id: stmt.id,
                     span: stmt.span,
-                    ty: expr.ty.clone(),
+                    ty: expr.ty,
                     kind: StmtKind::Expr(expr),
                 }
             }
             ast::stmt::StmtKind::Semi(expr) => {
                 let expr = self.infer_expr(expr);
                 Stmt {
+                    id: stmt.id,
                     span: stmt.span,
                     kind: StmtKind::Semi(expr),
-                    ty: Ty::Unit,
+                    ty: self.icx.tcx.types.unit,
                 }
             }
-        }
+        };
+        macros::ice::leave_node(outer);
+        stmt
     }
 
     /// Infers block
-    fn infer_block(&mut self, span: Span, mut block: ast::stmt::Block) -> Expr {
+    pub(crate) fn infer_block(&mut self, id: HirId, span: Span, block: ast::stmt::Block) -> Expr {
+        self.block(id, span, block, None)
+    }
+
+    /// Checks block against the expected type
+    pub(crate) fn check_block(
+        &mut self,
+        id: HirId,
+        span: Span,
+        block: ast::stmt::Block,
+        expected: Ty,
+        origin: &Origin,
+    ) -> Expr {
+        self.block(id, span, block, Some((expected, origin)))
+    }
+
+    /// Infers block. If expected type is presented,
+    /// last statement is checked against it
+    fn block(
+        &mut self,
+        id: HirId,
+        span: Span,
+        mut block: ast::stmt::Block,
+        expected: Option<(Ty, &Origin)>,
+    ) -> Expr {
         let last = block.stmts.pop();
         let mut stmts = Vec::new();
 
         // Inferring statements
+        self.resolver.push_scope();
         for stmt in block.stmts {
             stmts.push(self.infer_stmt(stmt));
         }
 
         // Inferring last
-        let ty = match last {
-            Some(last) => {
+        let ty = match (last, expected) {
+            (
+                Some(ast::stmt::Stmt {
+                    id,
+                    kind: ast::stmt::StmtKind::Expr(expr),
+                    span,
+                }),
+                Some((expected, origin)),
+            ) => {
+                let expr = self.check_expr(expr, expected, origin);
+                let ty = expr.ty;
+                stmts.push(Stmt {
+                    id,
+                    span,
+                    ty,
+                    kind: StmtKind::Expr(expr),
+                });
+                ty
+            }
+            (Some(last), expected) => {
                 let last = self.infer_stmt(last);
-                let ty = last.ty.clone();
+                let ty = match expected {
+                    Some((expected, origin)) => {
+                        self.coerce(&last.span, expected, last.ty, origin);
+                        last.ty
+                    }
+                    None => last.ty,
+                };
                 stmts.push(last);
                 ty
             }
-            None => Ty::Unit,
+            (None, expected) => {
+                let unit = self.icx.tcx.types.unit;
+                if let Some((expected, origin)) = expected {
+                    self.coerce(&span, expected, unit, origin);
+                }
+                unit
+            }
         };
+        self.pop_scope();
 
         Expr {
+            id,
             span,
             kind: ExprKind::Block(Box::new(Block {
                 stmts,
@@ -619,25 +1037,491 @@ impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
         }
     }
 
+    /// Infers closure expression. If expected signature is presented,
+    /// params take its types and body is checked against its return type
+    fn closure(
+        &mut self,
+        id: HirId,
+        span: Span,
+        params: Vec<Symbol>,
+        body: ast::expr::Expr,
+        expected: Option<(FnSig, &Origin)>,
+    ) -> Expr {
+        // Params types
+        let (tys, expected) = match expected {
+            Some((sig, origin)) => (sig.params, Some((sig.ret, origin))),
+            None => (params.iter().map(|_| self.icx.fresh_ty()).collect(), None),
+        };
+
+        // Defining params
+        self.resolver.push_scope();
+        let params = params
+            .into_iter()
+            .zip(tys)
+            .map(|(name, ty)| {
+                if !self.resolver.define_local(name, &span, Scheme::mono(ty)) {
+                    self.diagnostics.push(TypeckError::AlreadyDefined {
+                        src: self.icx.tcx.source(&span),
+                        span: span.1.clone().into(),
+                        binding: name.to_string(),
+                    });
+                }
+                Param {
+                    span: span.clone(),
+                    name,
+                    ty,
+                }
+            })
+            .collect::<Vec<Param>>();
+
+        // Inferring body
+        let body = match expected {
+            Some((ret, origin)) => self.check_expr(body, ret, origin),
+            None => self.infer_expr(body),
+        };
+        self.pop_scope();
+
+        let ty = self
+            .icx
+            .tcx
+            .mk_fn_ref(params.iter().map(|p| p.ty).collect(), body.ty);
+        Expr {
+            id,
+            span,
+            kind: ExprKind::Closure(params, Box::new(body)),
+            ty,
+        }
+    }
+
+    /// Checks if expression with both branches against the expected type
+    fn check_if(
+        &mut self,
+        id: HirId,
+        span: Span,
+        cond: ast::expr::Expr,
+        then: ast::expr::Expr,
+        else_: ast::expr::Expr,
+        (expected, origin): (Ty, &Origin),
+    ) -> Expr {
+        let cond = self.check_expr(cond, self.icx.tcx.types.bool, &Origin::None);
+        let then = self.check_expr(then, expected, origin);
+        let else_ = self.check_expr(else_, expected, origin);
+
+        let ty = then.ty;
+        Expr {
+            id,
+            span,
+            kind: ExprKind::If(Box::new(cond), Box::new(then), Some(Box::new(else_))),
+            ty,
+        }
+    }
+
+    /// Checks expression against the expected type and applies substitutions.
+    ///
+    /// The expected type is pushed down into closures, blocks and `if`
+    /// branches, so closure params and nested expressions get information
+    /// from the context. Other expressions are inferred and then coerced.
+    ///
+    pub fn check_expr(&mut self, expr: ast::expr::Expr, expected: Ty, origin: &Origin) -> Expr {
+        let outer = macros::ice::enter_node(expr.span.1.clone());
+        let resolved = self.icx.apply(expected);
+        let mut tir_expr = match (expr.kind, self.icx.tcx.kind(resolved).clone()) {
+            (ast::expr::ExprKind::Closure(params, body), TyKind::FnRef(sig))
+                if sig.params.len() == params.len() =>
+            {
+                self.closure(expr.id, expr.span, params, *body, Some((sig, origin)))
+            }
+            (ast::expr::ExprKind::If(cond, then, Some(else_)), _) => {
+                self.check_if(expr.id, expr.span, *cond, *then, *else_, (expected, origin))
+            }
+            (ast::expr::ExprKind::Block(block), _) => {
+                self.check_block(expr.id, expr.span, *block, expected, origin)
+            }
+            (kind, _) => {
+                let tir_expr = self.infer_expr(ast::expr::Expr {
+                    id: expr.id,
+                    kind,
+                    span: expr.span,
+                });
+                self.coerce(&tir_expr.span, expected, tir_expr.ty, origin);
+                tir_expr
+            }
+        };
+        tir_expr.ty = self.icx.apply(tir_expr.ty);
+        macros::ice::leave_node(outer);
+        tir_expr
+    }
+
     /// Infers expression and applies substitutions
     pub fn infer_expr(&mut self, expr: ast::expr::Expr) -> Expr {
+        let outer = macros::ice::enter_node(expr.span.1.clone());
         let mut tir_expr = match expr.kind {
-            ast::expr::ExprKind::Lit(lit) => self.infer_lit(expr.span, lit),
-            ast::expr::ExprKind::Unary(un_op, inner) => self.infer_unary(expr.span, un_op, *inner),
+            ast::expr::ExprKind::Lit(lit) => self.infer_lit(expr.id, expr.span, lit),
+            ast::expr::ExprKind::Unary(un_op, inner) => {
+                self.infer_unary(expr.id, expr.span, un_op, *inner)
+            }
             ast::expr::ExprKind::Bin(bin_op, lhs, rhs) => {
-                self.infer_binary(expr.span, bin_op, *lhs, *rhs)
+                self.infer_binary(expr.id, expr.span, bin_op, *lhs, *rhs)
             }
             ast::expr::ExprKind::If(cond, then, else_) => {
-                self.infer_if(expr.span, *cond, *then, else_.map(|it| *it))
-            }
-            ast::expr::ExprKind::Id(name) => self.infer_id(expr.span, name),
-            ast::expr::ExprKind::Field(what, name) => self.infer_field(expr.span, *what, name),
-            ast::expr::ExprKind::Call(what, args) => self.infer_call(expr.span, *what, args),
-            ast::expr::ExprKind::Assign(what, to) => self.infer_assign(expr.span, *what, *to),
-            ast::expr::ExprKind::Block(block) => self.infer_block(expr.span, *block),
-            ast::expr::ExprKind::Closure(items, expr) => todo!(),
+                self.infer_if(expr.id, expr.span, *cond, *then, else_.map(|it| *it))
+            }
+            ast::expr::ExprKind::Id(name) => self.infer_id(expr.id, expr.span, name),
+            ast::expr::ExprKind::Field(what, name) => {
+                self.infer_field(expr.id, expr.span, *what, name)
+            }
+            ast::expr::ExprKind::Call(what, args) => {
+                self.infer_call(expr.id, expr.span, *what, args)
+            }
+            ast::expr::ExprKind::Assign(what, to) => {
+                self.infer_assign(expr.id, expr.span, *what, *to)
+            }
+            ast::expr::ExprKind::Block(block) => self.infer_block(expr.id, expr.span, *block),
+            ast::expr::ExprKind::Closure(params, body) => {
+                self.closure(expr.id, expr.span, params, *body, None)
+            }
+            ast::expr::ExprKind::Interp(parts) => self.infer_interp(expr.id, expr.span, parts),
         };
         tir_expr.ty = self.icx.apply(tir_expr.ty);
+        macros::ice::leave_node(outer);
         tir_expr
     }
+
+    /// Infers function body against its declared signature
+    fn infer_fn_body(
+        &mut self,
+        id: Id<FnDef>,
+        item_id: HirId,
+        function: ast::item::Function,
+    ) -> tir::item::Function {
+        // Retrieving signature
+        let def = self.icx.tcx._fn(id);
+        let params = def.params.clone();
+        let ret = def.ret;
+        let generics = def.generics.clone();
+
+        // Defining params
+        self.icx.generics.push(generics.clone());
+        self.resolver.set_owner(Some(function.name));
+        self.resolver.push_scope();
+        let params = function
+            .params
+            .into_iter()
+            .zip(params)
+            .map(|(p, ty)| {
+                if !self
+                    .resolver
+                    .define_local(p.name, &p.span, Scheme::mono(ty))
+                {
+                    self.diagnostics.push(TypeckError::AlreadyDefined {
+                        src: self.icx.tcx.source(&p.span),
+                        span: p.span.1.clone().into(),
+                        binding: p.name.to_string(),
+                    });
+                }
+                Param {
+                    span: p.span,
+                    name: p.name,
+                    ty,
+                }
+            })
+            .collect::<Vec<Param>>();
+
+        // Checking body against the return type
+        let origin = function.ret.span().map_or(Origin::None, Origin::Return);
+        let span = function.block.span.clone();
+        let body = self.check_block(item_id, span, function.block, ret, &origin);
+        self.pop_scope();
+        self.resolver.set_owner(None);
+        self.icx.generics.pop();
+
+        let block = match body.kind {
+            ExprKind::Block(block) => *block,
+            _ => unreachable!(),
+        };
+        tir::item::Function {
+            name: function.name,
+            generics,
+            params,
+            ty: ret,
+            block,
+        }
+    }
+
+    /// Infers strongly connected group of functions. Bodies are checked
+    /// together, then unbound variables left in the signature of every
+    /// function are generalized into its own new generic parameters.
+    ///
+    /// Generated parameters are numbered after the declared generics
+    /// of the function.
+    ///
+    fn infer_fn_group(
+        &mut self,
+        group: Vec<(usize, Id<FnDef>, ast::item::Item)>,
+    ) -> Vec<(usize, tir::item::Item)> {
+        // Remembering return types that are being inferred
+        for (_, id, item) in &group {
+            let ret = self.icx.shallow_resolve(self.icx.tcx._fn(*id).ret);
+            if let TyKind::Var(var) = self.icx.tcx.kind(ret)
+                && let ast::item::ItemKind::Function(f) = &item.kind
+            {
+                self.returns.insert(*var, f.name);
+            }
+        }
+
+        // Inferring bodies
+        self.icx.enter_level();
+        let functions = group
+            .into_iter()
+            .filter_map(|(idx, id, item)| match item.kind {
+                ast::item::ItemKind::Function(f) => {
+                    macros::ice::set_item(
+                        f.name.as_str(),
+                        self.icx.tcx.source(&item.span),
+                        item.span.1.clone(),
+                    );
+                    self.lints.push(&item.attrs);
+                    let f = self.infer_fn_body(id, item.id, f);
+                    self.lints.pop();
+                    macros::ice::clear_item();
+                    Some((idx, id, item.id, item.publicity, item.span, f))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        self.icx.exit_level();
+        self.returns.clear();
+        self.complete_returns_needed();
+
+        // Generalizing every signature over its own variables. Functions
+        // of the group share variables, so they are mapped to generics
+        // of every function separately, instead of being substituted.
+        // Variables of other signatures, used by the body, are not
+        // constrained by it, so the zonker reports them as needing
+        // annotations in this function
+        functions
+            .into_iter()
+            .map(|(idx, id, item_id, publicity, span, f)| {
+                let sig = f
+                    .params
+                    .iter()
+                    .map(|p| p.ty)
+                    .chain([f.ty])
+                    .collect::<Vec<Ty>>();
+                let base = f.generics.len();
+                let mapping = self.icx.generalize_generics(base, &sig);
+
+                let mut generics = f.generics;
+                Self::extend_generics(&mut generics, mapping.len());
+                let params = f
+                    .params
+                    .into_iter()
+                    .map(|p| {
+                        let ty = self.icx.apply(p.ty);
+                        Param {
+                            ty: self.icx.replace_vars(ty, &mapping),
+                            ..p
+                        }
+                    })
+                    .collect::<Vec<Param>>();
+                let ret = self.icx.apply(f.ty);
+                let ret = self.icx.replace_vars(ret, &mapping);
+
+                let def = self.icx.tcx.fn_mut(id);
+                def.params = params.iter().map(|p| p.ty).collect();
+                def.ret = ret;
+                def.generics = generics.clone();
+                let docs = def.docs.clone();
+
+                let function = self.zonk_fn(
+                    tir::item::Function {
+                        name: f.name,
+                        generics,
+                        params,
+                        ty: ret,
+                        block: f.block,
+                    },
+                    mapping,
+                );
+                (
+                    idx,
+                    tir::item::Item {
+                        id: item_id,
+                        docs,
+                        publicity,
+                        kind: tir::item::ItemKind::Function(function),
+                        span,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Appends `count` generated generic names to `generics`. Names
+    /// are numbered after the index of the parameter and skip the
+    /// declared ones, so `fn f<T1>(x)` gets `T2` rather than a clash.
+    fn extend_generics(generics: &mut Vec<Symbol>, count: usize) {
+        let mut n = generics.len();
+        for _ in 0..count {
+            let name = loop {
+                let name = Symbol::intern(&format!("T{n}"));
+                n += 1;
+                if !generics.contains(&name) {
+                    break name;
+                }
+            };
+            generics.push(name);
+        }
+    }
+
+    /// Converts ADT item into the typed one using its definition
+    fn infer_adt(&mut self, id: Id<AdtDef>) -> tir::item::ItemKind {
+        match self.icx.tcx.adt(id) {
+            AdtDef::Struct(s) => tir::item::ItemKind::Struct(tir::item::Struct {
+                name: s.name,
+                generics: s.generics.clone(),
+                fields: s
+                    .fields
+                    .iter()
+                    .map(|f| tir::item::Field {
+                        docs: f.docs.clone(),
+                        span: f.span.clone(),
+                        name: f.name,
+                        ty: f.ty,
+                    })
+                    .collect(),
+            }),
+            AdtDef::Enum(e) => tir::item::ItemKind::Enum(tir::item::Enum {
+                name: e.name,
+                generics: e.generics.clone(),
+                variants: e
+                    .variants
+                    .iter()
+                    .map(|v| tir::item::Variant {
+                        docs: v.docs.clone(),
+                        span: v.span.clone(),
+                        name: v.name,
+                        params: v.fields.clone(),
+                    })
+                    .collect(),
+            }),
+        }
+    }
+
+    /// Infers top-level item
+    fn infer_item(&mut self, item: ast::item::Item) -> Option<tir::item::Item> {
+        let kind = match item.kind {
+            ast::item::ItemKind::Struct(s) => self.infer_adt(self.resolver.lookup_adt(s.name)?),
+            ast::item::ItemKind::Enum(e) => self.infer_adt(self.resolver.lookup_adt(e.name)?),
+            // Functions are inferred by call graph components,
+            // the rest are duplicates reported by the early phase
+            ast::item::ItemKind::Function(_) => return None,
+            ast::item::ItemKind::Use(u) => tir::item::ItemKind::Use(tir::item::Use {
+                path: u.path,
+                kind: match u.kind {
+                    ast::item::UseKind::As(name) => tir::item::UseKind::As(name),
+                    ast::item::UseKind::For(names) => tir::item::UseKind::For(names),
+                    ast::item::UseKind::Just => tir::item::UseKind::Just,
+                },
+            }),
+        };
+
+        Some(tir::item::Item {
+            id: item.id,
+            docs: item.docs,
+            publicity: item.publicity,
+            kind,
+            span: item.span,
+        })
+    }
+
+    /// Sets item, that is being checked, reported by internal compiler errors
+    pub(crate) fn set_ice_item(&self, item: &ast::item::Item) {
+        let name = match &item.kind {
+            ast::item::ItemKind::Struct(s) => s.name.as_str(),
+            ast::item::ItemKind::Enum(e) => e.name.as_str(),
+            ast::item::ItemKind::Function(f) => f.name.as_str(),
+            ast::item::ItemKind::Use(u) => u.path.as_str(),
+        };
+        macros::ice::set_item(name, self.icx.tcx.source(&item.span), item.span.1.clone());
+    }
+
+    /// Checks module: declares its items, then infers function bodies
+    /// by strongly connected components of the call graph, so callees
+    /// are inferred before their callers
+    pub fn check_module(&mut self, m: ast::item::Module) -> tir::item::Module {
+        macros::ice::set_phase("early analysis");
+        self.early_phase(&m);
+        self.check_attributes(&m);
+        let attrs = m
+            .items
+            .iter()
+            .map(|item| (item.span.clone(), item.attrs.clone()))
+            .collect::<Vec<_>>();
+
+        // Collecting functions, that own their definitions
+        let mut items = m.items.into_iter().map(Some).collect::<Vec<_>>();
+        let functions = items
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, item)| {
+                let item = item.as_ref()?;
+                let ast::item::ItemKind::Function(f) = &item.kind else {
+                    return None;
+                };
+                match self.resolver.lookup_item(f.name)?.kind {
+                    ItemDefKind::Fn(id) if self.icx.tcx._fn(id).span == item.span => {
+                        Some((idx, id, f))
+                    }
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        let graph = CallGraph::build(&functions.iter().map(|(.., f)| *f).collect::<Vec<_>>());
+        let functions = functions
+            .into_iter()
+            .map(|(idx, id, _)| (idx, id))
+            .collect::<Vec<_>>();
+
+        // Inferring functions by components
+        macros::ice::set_phase("type checking");
+        let mut results = (0..items.len()).map(|_| None).collect::<Vec<_>>();
+        for scc in graph.sccs() {
+            let group = scc
+                .into_iter()
+                .filter_map(|node| {
+                    let (idx, id) = functions[node];
+                    Some((idx, id, items[idx].take()?))
+                })
+                .collect();
+            for (idx, item) in self.infer_fn_group(group) {
+                results[idx] = Some(item);
+            }
+        }
+
+        // Inferring rest items
+        for (idx, item) in items.into_iter().enumerate() {
+            if let Some(item) = item {
+                self.set_ice_item(&item);
+                results[idx] = self.infer_item(item);
+            }
+        }
+        macros::ice::clear_item();
+
+        macros::ice::set_phase("lints");
+        self.check_unused(&attrs);
+        self.check_confusables(&m.idents, &attrs);
+
+        // Moving results to the context
+        let typeck_results = std::mem::take(&mut self.results);
+        self.icx.tcx.results.extend(typeck_results);
+        tir::item::Module {
+            items: results.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Takes reported diagnostics
+    pub fn take_diagnostics(&mut self) -> Vec<TypeckError> {
+        std::mem::take(&mut self.diagnostics)
+    }
 }
@@ -0,0 +1,94 @@
+/// Imports
+use crate::{
+    check::ModuleTyck,
+    lint::{CONFUSABLE_IDENTS, MIXED_SCRIPT_CONFUSABLES, item_attrs},
+};
+use ast::item::Attribute;
+use common::{symbol::Symbol, token::Span};
+use std::collections::{BTreeSet, HashMap};
+use unicode_script::{Script, UnicodeScript};
+use unicode_security::{MixedScript, is_potential_mixed_script_confusable_char, skeleton};
+
+/// Returns names of the scripts, used by identifier: `Cyrillic and Latin`
+fn scripts(name: &str) -> String {
+    let scripts = name
+        .chars()
+        .map(|ch| ch.script())
+        .filter(|script| !matches!(script, Script::Common | Script::Inherited))
+        .map(|script| script.full_name())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect::<Vec<_>>();
+    match scripts.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {last}", rest.join(", ")),
+        _ => scripts.join(""),
+    }
+}
+
+/// Confusable identifiers detection.
+///
+/// Identifiers are already NFC normalized by the lexer, so only
+/// visually similar, but different identifiers are reported: the
+/// ones mixing scripts with confusable characters, like Latin `pаy`
+/// with Cyrillic `а`, and pairs sharing the same UTS #39 skeleton.
+/// Modules with ASCII identifiers only are skipped. Lint levels
+/// are resolved by the item, that contains the identifier.
+///
+impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
+    /// Reports mixed-script and confusable identifiers of the module.
+    /// Items are given by their spans and attributes
+    pub(crate) fn check_confusables(
+        &mut self,
+        idents: &[(Symbol, Span)],
+        items: &[(Span, Vec<Attribute>)],
+    ) {
+        if idents.iter().all(|(name, _)| name.as_str().is_ascii()) {
+            return;
+        }
+
+        // Mixed-script identifiers
+        for (name, span) in idents {
+            let name = name.as_str();
+            if !name.is_single_script()
+                && name.chars().any(is_potential_mixed_script_confusable_char)
+            {
+                self.lints.push(item_attrs(items, span));
+                self.lint(
+                    &MIXED_SCRIPT_CONFUSABLES,
+                    span,
+                    "mixes scripts".to_string(),
+                    format!("identifier `{name}` mixes {} scripts", scripts(name)),
+                    Some("use characters of a single script in the identifier".to_string()),
+                );
+                self.lints.pop();
+            }
+        }
+
+        // Identifiers with the same skeleton
+        let mut skeletons: HashMap<String, &(Symbol, Span)> = HashMap::new();
+        for ident @ (name, span) in idents {
+            let key = skeleton(name.as_str()).collect::<String>();
+            match skeletons.get(&key) {
+                Some((other, _)) if name.as_str().is_ascii() && other.as_str().is_ascii() => {}
+                Some((other, at)) => {
+                    let at = self.icx.tcx.sources.get(at.0).line_col(at.1.start);
+                    self.lints.push(item_attrs(items, span));
+                    self.lint(
+                        &CONFUSABLE_IDENTS,
+                        span,
+                        "looks like another identifier".to_string(),
+                        format!("identifier `{name}` is confusable with `{other}`"),
+                        Some(format!(
+                            "`{other}` is used at {}:{}, consider renaming one of them",
+                            at.line, at.column
+                        )),
+                    );
+                    self.lints.pop();
+                }
+                None => {
+                    skeletons.insert(key, ident);
+                }
+            }
+        }
+    }
+}
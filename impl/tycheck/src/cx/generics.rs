@@ -1,9 +1,12 @@
+/// Imports
+use common::symbol::Symbol;
+
 /// A scoped stack of generic parameter lists used during
 /// type inference.
 ///
 /// Each entry on the stack corresponds to one set of generic parameters — for
 /// example, those declared on a function or a struct. Parameters are stored in
-/// declaration order, so the index of a name in its `Vec<String>` is the same
+/// declaration order, so the index of a name in its `Vec<Symbol>` is the same
 /// integer used in `Ty::Generic(i)`.
 ///
 /// Scopes must be pushed before entering a generic item and popped upon exit,
@@ -11,7 +14,7 @@
 ///
 #[derive(Default, Debug)]
 pub struct GenericsCx {
-    stack: Vec<Vec<String>>,
+    stack: Vec<Vec<Symbol>>,
 }
 
 /// Implementation
@@ -20,7 +23,7 @@ impl GenericsCx {
     ///
     /// `generics` must list parameter names in declaration order so that
     /// index `i` corresponds to `Ty::Generic(i)`.
-    pub fn push(&mut self, generics: Vec<String>) {
+    pub fn push(&mut self, generics: Vec<Symbol>) {
         self.stack.push(generics);
     }
 
@@ -31,14 +34,19 @@ impl GenericsCx {
 
     /// Looks up `name` in the innermost scope and returns its index, or
     /// `None` if the name is not present or the stack is empty.
-    pub fn lookup(&self, name: &str) -> Option<usize> {
-        self.stack.last()?.iter().position(|g| g == name)
+    pub fn lookup(&self, name: Symbol) -> Option<usize> {
+        self.stack.last()?.iter().position(|g| *g == name)
     }
 
     /// Returns the name of the generic parameter at position `idx` in the
     /// innermost scope, or `None` if the index is out of range or the stack
     /// is empty.
-    pub fn name_of(&self, idx: usize) -> Option<String> {
-        self.stack.last()?.get(idx).cloned()
+    pub fn name_of(&self, idx: usize) -> Option<Symbol> {
+        self.stack.last()?.get(idx).copied()
+    }
+
+    /// Returns names of the generic parameters in the innermost scope.
+    pub fn names(&self) -> Vec<Symbol> {
+        self.stack.last().cloned().unwrap_or_default()
     }
 }
@@ -1,10 +1,11 @@
 /// Imports
 use crate::{
-    cx::{generics::GenericsCx, tcx::TyCx},
+    cx::{generics::GenericsCx, printer::TyPrinter, tcx::TyCx, vars::VarStore},
     errors::ty::TypeError,
 };
-use id_arena::{Arena, Id};
-use tir::ty::{GenericArgs, Ty, TyVar};
+use id_arena::Id;
+use std::collections::{HashMap, HashSet};
+use tir::ty::{GenericArgs, Scheme, Ty, TyKind, TyVar};
 
 /// Inference context: manages type variables, substitutions, and generic
 /// instantiation during Hindley-Milner–style type inference.
@@ -14,11 +15,16 @@ use tir::ty::{GenericArgs, Ty, TyVar};
 ///
 /// - **Fresh type variables** - call [`fresh`] to create a new `TyVar::Unbound`
 ///   and obtain its `Id<TyVar>`. Unbound variables act as inference holes.
+///   Variables live in a union-find [`VarStore`], unified variables share
+///   one equivalence class.
 ///
 /// - **Substitutions** - [`substitute`] binds an unbound variable to a
 ///   concrete type. [`apply`] walks a `Ty` tree and replaces every
 ///   `Ty::Var(id)` whose variable is bound with its bound type.
 ///
+/// - **Speculation** - [`probe`] and [`commit_if_ok`] run inference steps
+///   inside a store snapshot, rolling substitutions back when needed.
+///
 /// - **Unification** - [`unify`] attempts to make two types equal by
 ///   recording substitutions, failing with [`TypeError`] if the types are
 ///   structurally incompatible. An occurs check prevents infinite types.
@@ -26,6 +32,15 @@ use tir::ty::{GenericArgs, Ty, TyVar};
 /// - **Generic instantiation** - [`instantiate`] replaces `Ty::Generic(i)`
 ///   placeholders with concrete arguments.
 ///
+/// - **Let-generalization** - every unbound variable remembers the binding
+///   level it was created at. [`generalize`] quantifies over variables whose
+///   level is deeper than the current one, producing a [`Scheme`], and
+///   [`instantiate_scheme`] replaces quantified variables with fresh ones at
+///   each use site.
+///
+/// - **Printing** - [`printer`] renders types for diagnostics, naming
+///   inference variables `?T1`, `?T2`, ... consistently within one message.
+///
 /// - **Generic scoping** - the embedded [`GenericsCx`] tracks which generic
 ///   parameters are in scope so that rigid variables can be identified during
 ///   unification.
@@ -35,16 +50,29 @@ use tir::ty::{GenericArgs, Ty, TyVar};
 /// [`apply`]: InferCx::apply
 /// [`unify`]: InferCx::unify
 /// [`instantiate`]: InferCx::instantiate
+/// [`generalize`]: InferCx::generalize
+/// [`instantiate_scheme`]: InferCx::instantiate_scheme
+/// [`probe`]: InferCx::probe
+/// [`commit_if_ok`]: InferCx::commit_if_ok
+/// [`printer`]: InferCx::printer
 ///
 pub struct InferCx<'tcx> {
     /// Shared type-definition context for looking up ADTs and functions.
     pub(crate) tcx: &'tcx mut TyCx,
 
-    /// Arena that owns all type variables created during inference.
-    type_variables: Arena<TyVar>,
+    /// Union-find store of all type variables created during inference.
+    type_variables: VarStore,
 
     /// Stack of in-scope generic parameter lists.
     pub(crate) generics: GenericsCx,
+
+    /// Current binding level. Incremented when entering the
+    /// right-hand side of a generalizable binding.
+    level: usize,
+
+    /// Type variables quantified by let-bound schemes. They stay
+    /// unbound and are instantiated at every use site.
+    quantified: HashSet<Id<TyVar>>,
 }
 
 /// Implementation
@@ -53,31 +81,51 @@ impl<'tcx> InferCx<'tcx> {
     pub fn new(tcx: &'tcx mut TyCx) -> Self {
         Self {
             tcx,
-            type_variables: Arena::new(),
+            type_variables: VarStore::default(),
             generics: GenericsCx::default(),
+            level: 0,
+            quantified: HashSet::new(),
         }
     }
 
+    /// Enters a new binding level. Variables created after this call
+    /// can be generalized once the level is exited.
+    pub fn enter_level(&mut self) {
+        self.level += 1;
+    }
+
+    /// Exits the current binding level.
+    pub fn exit_level(&mut self) {
+        self.level -= 1;
+    }
+
     /// Binds type variable `id` to `ty` if it is still unbound.
     ///
     /// If `id` is already bound, this call is a no-op — existing substitutions
     /// are never overwritten.
     pub fn subst(&mut self, id: Id<TyVar>, ty: Ty) {
-        let var = self.type_variables.get_mut(id).expect("invalid TyVar id");
-        if let TyVar::Unbound = var {
-            *var = TyVar::Bound(ty);
+        if let TyVar::Unbound(_) = self.type_variables.value(id) {
+            self.type_variables.set_value(id, TyVar::Bound(ty));
         }
     }
 
-    /// Allocates a new unbound type variable and returns its ID.
+    /// Allocates a new unbound type variable at the current
+    /// binding level and returns its ID.
     pub fn fresh(&mut self) -> Id<TyVar> {
-        self.type_variables.alloc(TyVar::Unbound)
+        self.type_variables.new_var(TyVar::Unbound(self.level))
+    }
+
+    /// Allocates a new unbound type variable at the current
+    /// binding level and returns its type.
+    pub fn fresh_ty(&mut self) -> Ty {
+        let id = self.fresh();
+        self.tcx.mk_var(id)
     }
 
     /// Allocates a new bound type variable with
     /// given binding and returns its ID.
     pub fn fresh_bound(&mut self, to: Ty) -> Id<TyVar> {
-        self.type_variables.alloc(TyVar::Bound(to))
+        self.type_variables.new_var(TyVar::Bound(to))
     }
 
     /// Allocates one fresh unbound type variable per entry in `generics` and
@@ -85,44 +133,93 @@ impl<'tcx> InferCx<'tcx> {
     /// polymorphic definition at a call site.
     ///
     pub fn fresh_generics(&mut self, amount: usize) -> GenericArgs {
-        (0..amount).map(|_| Ty::Var(self.fresh())).collect()
+        (0..amount).map(|_| self.fresh_ty()).collect()
     }
 
-    /// Returns an immutable reference to the type variable with `id`.
+    /// Returns value of the equivalence class of type variable `id`.
     ///
     /// # Panics
-    /// Panics if `id` is not a valid arena index.
+    /// Panics if `id` is not a valid variable.
     ///
     pub fn var(&self, id: Id<TyVar>) -> &TyVar {
-        self.type_variables.get(id).expect("invalid TyVar id")
+        self.type_variables.value(id)
+    }
+
+    /// Returns representative of the equivalence class of type variable `id`.
+    pub fn root(&self, id: Id<TyVar>) -> Id<TyVar> {
+        self.type_variables.find(id)
     }
 
-    /// Returns a mutable reference to the type variable with `id`.
+    /// Runs `f` speculatively: every substitution made by it is rolled
+    /// back afterwards, regardless of the result.
     ///
-    /// # Panics
-    /// Panics if `id` is not a valid arena index.
+    pub fn probe<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+        let snapshot = self.type_variables.snapshot();
+        let result = f(self);
+        self.type_variables.rollback_to(snapshot);
+        result
+    }
+
+    /// Runs `f` keeping its substitutions only if it succeeds. On error
+    /// the store is rolled back, so no partial substitutions leak.
     ///
-    pub fn var_mut(&mut self, id: Id<TyVar>) -> &mut TyVar {
-        self.type_variables.get_mut(id).expect("invalid TyVar id")
+    pub fn commit_if_ok<T, E>(
+        &mut self,
+        f: impl FnOnce(&mut Self) -> Result<T, E>,
+    ) -> Result<T, E> {
+        let snapshot = self.type_variables.snapshot();
+        let result = f(self);
+        match result {
+            Ok(_) => self.type_variables.commit(snapshot),
+            Err(_) => self.type_variables.rollback_to(snapshot),
+        }
+        result
     }
 
     /// Applies the current substitution map to `ty`, replacing every bound
     /// `Ty::Var(id)` with its bound type. Unbound variables and all other
-    /// type constructors are returned unchanged. The operation is shallow on
-    /// unbound variables (it does not chase chains of bound variables
-    /// recursively beyond one level for `Var`).
-    ///
-    pub fn apply(&self, ty: Ty) -> Ty {
-        match ty {
-            Ty::Var(id) => match self.var(id) {
-                TyVar::Unbound => ty,
-                TyVar::Bound(typ) => typ.clone(),
+    /// type constructors are returned unchanged.
+    ///
+    /// Types without inference variables are returned as is, without
+    /// rebuilding them.
+    ///
+    pub fn apply(&mut self, ty: Ty) -> Ty {
+        if !self.tcx.flags(ty).has_vars {
+            return ty;
+        }
+        match self.tcx.kind(ty).clone() {
+            TyKind::Var(id) => match self.var(id) {
+                TyVar::Unbound(_) => ty,
+                TyVar::Bound(typ) => self.apply(*typ),
             },
-            Ty::Adt(def, args) => Ty::Adt(def, args.into_iter().map(|it| self.apply(it)).collect()),
-            Ty::FnDef(def, args) => {
-                Ty::FnDef(def, args.into_iter().map(|it| self.apply(it)).collect())
+            TyKind::Adt(def, args) => {
+                let args = args.into_iter().map(|it| self.apply(it)).collect();
+                self.tcx.mk_adt(def, args)
+            }
+            TyKind::FnDef(def, args) => {
+                let args = args.into_iter().map(|it| self.apply(it)).collect();
+                self.tcx.mk_fn_def(def, args)
             }
-            other => other,
+            TyKind::FnRef(sig) => {
+                let params = sig.params.into_iter().map(|it| self.apply(it)).collect();
+                let ret = self.apply(sig.ret);
+                self.tcx.mk_fn_ref(params, ret)
+            }
+            _ => ty,
+        }
+    }
+
+    /// Resolves only the head of `ty`: a bound variable is
+    /// replaced with its bound type, until a non-variable type
+    /// or an unbound variable is reached.
+    ///
+    pub fn shallow_resolve(&self, ty: Ty) -> Ty {
+        match self.tcx.kind(ty) {
+            TyKind::Var(id) => match self.var(*id) {
+                TyVar::Unbound(_) => ty,
+                TyVar::Bound(typ) => self.shallow_resolve(*typ),
+            },
+            _ => ty,
         }
     }
 
@@ -131,32 +228,221 @@ impl<'tcx> InferCx<'tcx> {
     /// If `i` is out of bounds for `args` (which should not happen in
     /// well-formed code), the `Generic` is left unchanged.
     ///
-    pub fn instantiate(&self, ty: Ty, args: &GenericArgs) -> Ty {
-        match ty {
-            Ty::Generic(i) => args.get(i).cloned().unwrap_or(Ty::Generic(i)),
-            Ty::Adt(id, inner_args) => Ty::Adt(
-                id,
-                inner_args
+    pub fn instantiate(&mut self, ty: Ty, args: &[Ty]) -> Ty {
+        if !self.tcx.flags(ty).has_generics {
+            return ty;
+        }
+        match self.tcx.kind(ty).clone() {
+            TyKind::Generic(i) => args.get(i).copied().unwrap_or(ty),
+            TyKind::Adt(id, inner_args) => {
+                let inner_args = inner_args
                     .into_iter()
                     .map(|a| self.instantiate(a, args))
-                    .collect(),
-            ),
-            Ty::FnDef(id, inner_args) => Ty::FnDef(
-                id,
-                inner_args
+                    .collect();
+                self.tcx.mk_adt(id, inner_args)
+            }
+            TyKind::FnDef(id, inner_args) => {
+                let inner_args = inner_args
                     .into_iter()
                     .map(|a| self.instantiate(a, args))
-                    .collect(),
-            ),
-            other => other,
+                    .collect();
+                self.tcx.mk_fn_def(id, inner_args)
+            }
+            TyKind::FnRef(sig) => {
+                let params = sig
+                    .params
+                    .into_iter()
+                    .map(|p| self.instantiate(p, args))
+                    .collect();
+                let ret = self.instantiate(sig.ret, args);
+                self.tcx.mk_fn_ref(params, ret)
+            }
+            _ => ty,
+        }
+    }
+
+    /// Collects representatives of unbound type variables
+    /// of `ty` into `out`, preserving first-occurrence order.
+    ///
+    fn free_vars(&self, ty: Ty, out: &mut Vec<Id<TyVar>>) {
+        if !self.tcx.flags(ty).has_vars {
+            return;
+        }
+        match self.tcx.kind(ty) {
+            TyKind::Var(id) => match self.var(*id) {
+                TyVar::Unbound(_) => {
+                    let root = self.root(*id);
+                    if !out.contains(&root) {
+                        out.push(root);
+                    }
+                }
+                TyVar::Bound(bound) => self.free_vars(*bound, out),
+            },
+            TyKind::Adt(_, args) | TyKind::FnDef(_, args) => {
+                args.iter().for_each(|a| self.free_vars(*a, out))
+            }
+            TyKind::FnRef(sig) => {
+                sig.params.iter().for_each(|p| self.free_vars(*p, out));
+                self.free_vars(sig.ret, out);
+            }
+            _ => {}
+        }
+    }
+
+    /// Collects unbound type variables of `ty` created at a level deeper than
+    /// the current one into `out`, preserving first-occurrence order.
+    ///
+    fn generalizable_vars(&self, ty: Ty, out: &mut Vec<Id<TyVar>>) {
+        let mut vars = Vec::new();
+        self.free_vars(ty, &mut vars);
+        for var in vars {
+            if matches!(self.var(var), TyVar::Unbound(level) if *level > self.level)
+                && !out.contains(&var)
+            {
+                out.push(var);
+            }
+        }
+    }
+
+    /// Returns unbound type variables of `ty`, that are not quantified by
+    /// any let-bound scheme. Such variables are inference holes left
+    /// unresolved after checking.
+    ///
+    pub fn unresolved_vars(&self, ty: Ty) -> Vec<Id<TyVar>> {
+        let mut vars = Vec::new();
+        self.free_vars(ty, &mut vars);
+        vars.retain(|var| !self.quantified.contains(var));
+        vars
+    }
+
+    /// Generalizes `ty` into a [`Scheme`] quantified over every unbound
+    /// variable created at a level deeper than the current one.
+    ///
+    /// Must be called after [`exit_level`], so variables that escaped into
+    /// the enclosing environment (and had their level lowered by
+    /// unification) stay monomorphic.
+    ///
+    /// [`exit_level`]: InferCx::exit_level
+    ///
+    pub fn generalize(&mut self, ty: Ty) -> Scheme {
+        let ty = self.apply(ty);
+        let mut vars = Vec::new();
+        self.generalizable_vars(ty, &mut vars);
+        self.quantified.extend(vars.iter().copied());
+        Scheme { vars, ty }
+    }
+
+    /// Keeps `ty` monomorphic, lowering levels of its unbound variables
+    /// to the current one, so later generalizations won't quantify them.
+    ///
+    /// Used for bindings rejected by the value restriction.
+    ///
+    pub fn monomorphize(&mut self, ty: Ty) -> Scheme {
+        let ty = self.apply(ty);
+        self.adjust_levels(self.level, ty);
+        Scheme::mono(ty)
+    }
+
+    /// Returns unbound variables of `tys`, created at a level deeper
+    /// than the current one, preserving first-occurrence order.
+    pub fn generalizable(&self, tys: &[Ty]) -> Vec<Id<TyVar>> {
+        let mut vars = Vec::new();
+        tys.iter()
+            .for_each(|ty| self.generalizable_vars(*ty, &mut vars));
+        vars
+    }
+
+    /// Returns unbound type variables of `ty`, that are quantified by
+    /// let-bound schemes, preserving first-occurrence order.
+    pub fn quantified_vars(&self, ty: Ty) -> Vec<Id<TyVar>> {
+        let mut vars = Vec::new();
+        self.free_vars(ty, &mut vars);
+        vars.retain(|var| self.quantified.contains(var));
+        vars
+    }
+
+    /// Generalizes a function signature by mapping every unbound variable
+    /// created at a level deeper than the current one to a new rigid
+    /// generic parameter, numbered starting from `base`.
+    ///
+    /// Variables are not substituted, so functions sharing them could
+    /// be generalized separately. Returns the mapping from variables
+    /// to introduced generic parameters.
+    ///
+    pub fn generalize_generics(&mut self, base: usize, tys: &[Ty]) -> HashMap<Id<TyVar>, Ty> {
+        self.generalizable(tys)
+            .into_iter()
+            .enumerate()
+            .map(|(i, var)| (var, self.tcx.mk_generic(base + i)))
+            .collect()
+    }
+
+    /// Instantiates type scheme by replacing every quantified
+    /// variable with a fresh unbound type variable. Returns the
+    /// instantiated type and fresh variables in order of quantified ones.
+    ///
+    pub fn instantiate_scheme(&mut self, scheme: &Scheme) -> (Ty, GenericArgs) {
+        if scheme.vars.is_empty() {
+            return (scheme.ty, Vec::new());
+        }
+        let args = self.fresh_generics(scheme.vars.len());
+        let mapping: HashMap<Id<TyVar>, Ty> = scheme
+            .vars
+            .iter()
+            .copied()
+            .zip(args.iter().copied())
+            .collect();
+        let ty = self.apply(scheme.ty);
+        (self.replace_vars(ty, &mapping), args)
+    }
+
+    /// Replaces type variables of `ty` according to `mapping`,
+    /// that is keyed by the class representatives.
+    pub(crate) fn replace_vars(&mut self, ty: Ty, mapping: &HashMap<Id<TyVar>, Ty>) -> Ty {
+        if !self.tcx.flags(ty).has_vars {
+            return ty;
+        }
+        match self.tcx.kind(ty).clone() {
+            TyKind::Var(id) => mapping.get(&self.root(id)).copied().unwrap_or(ty),
+            TyKind::Adt(id, args) => {
+                let args = args
+                    .into_iter()
+                    .map(|a| self.replace_vars(a, mapping))
+                    .collect();
+                self.tcx.mk_adt(id, args)
+            }
+            TyKind::FnDef(id, args) => {
+                let args = args
+                    .into_iter()
+                    .map(|a| self.replace_vars(a, mapping))
+                    .collect();
+                self.tcx.mk_fn_def(id, args)
+            }
+            TyKind::FnRef(sig) => {
+                let params = sig
+                    .params
+                    .into_iter()
+                    .map(|p| self.replace_vars(p, mapping))
+                    .collect();
+                let ret = self.replace_vars(sig.ret, mapping);
+                self.tcx.mk_fn_ref(params, ret)
+            }
+            _ => ty,
         }
     }
 
     /// Attempts to make `t1` and `t2` equal by recording substitutions for
     /// unbound type variables.
     ///
-    /// Substitutions applied before comparing using [`apply`], so partially
-    /// solved variables are chased before any structural comparison.
+    /// Heads of both types are resolved before comparing, so solved variables
+    /// are chased before any structural comparison. Interned types are equal
+    /// exactly when their handles are, so identical types unify immediately.
+    /// Unification is atomic: if it fails, substitutions recorded so far are
+    /// rolled back.
+    ///
+    /// # Errors absorbing
+    /// `Ty::Error` unifies with any type, including nested positions, so
+    /// one root cause never produces secondary mismatches.
     ///
     /// # Coercions
     /// A `Ty::FnDef` (a specific named function) unifies with a `Ty::FnRef`
@@ -170,80 +456,92 @@ impl<'tcx> InferCx<'tcx> {
     /// occurs check detects a cycle (e.g. `T = Vec<T>`).
     ///
     pub fn unify(&mut self, t1: Ty, t2: Ty) -> Result<(), TypeError> {
-        // Applying substitutions
-        let t1 = self.apply(t1);
-        let t2 = self.apply(t2);
+        self.commit_if_ok(|icx| icx.unify_types(t1, t2))
+    }
+
+    /// Unifies `t1` and `t2` without rolling back on failure
+    fn unify_types(&mut self, t1: Ty, t2: Ty) -> Result<(), TypeError> {
+        // Resolving heads
+        let t1 = self.shallow_resolve(t1);
+        let t2 = self.shallow_resolve(t2);
+
+        // Same types
+        if t1 == t2 {
+            return Ok(());
+        }
 
         // Matching types
-        match (t1, t2) {
-            // Skipping errors
-            (Ty::Error, _) | (_, Ty::Error) => Ok(()),
-
-            // Same primitive types
-            (Ty::Int, Ty::Int) => Ok(()),
-            (Ty::Float, Ty::Float) => Ok(()),
-            (Ty::Bool, Ty::Bool) => Ok(()),
-            (Ty::String, Ty::String) => Ok(()),
-            (Ty::Unit, Ty::Unit) => Ok(()),
-
-            // Rigid generics: two identical generic indices unify; anything else is an error.
-            (Ty::Generic(a), Ty::Generic(b)) if a == b => Ok(()),
-            (Ty::Generic(_), other) | (other, Ty::Generic(_)) => {
-                Err(TypeError::RigidMismatch(other))
+        match (self.tcx.kind(t1).clone(), self.tcx.kind(t2).clone()) {
+            // Same type variables
+            (TyKind::Var(a), TyKind::Var(b)) if self.root(a) == self.root(b) => Ok(()),
+
+            // Unbound type variables, merging their classes
+            (TyKind::Var(a), TyKind::Var(b)) => {
+                self.type_variables.union(a, b);
+                Ok(())
             }
 
+            // Type variables: delegate to unify_var. Variables unified
+            // with an error are bound to it, so the error keeps absorbing.
+            (TyKind::Var(id), _) => self.unify_var(id, t2),
+            (_, TyKind::Var(id)) => self.unify_var(id, t1),
+
+            // Errors are compatible with anything: they are
+            // already reported, so no cascading diagnostics
+            (TyKind::Error, _) | (_, TyKind::Error) => Ok(()),
+
+            // Rigid generics and scheme params: identical indices are
+            // the same interned type; anything else is an error.
+            (TyKind::Generic(_) | TyKind::Param(_), _) => Err(TypeError::RigidMismatch(t2)),
+            (_, TyKind::Generic(_) | TyKind::Param(_)) => Err(TypeError::RigidMismatch(t1)),
+
             // ADT, unifying args
-            (Ty::Adt(a_id, a_args), Ty::Adt(b_id, b_args)) if a_id == b_id => {
+            (TyKind::Adt(a_id, a_args), TyKind::Adt(b_id, b_args)) if a_id == b_id => {
                 for (a, b) in a_args.into_iter().zip(b_args) {
-                    self.unify(a, b)?;
+                    self.unify_types(a, b)?;
                 }
                 Ok(())
             }
 
             // Function definitions, unifying args
-            (Ty::FnDef(a_id, a_args), Ty::FnDef(b_id, b_args)) if a_id == b_id => {
+            (TyKind::FnDef(a_id, a_args), TyKind::FnDef(b_id, b_args)) if a_id == b_id => {
                 for (a, b) in a_args.into_iter().zip(b_args) {
-                    self.unify(a, b)?;
+                    self.unify_types(a, b)?;
                 }
                 Ok(())
             }
 
             // Two function-pointer types: unify param lists and return type.
-            (Ty::FnRef(a_sig), Ty::FnRef(b_sig)) => {
+            (TyKind::FnRef(a_sig), TyKind::FnRef(b_sig)) => {
                 if a_sig.params.len() != b_sig.params.len() {
-                    return Err(TypeError::Mismatch(Ty::FnRef(a_sig), Ty::FnRef(b_sig)));
+                    return Err(TypeError::Mismatch(t1, t2));
                 }
                 for (a, b) in a_sig.params.into_iter().zip(b_sig.params) {
-                    self.unify(a, b)?;
+                    self.unify_types(a, b)?;
                 }
-                self.unify(a_sig.ret, b_sig.ret)
+                self.unify_types(a_sig.ret, b_sig.ret)
             }
 
             // Implicit coercion: a named function definition unifies with a
             // compatible function-pointer signature.
-            (Ty::FnDef(id, args), Ty::FnRef(sig)) | (Ty::FnRef(sig), Ty::FnDef(id, args)) => {
+            (TyKind::FnDef(id, args), TyKind::FnRef(sig))
+            | (TyKind::FnRef(sig), TyKind::FnDef(id, args)) => {
                 let def = self.tcx._fn(id);
-                let params: Vec<Ty> = def
-                    .params
-                    .iter()
-                    .map(|p| self.instantiate(p.clone(), &args))
+                let (params, ret) = (def.params.clone(), def.ret);
+                let params: Vec<Ty> = params
+                    .into_iter()
+                    .map(|p| self.instantiate(p, &args))
                     .collect();
-                let ret = self.instantiate(def.ret.clone(), &args);
+                let ret = self.instantiate(ret, &args);
                 for (a, b) in params.into_iter().zip(sig.params) {
-                    self.unify(a, b)?;
+                    self.unify_types(a, b)?;
                 }
-                self.unify(ret, sig.ret)
+                self.unify_types(ret, sig.ret)
             }
 
-            // Type variables: delegate to unify_var.
-            (Ty::Var(id), ty) => self.unify_var(id, ty),
-            (ty, Ty::Var(id)) => self.unify_var(id, ty),
-
-            // Meta types: equal only if they carry identical metadata.
-            (Ty::Meta(a), Ty::Meta(b)) if a == b => Ok(()),
-
-            // All other combinations are hard mismatches.
-            (t1, t2) => Err(TypeError::Mismatch(t1, t2)),
+            // All other combinations, including different primitives
+            // and meta types, are hard mismatches.
+            _ => Err(TypeError::Mismatch(t1, t2)),
         }
     }
 
@@ -251,127 +549,106 @@ impl<'tcx> InferCx<'tcx> {
     ///
     /// - If `id` is already bound, the bound type is unified with `ty`.
     /// - If `id` is unbound, an occurs check is performed first to rule out
-    ///   infinite types, then `id` is substituted with `ty`.
+    ///   infinite types, then levels of variables inside `ty` are lowered to
+    ///   the level of `id` and `id` is substituted with `ty`.
     ///
     fn unify_var(&mut self, id: Id<TyVar>, ty: Ty) -> Result<(), TypeError> {
-        match self.var(id).clone() {
+        match *self.var(id) {
             // Variable already bound, unifying
-            TyVar::Bound(bound) => self.unify(bound, ty),
+            TyVar::Bound(bound) => self.unify_types(bound, ty),
 
             // Unbound variable
-            TyVar::Unbound => {
+            TyVar::Unbound(level) => {
                 // Performing occurs check: restricts infinite types like `T = Vec<T>`
-                if self.occurs(id, &ty) {
+                if self.occurs(id, ty) {
                     return Err(TypeError::InfiniteType);
                 }
+                self.adjust_levels(level, ty);
                 self.subst(id, ty);
                 Ok(())
             }
         }
     }
 
+    /// Lowers level of every unbound variable inside `ty` to `level`.
+    ///
+    /// When a variable escapes into a type visible from an outer binding
+    /// level, it must not be generalized by inner bindings anymore.
+    ///
+    fn adjust_levels(&mut self, level: usize, ty: Ty) {
+        let mut vars = Vec::new();
+        self.free_vars(ty, &mut vars);
+        for var in vars {
+            if matches!(self.var(var), TyVar::Unbound(other) if *other > level) {
+                self.type_variables.set_value(var, TyVar::Unbound(level));
+            }
+        }
+    }
+
     /// Returns `true` if type variable `id` appears anywhere inside `ty`.
     ///
     /// Used by the occurs check in [`unify_var`] to prevent binding a variable
     /// to a type that contains itself, which would create an infinite type.
     ///
-    fn occurs(&self, id: Id<TyVar>, ty: &Ty) -> bool {
-        match ty {
-            Ty::Var(other) => {
-                if *other == id {
+    fn occurs(&self, id: Id<TyVar>, ty: Ty) -> bool {
+        if !self.tcx.flags(ty).has_vars {
+            return false;
+        }
+        match self.tcx.kind(ty) {
+            TyKind::Var(other) => {
+                if self.root(*other) == self.root(id) {
                     return true;
                 }
                 match self.var(*other) {
-                    TyVar::Bound(inner) => self.occurs(id, inner),
+                    TyVar::Bound(inner) => self.occurs(id, *inner),
                     _ => false,
                 }
             }
-            Ty::Adt(_, args) | Ty::FnDef(_, args) => args.iter().any(|a| self.occurs(id, a)),
-            Ty::FnRef(sig) => {
-                sig.params.iter().any(|a| self.occurs(id, a)) || self.occurs(id, &sig.ret)
+            TyKind::Adt(_, args) | TyKind::FnDef(_, args) => {
+                args.iter().any(|a| self.occurs(id, *a))
+            }
+            TyKind::FnRef(sig) => {
+                sig.params.iter().any(|a| self.occurs(id, *a)) || self.occurs(id, sig.ret)
             }
             _ => false,
         }
     }
 
-    /// Returns a human-readable string representation of `ty`.
-    ///
-    /// Generic parameters are displayed using their declared names from the
-    /// current [`GenericsCx`] scope when available, falling back to `T{i}`.
-    /// Unresolved inference variables are shown as `_`.
-    pub fn pretty(&self, ty: &Ty) -> String {
-        match ty {
-            Ty::Int => "Int".to_string(),
-            Ty::Float => "Float".to_string(),
-            Ty::Bool => "Bool".to_string(),
-            Ty::String => "String".to_string(),
-            Ty::Unit => "()".to_string(),
-            Ty::Var(_) => "_".to_string(),
-            Ty::Generic(id) => self
-                .generics
-                .name_of(*id)
-                .unwrap_or_else(|| format!("T{id}")),
-            Ty::Adt(id, args) => {
-                let name = self.tcx.adt(*id).name().to_string();
-                if args.is_empty() {
-                    name
-                } else {
-                    let args = args
-                        .iter()
-                        .map(|a| self.pretty(a))
-                        .collect::<Vec<_>>()
-                        .join(", ");
-                    format!("{name}<{args}>")
-                }
-            }
-            Ty::FnDef(id, args) => {
-                let def = self.tcx._fn(*id);
-                let params = def
-                    .params
-                    .iter()
-                    .map(|p| self.pretty(&self.instantiate(p.clone(), args)))
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                let ret = self.pretty(&def.ret);
-                format!("fn({params}) -> {ret}")
-            }
-            Ty::FnRef(sig) => {
-                let params = sig
-                    .params
-                    .iter()
-                    .map(|p| self.pretty(p))
-                    .collect::<Vec<_>>()
-                    .join(", ");
-                let ret = self.pretty(&sig.ret);
-                format!("fn({params}) -> {ret}")
-            }
-            Ty::Meta(meta) => match meta {
-                tir::ty::TyMeta::Module(_) => "Meta(Module)".to_string(),
-                tir::ty::TyMeta::Adt(_) => "Meta(Adt)".to_string(),
-                tir::ty::TyMeta::Variant(_, _) => "Meta(Variant)".to_string(),
-            },
-            Ty::Error => "Error".to_string(),
-        }
+    /// Creates a [`TyPrinter`] for the types of one diagnostic,
+    /// so inference variables and ambiguous ADTs are named consistently.
+    pub fn printer(&self, tys: &[Ty]) -> TyPrinter<'_, 'tcx> {
+        TyPrinter::new(self, tys)
+    }
+
+    /// Returns a human-readable string representation of a single `ty`.
+    pub fn pretty(&self, ty: Ty) -> String {
+        self.printer(&[ty]).print(ty)
+    }
+
+    /// Describes a single `ty` for the diagnostic message,
+    /// see [`TyPrinter::describe`].
+    pub fn describe(&self, ty: Ty) -> String {
+        self.printer(&[ty]).describe(ty)
     }
 
     /// Returns `true` if `ty` is a numeric type (`Int` or `Float`).
-    pub fn is_numeric_ty(&self, ty: &Ty) -> bool {
-        match ty {
-            Ty::Int | Ty::Float => true,
-            _ => false,
-        }
+    pub fn is_numeric_ty(&self, ty: Ty) -> bool {
+        matches!(self.tcx.kind(ty), TyKind::Int | TyKind::Float)
     }
 
     /// Returns `true` if `ty` is exactly `Int`.
-    pub fn is_int_ty(&self, ty: &Ty) -> bool {
-        match ty {
-            Ty::Int => true,
-            _ => false,
-        }
+    pub fn is_int_ty(&self, ty: Ty) -> bool {
+        matches!(self.tcx.kind(ty), TyKind::Int)
+    }
+
+    /// Returns `true` if `ty` is an error placeholder. Such types are
+    /// compatible with anything and never produce diagnostics.
+    pub fn is_error_ty(&self, ty: Ty) -> bool {
+        matches!(self.tcx.kind(self.shallow_resolve(ty)), TyKind::Error)
     }
 
     /// Returns `true` if `ty` is exactly `Bool`.
-    pub fn is_bool_ty(&self, ty: &Ty) -> bool {
-        matches!(ty, Ty::Bool)
+    pub fn is_bool_ty(&self, ty: Ty) -> bool {
+        matches!(self.tcx.kind(ty), TyKind::Bool)
     }
 }
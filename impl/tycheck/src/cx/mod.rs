@@ -1,3 +1,5 @@
 pub mod tcx;
 pub mod icx;
-pub mod generics;
\ No newline at end of file
+pub mod generics;
+pub mod vars;
+pub mod printer;
@@ -0,0 +1,212 @@
+/// Imports
+use crate::cx::icx::InferCx;
+use common::symbol::Symbol;
+use id_arena::Id;
+use std::collections::{HashMap, HashSet};
+use tir::{
+    def::AdtDef,
+    ty::{Ty, TyKind, TyMeta, TyVar},
+};
+
+/// Type printer, that renders all types of a single diagnostic
+/// consistently.
+///
+/// - **Inference variables** - unresolved variables get stable names
+///   `?T1`, `?T2`, ... in order of their first appearance, so the same
+///   variable has the same name in every type printed by one printer.
+///
+/// - **Qualified paths** - ADTs sharing a name, but defined in different
+///   modules, are qualified with their module: `geo.House`.
+///
+/// - **Meta types** - are described rather than printed: type `House`,
+///   module `geo`, variant `Shape.Circle`.
+///
+/// Generic parameters are displayed using their declared names from the
+/// current generics scope when available, falling back to `T{i}`. Params
+/// of the let-bound schemes are displayed as `'a`, `'b`, ...
+///
+pub struct TyPrinter<'p, 'tcx> {
+    /// Inference context reference
+    pub(crate) icx: &'p InferCx<'tcx>,
+
+    /// Names of printed inference variables
+    vars: HashMap<Id<TyVar>, usize>,
+
+    /// ADT names, that need qualification
+    ambiguous: HashSet<Symbol>,
+}
+
+/// Implementation
+impl<'p, 'tcx> TyPrinter<'p, 'tcx> {
+    /// Creates printer for the types of one diagnostic.
+    /// All of them are scanned upfront to find ambiguous names
+    pub fn new(icx: &'p InferCx<'tcx>, tys: &[Ty]) -> Self {
+        let mut adts = Vec::new();
+        tys.iter()
+            .for_each(|ty| Self::collect_adts(icx, *ty, &mut adts));
+
+        // Names shared by the different ADTs
+        let mut names: HashMap<Symbol, Id<AdtDef>> = HashMap::new();
+        let mut ambiguous = HashSet::new();
+        for id in adts {
+            let name = icx.tcx.adt(id).name();
+            match names.get(&name) {
+                Some(other) if *other != id => {
+                    ambiguous.insert(name);
+                }
+                Some(_) => {}
+                None => {
+                    names.insert(name, id);
+                }
+            }
+        }
+
+        Self {
+            icx,
+            vars: HashMap::new(),
+            ambiguous,
+        }
+    }
+
+    /// Collects ADTs mentioned by `ty`
+    fn collect_adts(icx: &InferCx, ty: Ty, out: &mut Vec<Id<AdtDef>>) {
+        let ty = icx.shallow_resolve(ty);
+        match icx.tcx.kind(ty) {
+            TyKind::Adt(id, args) => {
+                out.push(*id);
+                args.iter().for_each(|a| Self::collect_adts(icx, *a, out));
+            }
+            TyKind::FnDef(id, args) => {
+                let def = icx.tcx._fn(*id);
+                def.params
+                    .iter()
+                    .chain([&def.ret])
+                    .chain(args)
+                    .for_each(|t| Self::collect_adts(icx, *t, out));
+            }
+            TyKind::FnRef(sig) => {
+                sig.params
+                    .iter()
+                    .chain([&sig.ret])
+                    .for_each(|t| Self::collect_adts(icx, *t, out));
+            }
+            TyKind::Meta(TyMeta::Adt(id) | TyMeta::Variant(id, _)) => out.push(*id),
+            _ => {}
+        }
+    }
+
+    /// Describes type for the diagnostic message: types are quoted
+    /// (`` `Int` ``), meta types are named (type `House`)
+    pub fn describe(&mut self, ty: Ty) -> String {
+        let ty = self.icx.shallow_resolve(ty);
+        match self.icx.tcx.kind(ty) {
+            TyKind::Meta(meta) => match meta {
+                TyMeta::Module(id) => {
+                    let file = self.icx.tcx._mod(*id).file;
+                    format!("module `{}`", self.icx.tcx.sources.get(file).module_name())
+                }
+                TyMeta::Adt(id) => format!("type `{}`", self.adt_name(*id)),
+                TyMeta::Variant(id, variant) => {
+                    format!("variant `{}.{variant}`", self.adt_name(*id))
+                }
+            },
+            _ => format!("`{}`", self.print(ty)),
+        }
+    }
+
+    /// Prints type
+    pub fn print(&mut self, ty: Ty) -> String {
+        self.print_with(ty, &[])
+    }
+
+    /// Prints type, displaying generic
+    /// parameters present in `args` as their arguments
+    fn print_with(&mut self, ty: Ty, args: &[Ty]) -> String {
+        let ty = self.icx.shallow_resolve(ty);
+        match self.icx.tcx.kind(ty) {
+            TyKind::Int => "Int".to_string(),
+            TyKind::Float => "Float".to_string(),
+            TyKind::Bool => "Bool".to_string(),
+            TyKind::String => "String".to_string(),
+            TyKind::Unit => "()".to_string(),
+            TyKind::Var(id) => {
+                let root = self.icx.root(*id);
+                let next = self.vars.len() + 1;
+                format!("?T{}", self.vars.entry(root).or_insert(next))
+            }
+            TyKind::Generic(id) => match args.get(*id) {
+                Some(arg) => self.print(*arg),
+                None => self
+                    .icx
+                    .generics
+                    .name_of(*id)
+                    .map_or_else(|| format!("T{id}"), |name| name.to_string()),
+            },
+            TyKind::Param(id) => format!("'{}", Self::param_name(*id)),
+            TyKind::Adt(id, adt_args) => {
+                let name = self.adt_name(*id);
+                if adt_args.is_empty() {
+                    name
+                } else {
+                    format!("{name}<{}>", self.list(adt_args, args))
+                }
+            }
+            TyKind::FnDef(id, fn_args) => {
+                let def = self.icx.tcx._fn(*id);
+                let fn_args = fn_args
+                    .iter()
+                    .map(|a| match self.icx.tcx.kind(*a) {
+                        TyKind::Generic(i) => args.get(*i).copied().unwrap_or(*a),
+                        _ => *a,
+                    })
+                    .collect::<Vec<_>>();
+                let generics = if fn_args.is_empty() {
+                    String::new()
+                } else {
+                    format!("<{}>", self.list(&fn_args, &[]))
+                };
+                let params = self.list(&def.params, &fn_args);
+                let ret = self.print_with(def.ret, &fn_args);
+                format!("fn {}{generics}({params}) -> {ret}", def.name)
+            }
+            TyKind::FnRef(sig) => {
+                let params = self.list(&sig.params, args);
+                let ret = self.print_with(sig.ret, args);
+                format!("fn({params}) -> {ret}")
+            }
+            TyKind::Meta(_) => self.describe(ty),
+            TyKind::Error => "Error".to_string(),
+        }
+    }
+
+    /// Returns name of the let-bound scheme param: `a`, ..., `z`, `a1`, ...
+    fn param_name(id: usize) -> String {
+        let letter = (b'a' + (id % 26) as u8) as char;
+        match id / 26 {
+            0 => letter.to_string(),
+            n => format!("{letter}{n}"),
+        }
+    }
+
+    /// Prints comma separated types
+    fn list(&mut self, tys: &[Ty], args: &[Ty]) -> String {
+        tys.iter()
+            .map(|t| self.print_with(*t, args))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    /// Returns ADT name, qualified with its module if ambiguous
+    fn adt_name(&self, id: Id<AdtDef>) -> String {
+        let def = self.icx.tcx.adt(id);
+        let name = def.name();
+        if self.ambiguous.contains(&name) {
+            format!(
+                "{}.{name}",
+                self.icx.tcx.sources.get(def.file()).module_name()
+            )
+        } else {
+            name.to_string()
+        }
+    }
+}
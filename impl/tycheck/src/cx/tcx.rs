@@ -1,7 +1,113 @@
+use common::{
+    source::SourceMap,
+    symbol::{Symbol, sym},
+    token::Span,
+};
 /// Imports
 use id_arena::{Arena, Id};
 use macros::bug;
-use tir::ty::{AdtDef, FnDef, ModDef};
+use miette::NamedSource;
+use std::{collections::HashMap, sync::Arc};
+use tir::{
+    def::{AdtDef, FnDef, ModDef},
+    results::TypeckResults,
+    ty::{FnSig, GenericArgs, Ty, TyKind, TyMeta, TyVar},
+};
+
+/// Flags of the interned type, computed once on interning
+#[derive(Debug, Default, Clone, Copy)]
+pub struct TyFlags {
+    /// Type mentions inference variables
+    pub has_vars: bool,
+
+    /// Type mentions generic parameters
+    pub has_generics: bool,
+}
+
+/// Implementation
+impl TyFlags {
+    /// Unites flags of two types
+    fn union(self, other: TyFlags) -> TyFlags {
+        TyFlags {
+            has_vars: self.has_vars || other.has_vars,
+            has_generics: self.has_generics || other.has_generics,
+        }
+    }
+}
+
+/// Frequently used types, interned upfront
+#[derive(Debug, Clone, Copy)]
+pub struct CommonTys {
+    pub int: Ty,
+    pub float: Ty,
+    pub string: Ty,
+    pub bool: Ty,
+    pub unit: Ty,
+    pub error: Ty,
+}
+
+/// Implementation
+impl CommonTys {
+    /// Returns primitive types with their names
+    pub fn primitives(&self) -> [(Symbol, Ty); 4] {
+        [
+            (sym::INT, self.int),
+            (sym::FLOAT, self.float),
+            (sym::STRING, self.string),
+            (sym::BOOL, self.bool),
+        ]
+    }
+}
+
+/// Hash-consing interner of type kinds
+#[derive(Default)]
+struct Interner {
+    /// Storage for all interned type kinds
+    kinds: Arena<TyKind>,
+
+    /// Interned types by their kinds
+    interned: HashMap<TyKind, Ty>,
+
+    /// Flags of every interned type
+    flags: Vec<TyFlags>,
+}
+
+/// Implementation
+impl Interner {
+    /// Interns type kind, computing its flags
+    fn intern(&mut self, kind: TyKind) -> Ty {
+        if let Some(ty) = self.interned.get(&kind) {
+            return *ty;
+        }
+        let flags = match &kind {
+            TyKind::Adt(_, args) | TyKind::FnDef(_, args) => args
+                .iter()
+                .fold(TyFlags::default(), |acc, arg| acc.union(self.flags(*arg))),
+            TyKind::FnRef(sig) => sig
+                .params
+                .iter()
+                .fold(self.flags(sig.ret), |acc, p| acc.union(self.flags(*p))),
+            TyKind::Generic(_) => TyFlags {
+                has_generics: true,
+                ..TyFlags::default()
+            },
+            TyKind::Var(_) => TyFlags {
+                has_vars: true,
+                ..TyFlags::default()
+            },
+            _ => TyFlags::default(),
+        };
+        let ty = Ty::new(self.kinds.alloc(kind.clone()));
+        self.interned.insert(kind, ty);
+        self.flags.push(flags);
+        ty
+    }
+
+    /// Returns flags of the interned type
+    fn flags(&self, ty: Ty) -> TyFlags {
+        self.flags[ty.id().index()]
+    }
+}
 
 /// Context for all type-level definitions used across compilation.
 ///
@@ -9,10 +115,14 @@ use tir::ty::{AdtDef, FnDef, ModDef};
 /// definitions, and module definitions. Every definition is heap-allocated
 /// inside its arena and identified by a typed `Id<T>`.
 ///
+/// It also interns types: every [`TyKind`] is stored once and referred to
+/// by a `Copy` [`Ty`] handle, so equal types share a handle and comparing
+/// them is comparing integers. Frequently used types are available
+/// through [`TyCx::types`].
+///
 /// The context is expected to be created once and kept alive for the full
 /// duration of type-checking and all subsequent compilation phases.
 ///
-#[derive(Default)]
 pub struct TyCx {
     /// Storage for all algebraic data type definitions (structs and enums).
     pub adt: Arena<AdtDef>,
@@ -22,10 +132,105 @@ pub struct TyCx {
 
     /// Storage for all module definitions.
     pub modules: Arena<ModDef>,
+
+    /// Source files of the compilation, referenced by spans.
+    pub sources: SourceMap,
+
+    /// Type interner.
+    interner: Interner,
+
+    /// Frequently used types.
+    pub types: CommonTys,
+
+    /// Results of the type checking, keyed by node ids.
+    pub results: TypeckResults,
+}
+
+/// Default implementation
+impl Default for TyCx {
+    fn default() -> Self {
+        let mut interner = Interner::default();
+        let types = CommonTys {
+            int: interner.intern(TyKind::Int),
+            float: interner.intern(TyKind::Float),
+            string: interner.intern(TyKind::String),
+            bool: interner.intern(TyKind::Bool),
+            unit: interner.intern(TyKind::Unit),
+            error: interner.intern(TyKind::Error),
+        };
+        Self {
+            adt: Arena::new(),
+            functions: Arena::new(),
+            modules: Arena::new(),
+            sources: SourceMap::default(),
+            interner,
+            types,
+            results: TypeckResults::default(),
+        }
+    }
 }
 
 /// Implementation
 impl TyCx {
+    /// Returns named source of the span file, used by diagnostics
+    pub fn source(&self, span: &Span) -> Arc<NamedSource<String>> {
+        self.sources.source(span.0)
+    }
+
+    /// Interns type kind and returns its handle. Equal
+    /// kinds are interned once and share the handle.
+    pub fn intern(&mut self, kind: TyKind) -> Ty {
+        self.interner.intern(kind)
+    }
+
+    /// Returns kind of the interned type
+    pub fn kind(&self, ty: Ty) -> &TyKind {
+        self.interner
+            .kinds
+            .get(ty.id())
+            .unwrap_or_else(|| bug!("type {:?} not found", ty.id()))
+    }
+
+    /// Returns flags of the interned type
+    pub fn flags(&self, ty: Ty) -> TyFlags {
+        self.interner.flags(ty)
+    }
+
+    /// Interns ADT type
+    pub fn mk_adt(&mut self, id: Id<AdtDef>, args: GenericArgs) -> Ty {
+        self.intern(TyKind::Adt(id, args))
+    }
+
+    /// Interns function definition type
+    pub fn mk_fn_def(&mut self, id: Id<FnDef>, args: GenericArgs) -> Ty {
+        self.intern(TyKind::FnDef(id, args))
+    }
+
+    /// Interns function pointer type
+    pub fn mk_fn_ref(&mut self, params: Vec<Ty>, ret: Ty) -> Ty {
+        self.intern(TyKind::FnRef(FnSig { params, ret }))
+    }
+
+    /// Interns generic parameter type
+    pub fn mk_generic(&mut self, idx: usize) -> Ty {
+        self.intern(TyKind::Generic(idx))
+    }
+
+    /// Interns let-bound scheme parameter type
+    pub fn mk_param(&mut self, idx: usize) -> Ty {
+        self.intern(TyKind::Param(idx))
+    }
+
+    /// Interns type variable type
+    pub fn mk_var(&mut self, id: Id<TyVar>) -> Ty {
+        self.intern(TyKind::Var(id))
+    }
+
+    /// Interns meta type
+    pub fn mk_meta(&mut self, meta: TyMeta) -> Ty {
+        self.intern(TyKind::Meta(meta))
+    }
+
     /// Inserts an ADT definition into the arena and returns its fresh ID.
     pub fn insert_adt(&mut self, adt: AdtDef) -> Id<AdtDef> {
         self.adt.alloc(adt)
@@ -50,7 +255,7 @@ impl TyCx {
     pub fn adt(&self, id: Id<AdtDef>) -> &AdtDef {
         self.adt
             .get(id)
-            .unwrap_or_else(|| bug!("adt not found by id."))
+            .unwrap_or_else(|| bug!("adt {id:?} not found"))
     }
 
     /// Returns a reference to the function definition with the given ID.
@@ -62,7 +267,7 @@ impl TyCx {
     pub fn _fn(&self, id: Id<FnDef>) -> &FnDef {
         self.functions
             .get(id)
-            .unwrap_or_else(|| bug!("fn not found by id."))
+            .unwrap_or_else(|| bug!("fn {id:?} not found"))
     }
 
     /// Returns a reference to the module definition with the given ID.
@@ -74,7 +279,7 @@ impl TyCx {
     pub fn _mod(&self, id: Id<ModDef>) -> &ModDef {
         self.modules
             .get(id)
-            .unwrap_or_else(|| bug!("module not found by id."))
+            .unwrap_or_else(|| bug!("module {id:?} not found"))
     }
 
     /// Returns a mutable reference to the ADT definition with the given ID.
@@ -86,7 +291,7 @@ impl TyCx {
     pub fn adt_mut(&mut self, id: Id<AdtDef>) -> &mut AdtDef {
         self.adt
             .get_mut(id)
-            .unwrap_or_else(|| bug!("adt not found by id."))
+            .unwrap_or_else(|| bug!("adt {id:?} not found"))
     }
 
     /// Returns a mutable reference to the function definition with the given ID.
@@ -98,7 +303,7 @@ impl TyCx {
     pub fn fn_mut(&mut self, id: Id<FnDef>) -> &mut FnDef {
         self.functions
             .get_mut(id)
-            .unwrap_or_else(|| bug!("fn not found by id."))
+            .unwrap_or_else(|| bug!("fn {id:?} not found"))
     }
 
     /// Returns a mutable reference to the module definition with the given ID.
@@ -110,6 +315,6 @@ impl TyCx {
     pub fn mod_mut(&mut self, id: Id<ModDef>) -> &mut ModDef {
         self.modules
             .get_mut(id)
-            .unwrap_or_else(|| bug!("module not found by id."))
+            .unwrap_or_else(|| bug!("module {id:?} not found"))
     }
 }
@@ -0,0 +1,197 @@
+/// Imports
+use id_arena::{Arena, Id};
+use std::cell::{Cell, RefCell};
+use tir::ty::TyVar;
+
+/// Undo log entry, records the previous state of a changed variable
+enum Undo {
+    /// Parent of the variable changed
+    Parent(Id<TyVar>, Id<TyVar>),
+
+    /// Rank of the root changed
+    Rank(Id<TyVar>, u32),
+
+    /// Value of the root changed
+    Value(Id<TyVar>, TyVar),
+}
+
+/// Snapshot of the [`VarStore`], used to roll back speculative
+/// unification. Must be either rolled back or committed.
+#[must_use]
+pub struct Snapshot {
+    /// Undo log length at the moment of snapshot
+    undo_len: usize,
+}
+
+/// Type variables store: a union-find over inference variables.
+///
+/// Every variable belongs to an equivalence class, represented by its
+/// root. Only roots carry meaningful values: `TyVar::Unbound(level)` for
+/// unsolved classes and `TyVar::Bound(ty)` for solved ones. Unifying two
+/// unbound variables merges their classes instead of binding one to the
+/// other, so chains of variables never appear and lookups stay almost
+/// constant.
+///
+/// - **Union by rank** - the root of the shallower tree is attached to the
+///   root of the deeper one, keeping trees logarithmic.
+///
+/// - **Path compression** - [`find`] re-attaches visited variables directly
+///   to the root. Inside a snapshot re-attached parents are recorded in the
+///   undo log, since compressed paths could outlive an undone union.
+///
+/// - **Snapshots** - every change made after [`snapshot`] is recorded in the
+///   undo log and can be reverted with [`rollback_to`]. Variables created
+///   inside a snapshot stay allocated, but nothing refers to them after
+///   rollback.
+///
+/// [`find`]: VarStore::find
+/// [`snapshot`]: VarStore::snapshot
+/// [`rollback_to`]: VarStore::rollback_to
+///
+#[derive(Default)]
+pub struct VarStore {
+    /// Variable values, meaningful only for roots
+    values: Arena<TyVar>,
+
+    /// Parent of every variable, roots are their own parents
+    parents: Vec<Cell<Id<TyVar>>>,
+
+    /// Rank of every root
+    ranks: Vec<u32>,
+
+    /// Undo log of open snapshots, appended by `find` too
+    undo: RefCell<Vec<Undo>>,
+
+    /// Amount of open snapshots
+    snapshots: usize,
+}
+
+/// Implementation
+impl VarStore {
+    /// Allocates a new variable in its own class
+    pub fn new_var(&mut self, value: TyVar) -> Id<TyVar> {
+        let id = self.values.alloc(value);
+        self.parents.push(Cell::new(id));
+        self.ranks.push(0);
+        id
+    }
+
+    /// Returns the root of the `id` class
+    pub fn find(&self, id: Id<TyVar>) -> Id<TyVar> {
+        // Searching for the root
+        let mut root = id;
+        loop {
+            let parent = self.parents[root.index()].get();
+            if parent == root {
+                break;
+            }
+            root = parent;
+        }
+
+        // Compressing the path
+        let mut current = id;
+        while current != root {
+            let parent = self.parents[current.index()].replace(root);
+            if parent != root && self.snapshots > 0 {
+                self.undo.borrow_mut().push(Undo::Parent(current, parent));
+            }
+            current = parent;
+        }
+        root
+    }
+
+    /// Returns value of the `id` class
+    pub fn value(&self, id: Id<TyVar>) -> &TyVar {
+        self.values.get(self.find(id)).expect("invalid TyVar id")
+    }
+
+    /// Sets value of the `id` class
+    pub fn set_value(&mut self, id: Id<TyVar>, value: TyVar) {
+        let root = self.find(id);
+        let slot = self.values.get_mut(root).expect("invalid TyVar id");
+        let old = std::mem::replace(slot, value);
+        if self.snapshots > 0 {
+            self.undo.get_mut().push(Undo::Value(root, old));
+        }
+    }
+
+    /// Sets parent of the root `id`
+    fn set_parent(&mut self, id: Id<TyVar>, parent: Id<TyVar>) {
+        let old = self.parents[id.index()].replace(parent);
+        if self.snapshots > 0 {
+            self.undo.get_mut().push(Undo::Parent(id, old));
+        }
+    }
+
+    /// Sets rank of the root `id`
+    fn set_rank(&mut self, id: Id<TyVar>, rank: u32) {
+        let old = std::mem::replace(&mut self.ranks[id.index()], rank);
+        if self.snapshots > 0 {
+            self.undo.get_mut().push(Undo::Rank(id, old));
+        }
+    }
+
+    /// Merges classes of two unbound variables. The merged class
+    /// keeps the smallest binding level of both.
+    ///
+    /// # Panics
+    /// Panics if any of the classes is already bound.
+    ///
+    pub fn union(&mut self, a: Id<TyVar>, b: Id<TyVar>) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+        let level = match (self.value(a), self.value(b)) {
+            (TyVar::Unbound(a), TyVar::Unbound(b)) => *a.min(b),
+            _ => panic!("union of bound type variables"),
+        };
+
+        // Attaching shallower tree to the deeper one
+        let (rank_a, rank_b) = (self.ranks[a.index()], self.ranks[b.index()]);
+        let root = if rank_a < rank_b {
+            self.set_parent(a, b);
+            b
+        } else {
+            self.set_parent(b, a);
+            if rank_a == rank_b {
+                self.set_rank(a, rank_a + 1);
+            }
+            a
+        };
+        self.set_value(root, TyVar::Unbound(level));
+    }
+
+    /// Opens a snapshot. Changes made after it could be rolled back
+    pub fn snapshot(&mut self) -> Snapshot {
+        self.snapshots += 1;
+        Snapshot {
+            undo_len: self.undo.get_mut().len(),
+        }
+    }
+
+    /// Reverts every change made since `snapshot` and closes it
+    pub fn rollback_to(&mut self, snapshot: Snapshot) {
+        while self.undo.get_mut().len() > snapshot.undo_len {
+            match self.undo.get_mut().pop() {
+                Some(Undo::Parent(id, old)) => self.parents[id.index()].set(old),
+                Some(Undo::Rank(id, old)) => self.ranks[id.index()] = old,
+                Some(Undo::Value(id, old)) => {
+                    *self.values.get_mut(id).expect("invalid TyVar id") = old
+                }
+                None => break,
+            }
+        }
+        self.snapshots -= 1;
+    }
+
+    /// Keeps every change made since `snapshot` and closes it
+    pub fn commit(&mut self, snapshot: Snapshot) {
+        self.snapshots -= 1;
+        // Outermost snapshot is closed, so nothing could be rolled back
+        if self.snapshots == 0 {
+            debug_assert_eq!(snapshot.undo_len, 0);
+            self.undo.get_mut().clear();
+        }
+    }
+}
@@ -1,21 +1,211 @@
 /// Imports
-use crate::check::ModuleTyck;
+use crate::{check::ModuleTyck, errors::TypeckError};
+use ast::{
+    atom::{Publicity, TypeHint},
+    item::{Item, ItemKind, UseKind},
+};
+use common::{symbol::Symbol, token::Span};
+use tir::def::{AdtDef, EnumDef, FieldDef, FnDef, ItemDef, ItemDefKind, StructDef, VariantDef};
 
 /// Implementation
 impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
-    /// Performs imports 
-    fn early_define_item(&mut self, item: &ast::item::Item) {
-        match item.kind {
-            ast::item::ItemKind::Struct(_) => todo!(),
-            ast::item::ItemKind::Enum(_) => todo!(),
+    /// Defines top-level item in the resolver, reports diagnostic
+    /// if item with the same name is already defined
+    fn early_define(&mut self, span: &Span, item: &Item, name: Symbol, kind: ItemDefKind) {
+        let def = ItemDef {
+            publicity: item.publicity.clone(),
+            kind,
+        };
+        if !self.resolver.define_item(name, span, def) {
+            self.early_already_defined(span, name);
+        }
+    }
+
+    /// Reports already defined item or import
+    fn early_already_defined(&mut self, span: &Span, name: Symbol) {
+        self.diagnostics.push(TypeckError::AlreadyDefined {
+            src: self.icx.tcx.source(span),
+            span: span.1.clone().into(),
+            binding: name.to_string(),
+        });
+    }
+
+    /// Imports module or its public items by the `use` item. Module is
+    /// looked up among the already known ones by the last path segment,
+    /// unknown modules and items are left unresolved
+    fn early_import(&mut self, item: &Item) {
+        let ItemKind::Use(u) = &item.kind else {
+            return;
+        };
+        let name = u.path.rsplit('/').next().unwrap_or(&u.path);
+        let Some(id) = self
+            .icx
+            .tcx
+            .modules
+            .iter()
+            .find(|(_, m)| self.icx.tcx.sources.get(m.file).module_name() == name)
+            .map(|(id, _)| id)
+        else {
+            return;
+        };
+
+        match &u.kind {
+            UseKind::Just | UseKind::As(_) => {
+                let alias = match &u.kind {
+                    UseKind::As(alias) => *alias,
+                    _ => Symbol::intern(name),
+                };
+                if !self.resolver.define_mod(alias, &item.span, id) {
+                    self.early_already_defined(&item.span, alias);
+                }
+            }
+            UseKind::For(names) => {
+                for &name in names {
+                    let Some(def) = self.icx.tcx._mod(id).defs.get(&name).cloned() else {
+                        continue;
+                    };
+                    if def.publicity == Publicity::Pub
+                        && !self.resolver.import_item(name, &item.span, def)
+                    {
+                        self.early_already_defined(&item.span, name);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Declares ADT item without fields and variants,
+    /// so they could reference each other
+    fn early_declare_adt(&mut self, item: &Item) {
+        match &item.kind {
+            ItemKind::Struct(s) => {
+                let id = self.icx.tcx.insert_adt(AdtDef::Struct(StructDef {
+                    docs: item.docs.clone(),
+                    span: item.span.clone(),
+                    name: s.name,
+                    generics: s.generics.clone(),
+                    fields: Vec::new(),
+                }));
+                self.early_define(&item.span, item, s.name, ItemDefKind::Adt(id));
+            }
+            ItemKind::Enum(e) => {
+                let id = self.icx.tcx.insert_adt(AdtDef::Enum(EnumDef {
+                    docs: item.docs.clone(),
+                    span: item.span.clone(),
+                    name: e.name,
+                    generics: e.generics.clone(),
+                    variants: Vec::new(),
+                }));
+                self.early_define(&item.span, item, e.name, ItemDefKind::Adt(id));
+            }
             _ => {}
         }
     }
 
-    /// Early analysis phase: performs imports
+    /// Defines fields and variants of previously declared ADT item
+    fn early_define_adt(&mut self, item: &Item) {
+        match &item.kind {
+            ItemKind::Struct(s) => {
+                let Some(id) = self.resolver.lookup_adt(s.name) else {
+                    return;
+                };
+                self.icx.generics.push(s.generics.clone());
+                let fields = s
+                    .fields
+                    .iter()
+                    .map(|f| FieldDef {
+                        docs: f.docs.clone(),
+                        span: f.span.clone(),
+                        name: f.name,
+                        ty: self.infer_type_hint(f.hint.clone()),
+                    })
+                    .collect();
+                self.icx.generics.pop();
+
+                if let AdtDef::Struct(def) = self.icx.tcx.adt_mut(id) {
+                    def.fields = fields;
+                }
+            }
+            ItemKind::Enum(e) => {
+                let Some(id) = self.resolver.lookup_adt(e.name) else {
+                    return;
+                };
+                self.icx.generics.push(e.generics.clone());
+                let variants = e
+                    .variants
+                    .iter()
+                    .map(|v| VariantDef {
+                        docs: v.docs.clone(),
+                        span: v.span.clone(),
+                        name: v.name,
+                        fields: v
+                            .params
+                            .iter()
+                            .map(|p| self.infer_type_hint(p.clone()))
+                            .collect(),
+                    })
+                    .collect();
+                self.icx.generics.pop();
+
+                if let AdtDef::Enum(def) = self.icx.tcx.adt_mut(id) {
+                    def.variants = variants;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Declares function signature. Missing return type becomes
+    /// an inference variable one level deeper than the module level,
+    /// so it could be generalized after the body is checked
+    fn early_declare_fn(&mut self, item: &Item) {
+        if let ItemKind::Function(f) = &item.kind {
+            self.icx.generics.push(f.generics.clone());
+            self.icx.enter_level();
+            let params = f
+                .params
+                .iter()
+                .map(|p| self.infer_type_hint(p.hint.clone()))
+                .collect();
+            let ret = match &f.ret {
+                TypeHint::Infer => self.icx.fresh_ty(),
+                hint => self.infer_type_hint(hint.clone()),
+            };
+            self.icx.exit_level();
+            self.icx.generics.pop();
+
+            let id = self.icx.tcx.insert_fn(FnDef {
+                docs: item.docs.clone(),
+                span: item.span.clone(),
+                name: f.name,
+                generics: f.generics.clone(),
+                params,
+                param_spans: f.params.iter().map(|p| p.span.clone()).collect(),
+                ret,
+            });
+            self.early_define(&item.span, item, f.name, ItemDefKind::Fn(id));
+        }
+    }
+
+    /// Early analysis phase: resolves imports,
+    /// declares types and function signatures
     pub fn early_phase(&mut self, m: &ast::item::Module) {
         for item in &m.items {
-            self.early_define_item(item);
+            self.set_ice_item(item);
+            self.early_import(item);
+        }
+        for item in &m.items {
+            self.set_ice_item(item);
+            self.early_declare_adt(item);
+        }
+        for item in &m.items {
+            self.set_ice_item(item);
+            self.early_define_adt(item);
+        }
+        for item in &m.items {
+            self.set_ice_item(item);
+            self.early_declare_fn(item);
         }
+        macros::ice::clear_item();
     }
 }
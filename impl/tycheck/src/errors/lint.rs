@@ -0,0 +1,101 @@
+/// Imports
+use crate::lint::{Level, LevelSource, Lint};
+use miette::{Diagnostic, LabeledSpan, NamedSource, Severity, SourceCode, SourceSpan};
+use std::{fmt::Display, sync::Arc};
+use thiserror::Error;
+
+/// Reported lint. Its severity depends on the resolved level,
+/// so `Diagnostic` is implemented manually
+#[derive(Error, Debug)]
+#[error("{message}")]
+pub struct LintDiagnostic {
+    /// Reported lint
+    pub lint: &'static Lint,
+
+    /// Resolved level, either `warn` or `deny`
+    pub level: Level,
+
+    /// Where the level came from
+    pub origin: LevelSource,
+
+    /// Source code
+    pub src: Arc<NamedSource<String>>,
+
+    /// Primary span
+    pub span: SourceSpan,
+
+    /// Primary span label
+    pub label: String,
+
+    /// Diagnostic message
+    pub message: String,
+
+    /// Lint specific help
+    pub help: Option<String>,
+}
+
+/// Implementation
+impl LintDiagnostic {
+    /// Explains where the level came from,
+    /// if it is not labeled in the source
+    fn note(&self) -> Option<String> {
+        let (level, name) = (self.level.name(), self.lint.name);
+        match &self.origin {
+            LevelSource::Default => Some(format!("`#[{level}({name})]` on by default")),
+            LevelSource::CommandLine(group) if group == name => Some(format!(
+                "`{} {name}` requested on the command line",
+                self.level.flag()
+            )),
+            LevelSource::CommandLine(group) => Some(format!(
+                "`{} {name}` implied by `{} {group}`",
+                self.level.flag(),
+                self.level.flag()
+            )),
+            LevelSource::Attribute(..) => None,
+        }
+    }
+}
+
+/// Diagnostic implementation
+impl Diagnostic for LintDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        Some(Box::new(self.lint.code))
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        match self.level {
+            Level::Deny => Some(Severity::Error),
+            _ => Some(Severity::Warning),
+        }
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn Display + 'a>> {
+        let help = [self.help.clone(), self.note()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        if help.is_empty() {
+            None
+        } else {
+            Some(Box::new(help.join("\n")))
+        }
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&*self.src)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        let mut labels = vec![LabeledSpan::new_with_span(
+            Some(self.label.clone()),
+            self.span,
+        )];
+        if let LevelSource::Attribute(_, span) = &self.origin {
+            labels.push(LabeledSpan::new_with_span(
+                Some("the lint level is defined here".to_string()),
+                SourceSpan::from(span.1.clone()),
+            ));
+        }
+        Some(Box::new(labels.into_iter()))
+    }
+}
@@ -1,8 +1,9 @@
 /// Modules
+pub mod lint;
 pub mod ty;
 
 /// Imports
-use crate::cx::icx::InferCx;
+use crate::{cx::printer::TyPrinter, errors::lint::LintDiagnostic};
 use ast::expr::{BinOp, UnOp};
 use common::token::Span;
 use miette::{Diagnostic, NamedSource, SourceSpan};
@@ -13,8 +14,8 @@ use thiserror::Error;
 #[derive(Error, Diagnostic, Debug)]
 pub enum TypeckError {
     /// Invalid unary operation
-    #[error("invalid unary op `{op:?}` on expr with ty `{ty}`")]
-    #[diagnostic(code(typeck::invalid_unary_op))]
+    #[error("invalid unary op `{op:?}` on expr with ty {ty}")]
+    #[diagnostic(code(E0301))]
     InvalidUnaryOp {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -24,9 +25,9 @@ pub enum TypeckError {
         op: UnOp,
     },
 
-    /// Invalid unary operation
-    #[error("invalid unary op `{op:?}` on expr-s with ty-s `{t1}` and `{t2}`")]
-    #[diagnostic(code(typeck::invalid_bin_op))]
+    /// Invalid binary operation
+    #[error("invalid binary op `{op:?}` on expr-s with ty-s {t1} and {t2}")]
+    #[diagnostic(code(E0302))]
     InvalidBinOp {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -38,8 +39,8 @@ pub enum TypeckError {
     },
 
     /// Type mismatch
-    #[error("type mismatch: expected `{t1}`, found `{t2}`")]
-    #[diagnostic(code(typeck::type_mismatch))]
+    #[error("type mismatch: expected {t1}, found {t2}")]
+    #[diagnostic(code(E0303))]
     TypeMismatch {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -47,11 +48,16 @@ pub enum TypeckError {
         span: SourceSpan,
         t1: String,
         t2: String,
+        #[help]
+        reason: Option<String>,
+        #[label("{note}")]
+        origin: Option<SourceSpan>,
+        note: String,
     },
 
     /// Rigid type mismatch
-    #[error("can't unify generic type `{ty}` with a concrete type")]
-    #[diagnostic(code(typeck::rigid_mismatch))]
+    #[error("can't unify generic type {ty} with a concrete type")]
+    #[diagnostic(code(E0304))]
     RigidMismatch {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -62,7 +68,7 @@ pub enum TypeckError {
 
     /// Infinite type
     #[error("infinite type detected")]
-    #[diagnostic(code(typeck::infinite_type))]
+    #[diagnostic(code(E0305))]
     InfiniteType {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -72,36 +78,46 @@ pub enum TypeckError {
 
     /// Unresolved name
     #[error("can't find value with name `{name}`")]
+    #[diagnostic(code(E0306))]
     UnresolvedName {
         #[source_code]
         src: Arc<NamedSource<String>>,
         #[label("this name is not found in this scope")]
         span: SourceSpan,
         name: String,
+        #[help]
+        help: Option<String>,
     },
 
     /// Unresolved type
     #[error("can't find type with name `{name}`")]
+    #[diagnostic(code(E0307))]
     UnresolvedType {
         #[source_code]
         src: Arc<NamedSource<String>>,
         #[label("this type is not found")]
         span: SourceSpan,
         name: String,
+        #[help]
+        help: Option<String>,
     },
 
     /// Unresolved field
     #[error("can't find field with name `{name}`")]
+    #[diagnostic(code(E0308))]
     UnresolvedField {
         #[source_code]
         src: Arc<NamedSource<String>>,
         #[label("this field is not found")]
         span: SourceSpan,
         name: String,
+        #[help]
+        help: Option<String>,
     },
 
     /// Can not call
-    #[error("can't call value `{ty}`")]
+    #[error("can't call value {ty}")]
+    #[diagnostic(code(E0309))]
     CanNotCall {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -112,6 +128,7 @@ pub enum TypeckError {
 
     /// Arity missmatch
     #[error("arity missmatch. expected `{expected}`, got `{got}`")]
+    #[diagnostic(code(E0310))]
     ArityMissmatch {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -121,8 +138,33 @@ pub enum TypeckError {
         got: usize,
     },
 
+    /// Return type annotation needed
+    #[error("can't infer return type of `{name}` before its body is checked")]
+    #[diagnostic(code(E0311), help("{help}"))]
+    ReturnTypeNeeded {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("type of this value depends on the return type of `{name}`")]
+        span: SourceSpan,
+        name: String,
+        help: String,
+    },
+
+    /// Type annotations needed
+    #[error("type annotations needed")]
+    #[diagnostic(code(E0312), help("{help}"))]
+    AnnotationsNeeded {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("{what}")]
+        span: SourceSpan,
+        what: String,
+        help: String,
+    },
+
     /// Already defined
     #[error("value `{binding}` already defined in this scope")]
+    #[diagnostic(code(E0313))]
     AlreadyDefined {
         #[source_code]
         src: Arc<NamedSource<String>>,
@@ -130,12 +172,45 @@ pub enum TypeckError {
         span: SourceSpan,
         binding: String,
     },
+
+    /// Unknown attribute
+    #[error("unknown attribute `{name}`")]
+    #[diagnostic(
+        code(E0314),
+        help("supported attributes are `allow`, `warn` and `deny`")
+    )]
+    UnknownAttribute {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("this attribute is not supported")]
+        span: SourceSpan,
+        name: String,
+    },
+
+    /// Value can't be interpolated into a string
+    #[error("value of type {ty} can't be printed")]
+    #[diagnostic(
+        code(E0315),
+        help("only `Int`, `Float`, `String` and `Bool` values can be interpolated")
+    )]
+    NotPrintable {
+        #[source_code]
+        src: Arc<NamedSource<String>>,
+        #[label("this value is interpolated into the string")]
+        span: SourceSpan,
+        ty: String,
+    },
+
+    /// Reported lint
+    #[error(transparent)]
+    #[diagnostic(transparent)]
+    Lint(LintDiagnostic),
 }
 
 /// An `IntoDiagnostic` trait, used to convert error
-/// into `TypeckError`. Provides reference to `InferCx`
-/// and span, where error happened
+/// into `TypeckError`. Provides `TyPrinter`, shared by
+/// all types of the diagnostic, and span, where error happened
 ///
 pub trait IntoDiagnostic {
-    fn into_diag(&self, icx: &InferCx, span: Span) -> TypeckError;
+    fn into_diag(self, printer: &mut TyPrinter, span: Span) -> TypeckError;
 }
@@ -1,6 +1,6 @@
 /// Imports
 use crate::{
-    cx::icx::InferCx,
+    cx::printer::TyPrinter,
     errors::{IntoDiagnostic, TypeckError},
 };
 use common::token::Span;
@@ -19,23 +19,38 @@ pub enum TypeError {
     InfiniteType,
 }
 
+/// Implementation
+impl TypeError {
+    /// Returns types mentioned by the error
+    pub fn tys(&self) -> Vec<Ty> {
+        match self {
+            TypeError::Mismatch(t1, t2) => vec![*t1, *t2],
+            TypeError::RigidMismatch(ty) => vec![*ty],
+            TypeError::InfiniteType => Vec::new(),
+        }
+    }
+}
+
 /// Into diagnostic impl
 impl IntoDiagnostic for TypeError {
-    fn into_diag(&self, icx: &InferCx, span: Span) -> TypeckError {
+    fn into_diag(self, printer: &mut TyPrinter, span: Span) -> TypeckError {
         match self {
             TypeError::Mismatch(t1, t2) => TypeckError::TypeMismatch {
-                src: span.0,
+                src: printer.icx.tcx.source(&span),
                 span: span.1.into(),
-                t1: icx.pretty(&t1),
-                t2: icx.pretty(&t2),
+                t1: printer.describe(t1),
+                t2: printer.describe(t2),
+                reason: None,
+                origin: None,
+                note: String::new(),
             },
             TypeError::RigidMismatch(ty) => TypeckError::RigidMismatch {
-                src: span.0,
+                src: printer.icx.tcx.source(&span),
                 span: span.1.into(),
-                ty: icx.pretty(&ty),
+                ty: printer.describe(ty),
             },
             TypeError::InfiniteType => TypeckError::InfiniteType {
-                src: span.0,
+                src: printer.icx.tcx.source(&span),
                 span: span.1.into(),
             },
         }
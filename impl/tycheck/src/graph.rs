@@ -0,0 +1,188 @@
+/// Imports
+use ast::{
+    expr::{Expr, ExprKind},
+    item::Function,
+    stmt::{Block, Stmt, StmtKind},
+    visit::{self, Visitor},
+};
+use common::symbol::Symbol;
+use std::collections::{HashMap, HashSet};
+
+/// Call graph over the functions of a single module.
+///
+/// Nodes are functions in declaration order, an edge `a -> b` means that
+/// body of `a` mentions `b` by name (direct call or passing it as a value).
+/// Names shadowed by params, closure params or `let` bindings are not
+/// treated as references.
+///
+/// The graph is used to order type-checking: functions are checked by
+/// strongly connected components, callees before callers, so return types
+/// of unannotated functions are inferred before they are used, and mutually
+/// recursive functions are inferred together.
+///
+pub struct CallGraph {
+    /// Edges of every node, in declaration order
+    edges: Vec<Vec<usize>>,
+}
+
+/// Implementation
+impl CallGraph {
+    /// Builds call graph over `functions`
+    pub fn build(functions: &[&Function]) -> Self {
+        let nodes: HashMap<Symbol, usize> = functions
+            .iter()
+            .enumerate()
+            .map(|(i, f)| (f.name, i))
+            .collect();
+
+        let edges = functions
+            .iter()
+            .map(|f| {
+                let mut collector = RefCollector {
+                    nodes: &nodes,
+                    scopes: vec![f.params.iter().map(|p| p.name).collect()],
+                    refs: Vec::new(),
+                };
+                collector.visit_block(&f.block);
+                collector.refs
+            })
+            .collect();
+
+        Self { edges }
+    }
+
+    /// Returns strongly connected components of the graph using
+    /// Tarjan's algorithm. Components are returned in reverse
+    /// topological order — every component comes after all
+    /// components it references. Nodes inside a component keep
+    /// declaration order.
+    ///
+    pub fn sccs(&self) -> Vec<Vec<usize>> {
+        let mut tarjan = Tarjan {
+            graph: self,
+            index: 0,
+            indices: vec![None; self.edges.len()],
+            lowlinks: vec![0; self.edges.len()],
+            stack: Vec::new(),
+            on_stack: vec![false; self.edges.len()],
+            sccs: Vec::new(),
+        };
+        for node in 0..self.edges.len() {
+            if tarjan.indices[node].is_none() {
+                tarjan.connect(node);
+            }
+        }
+        tarjan.sccs
+    }
+}
+
+/// Tarjan's strongly connected components algorithm state
+struct Tarjan<'g> {
+    graph: &'g CallGraph,
+    index: usize,
+    indices: Vec<Option<usize>>,
+    lowlinks: Vec<usize>,
+    stack: Vec<usize>,
+    on_stack: Vec<bool>,
+    sccs: Vec<Vec<usize>>,
+}
+
+/// Implementation
+impl Tarjan<'_> {
+    /// Visits node and emits every component rooted in it
+    fn connect(&mut self, node: usize) {
+        self.indices[node] = Some(self.index);
+        self.lowlinks[node] = self.index;
+        self.index += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for &next in &self.graph.edges[node] {
+            match self.indices[next] {
+                None => {
+                    self.connect(next);
+                    self.lowlinks[node] = self.lowlinks[node].min(self.lowlinks[next]);
+                }
+                Some(index) if self.on_stack[next] => {
+                    self.lowlinks[node] = self.lowlinks[node].min(index);
+                }
+                _ => {}
+            }
+        }
+
+        // Node is a root of the component
+        if Some(self.lowlinks[node]) == self.indices[node] {
+            let mut scc = Vec::new();
+            while let Some(top) = self.stack.pop() {
+                self.on_stack[top] = false;
+                scc.push(top);
+                if top == node {
+                    break;
+                }
+            }
+            scc.sort();
+            self.sccs.push(scc);
+        }
+    }
+}
+
+/// Collects references to module functions from a function body
+struct RefCollector<'n> {
+    /// Module functions by name
+    nodes: &'n HashMap<Symbol, usize>,
+
+    /// Local names scopes
+    scopes: Vec<HashSet<Symbol>>,
+
+    /// Referenced functions
+    refs: Vec<usize>,
+}
+
+/// Implementation
+impl RefCollector<'_> {
+    /// Returns true if name is shadowed by a local
+    fn is_local(&self, name: Symbol) -> bool {
+        self.scopes.iter().any(|scope| scope.contains(&name))
+    }
+}
+
+/// Visitor implementation
+impl<'ast> Visitor<'ast> for RefCollector<'_> {
+    /// Collects references from block
+    fn visit_block(&mut self, block: &'ast Block) {
+        self.scopes.push(HashSet::new());
+        visit::walk_block(self, block);
+        self.scopes.pop();
+    }
+
+    /// Collects references from statement, `let`
+    /// binding shadows names after its value
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        visit::walk_stmt(self, stmt);
+        if let StmtKind::Let(name, ..) = &stmt.kind
+            && let Some(scope) = self.scopes.last_mut()
+        {
+            scope.insert(*name);
+        }
+    }
+
+    /// Collects references from expression
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match &expr.kind {
+            ExprKind::Id(name) => {
+                if !self.is_local(*name)
+                    && let Some(&node) = self.nodes.get(name)
+                    && !self.refs.contains(&node)
+                {
+                    self.refs.push(node);
+                }
+            }
+            ExprKind::Closure(params, body) => {
+                self.scopes.push(params.iter().cloned().collect());
+                self.visit_expr(body);
+                self.scopes.pop();
+            }
+            _ => visit::walk_expr(self, expr),
+        }
+    }
+}
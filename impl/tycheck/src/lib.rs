@@ -1,6 +1,11 @@
 pub mod check;
-mod cx;
+mod confusables;
+pub mod cx;
 mod early;
 #[allow(unused_assignments)]
-mod errors;
+pub mod errors;
+pub mod graph;
+pub mod lint;
 mod res;
+mod suggest;
+mod zonk;
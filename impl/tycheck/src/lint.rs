@@ -0,0 +1,364 @@
+/// Imports
+use crate::{
+    check::ModuleTyck,
+    errors::{TypeckError, lint::LintDiagnostic},
+};
+use ast::{atom::Publicity, item::Attribute};
+use common::{
+    symbol::{Symbol, sym},
+    token::Span,
+};
+use tir::def::ItemDefKind;
+
+/// Lint level
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Level {
+    /// Lint is not reported
+    Allow,
+
+    /// Lint is reported as a warning
+    Warn,
+
+    /// Lint is reported as an error
+    Deny,
+}
+
+/// Implementation
+impl Level {
+    /// Returns level by its attribute name
+    pub fn from_name(name: Symbol) -> Option<Level> {
+        match name {
+            sym::ALLOW => Some(Level::Allow),
+            sym::WARN => Some(Level::Warn),
+            sym::DENY => Some(Level::Deny),
+            _ => None,
+        }
+    }
+
+    /// Returns attribute name of the level
+    pub fn name(&self) -> &'static str {
+        match self {
+            Level::Allow => "allow",
+            Level::Warn => "warn",
+            Level::Deny => "deny",
+        }
+    }
+
+    /// Returns command line flag of the level
+    pub fn flag(&self) -> &'static str {
+        match self {
+            Level::Allow => "-A",
+            Level::Warn => "-W",
+            Level::Deny => "-D",
+        }
+    }
+}
+
+/// Lint declaration
+#[derive(Debug)]
+pub struct Lint {
+    /// Lint name, used by attributes and command line flags
+    pub name: &'static str,
+
+    /// Stable diagnostic code
+    pub code: &'static str,
+
+    /// Level, used when nothing overrides it
+    pub default: Level,
+
+    /// Short description
+    pub desc: &'static str,
+}
+
+/// Unknown lint names in the lint attributes
+pub static UNKNOWN_LINTS: Lint = Lint {
+    name: "unknown_lints",
+    code: "W0301",
+    default: Level::Warn,
+    desc: "unknown lint names in the lint attributes",
+};
+
+/// Local variables and parameters, that are never used
+pub static UNUSED_VARIABLES: Lint = Lint {
+    name: "unused_variables",
+    code: "W0302",
+    default: Level::Warn,
+    desc: "local variables and parameters, that are never used",
+};
+
+/// Imported modules and items, that are never used
+pub static UNUSED_IMPORTS: Lint = Lint {
+    name: "unused_imports",
+    code: "W0303",
+    default: Level::Warn,
+    desc: "imported modules and items, that are never used",
+};
+
+/// Private functions, that are never used
+pub static DEAD_CODE: Lint = Lint {
+    name: "dead_code",
+    code: "W0304",
+    default: Level::Warn,
+    desc: "private functions, that are never used",
+};
+
+/// Identifiers, mixing scripts with confusable characters
+pub static MIXED_SCRIPT_CONFUSABLES: Lint = Lint {
+    name: "mixed_script_confusables",
+    code: "W0305",
+    default: Level::Warn,
+    desc: "identifiers, mixing scripts with confusable characters",
+};
+
+/// Different identifiers, that look the same
+pub static CONFUSABLE_IDENTS: Lint = Lint {
+    name: "confusable_idents",
+    code: "W0306",
+    default: Level::Warn,
+    desc: "different identifiers, that look the same",
+};
+
+/// Lints registry
+pub static LINTS: &[&Lint] = &[
+    &UNKNOWN_LINTS,
+    &UNUSED_VARIABLES,
+    &UNUSED_IMPORTS,
+    &DEAD_CODE,
+    &MIXED_SCRIPT_CONFUSABLES,
+    &CONFUSABLE_IDENTS,
+];
+
+/// Returns attributes of the item, that contains `span`
+pub(crate) fn item_attrs<'a>(items: &'a [(Span, Vec<Attribute>)], span: &Span) -> &'a [Attribute] {
+    items
+        .iter()
+        .find(|(item, _)| {
+            item.0 == span.0 && item.1.start <= span.1.start && span.1.end <= item.1.end
+        })
+        .map(|(_, attrs)| attrs.as_slice())
+        .unwrap_or_default()
+}
+
+/// Lint group, that contains every lint with the `warn` level
+pub const WARNINGS: &str = "warnings";
+
+/// Looks up lint by its name
+pub fn find(name: &str) -> Option<&'static Lint> {
+    LINTS.iter().copied().find(|lint| lint.name == name)
+}
+
+/// Place, where the level of the reported lint came from
+#[derive(Debug, Clone)]
+pub enum LevelSource {
+    /// Lint default level
+    Default,
+
+    /// Command line flag for the lint or group
+    CommandLine(String),
+
+    /// Attribute for the lint or group
+    Attribute(String, Span),
+}
+
+/// Lint levels in the current position: command line
+/// overrides and a stack of the item attributes.
+///
+/// Levels are resolved from the innermost attribute, through the
+/// command line flags (the last flag wins), to the lint default.
+/// The `warnings` group then applies to lints resolved to `warn`,
+/// so `-D warnings` promotes them and `@allow(warnings)` silences them.
+///
+#[derive(Default, Clone)]
+pub struct LintLevels {
+    /// Command line levels, in order of flags
+    cli: Vec<(String, Level)>,
+
+    /// Attribute levels of the entered items
+    stack: Vec<Vec<(String, Level, Span)>>,
+}
+
+/// Implementation
+impl LintLevels {
+    /// Sets level of the lint or group from the command line
+    pub fn set(&mut self, name: &str, level: Level) {
+        self.cli.push((name.to_string(), level));
+    }
+
+    /// Enters item with the given attributes. Attributes, that
+    /// are not lint levels, are ignored
+    pub fn push(&mut self, attrs: &[Attribute]) {
+        self.stack.push(
+            attrs
+                .iter()
+                .filter_map(|attr| Some((Level::from_name(attr.name)?, attr)))
+                .flat_map(|(level, attr)| {
+                    attr.args
+                        .iter()
+                        .map(move |arg| (arg.name.to_string(), level, attr.span.clone()))
+                })
+                .collect(),
+        );
+    }
+
+    /// Exits item
+    pub fn pop(&mut self) {
+        self.stack.pop();
+    }
+
+    /// Returns explicitly set level of the lint or group
+    fn explicit(&self, name: &str) -> Option<(Level, LevelSource)> {
+        let attr = self.stack.iter().rev().find_map(|attrs| {
+            attrs
+                .iter()
+                .rev()
+                .find(|(it, ..)| it == name)
+                .map(|(_, level, span)| {
+                    (
+                        *level,
+                        LevelSource::Attribute(name.to_string(), span.clone()),
+                    )
+                })
+        });
+        attr.or_else(|| {
+            self.cli
+                .iter()
+                .rev()
+                .find(|(it, _)| it == name)
+                .map(|(_, level)| (*level, LevelSource::CommandLine(name.to_string())))
+        })
+    }
+
+    /// Resolves level of the lint in the current position
+    pub fn level(&self, lint: &Lint) -> (Level, LevelSource) {
+        let (level, source) = self
+            .explicit(lint.name)
+            .unwrap_or((lint.default, LevelSource::Default));
+        match level {
+            Level::Warn => self.explicit(WARNINGS).unwrap_or((level, source)),
+            _ => (level, source),
+        }
+    }
+}
+
+/// Lints reporting
+impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
+    /// Reports lint at `span`, unless it is allowed in the current position
+    pub(crate) fn lint(
+        &mut self,
+        lint: &'static Lint,
+        span: &Span,
+        label: String,
+        message: String,
+        help: Option<String>,
+    ) {
+        let (level, origin) = self.lints.level(lint);
+        if level == Level::Allow {
+            return;
+        }
+        self.diagnostics.push(TypeckError::Lint(LintDiagnostic {
+            lint,
+            level,
+            origin,
+            src: self.icx.tcx.source(span),
+            span: span.1.clone().into(),
+            label,
+            message,
+            help,
+        }));
+    }
+
+    /// Checks items attributes: reports unknown attributes and lints
+    pub(crate) fn check_attributes(&mut self, m: &ast::item::Module) {
+        for item in &m.items {
+            self.set_ice_item(item);
+            self.lints.push(&item.attrs);
+            for attr in &item.attrs {
+                if Level::from_name(attr.name).is_none() {
+                    self.diagnostics.push(TypeckError::UnknownAttribute {
+                        src: self.icx.tcx.source(&attr.span),
+                        span: attr.span.1.clone().into(),
+                        name: attr.name.to_string(),
+                    });
+                    continue;
+                }
+                for arg in &attr.args {
+                    if arg.name != WARNINGS && find(arg.name.as_str()).is_none() {
+                        self.lint(
+                            &UNKNOWN_LINTS,
+                            &arg.span,
+                            "this lint is not known".to_string(),
+                            format!("unknown lint `{}`", arg.name),
+                            None,
+                        );
+                    }
+                }
+            }
+            self.lints.pop();
+        }
+        macros::ice::clear_item();
+    }
+
+    /// Pops the resolver scope, reports its unused variables
+    pub(crate) fn pop_scope(&mut self) {
+        for unused in self.resolver.pop_scope() {
+            self.lint(
+                &UNUSED_VARIABLES,
+                &unused.span,
+                "unused variable".to_string(),
+                format!("unused variable: `{}`", unused.name),
+                Some(format!(
+                    "if this is intentional, prefix it with an underscore: `_{}`",
+                    unused.name
+                )),
+            );
+        }
+    }
+
+    /// Reports unused imports and private functions of the checked module,
+    /// that are unreachable from `main`, public items and items with the
+    /// allowed `dead_code`. Items are given by their spans and attributes
+    pub(crate) fn check_unused(&mut self, items: &[(Span, Vec<Attribute>)]) {
+        for unused in self.resolver.unused_imports() {
+            self.lints.push(item_attrs(items, &unused.span));
+            self.lint(
+                &UNUSED_IMPORTS,
+                &unused.span,
+                "unused import".to_string(),
+                format!("unused import: `{}`", unused.name),
+                Some("remove the unused import".to_string()),
+            );
+            self.lints.pop();
+        }
+
+        let allowed = items
+            .iter()
+            .filter(|(_, attrs)| {
+                self.lints.push(attrs);
+                let (level, _) = self.lints.level(&DEAD_CODE);
+                self.lints.pop();
+                level == Level::Allow
+            })
+            .map(|(span, _)| span)
+            .collect::<Vec<_>>();
+        let unreachable = self.resolver.unreachable_items(|name, span, def| {
+            def.publicity != Publicity::Private || name == sym::MAIN || allowed.contains(&span)
+        });
+        for (unused, def) in unreachable {
+            if !matches!(def.kind, ItemDefKind::Fn(_)) {
+                continue;
+            }
+            self.lints.push(item_attrs(items, &unused.span));
+            self.lint(
+                &DEAD_CODE,
+                &unused.span,
+                "never used".to_string(),
+                format!("function `{}` is never used", unused.name),
+                Some(format!(
+                    "remove it, or prefix it with an underscore: `_{}`",
+                    unused.name
+                )),
+            );
+            self.lints.pop();
+        }
+    }
+}
@@ -1,9 +1,10 @@
 /// Imports
+use common::{symbol::Symbol, token::Span};
 use id_arena::Id;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, hash_map::Entry};
 use tir::{
     def::{AdtDef, ItemDef, ModDef},
-    ty::Ty,
+    ty::Scheme,
 };
 
 /// Query resolution
@@ -15,21 +16,72 @@ pub enum Res {
     /// Module definition
     Mod(Id<ModDef>),
 
-    /// Local type
-    Local(Ty),
+    /// Local type scheme and span of the binding
+    Local(Scheme, Span),
 }
 
-/// Module resolver
+/// Definition with its span and use count
+#[derive(Debug)]
+struct Binding<T> {
+    def: T,
+    span: Span,
+    uses: usize,
+}
+
+/// Implementation
+impl<T: Clone> Binding<T> {
+    /// Creates unused binding
+    fn new(def: T, span: Span) -> Self {
+        Self { def, span, uses: 0 }
+    }
+
+    /// Counts use and returns definition
+    fn use_def(&mut self) -> T {
+        self.uses += 1;
+        self.def.clone()
+    }
+}
+
+/// Unused definition
+#[derive(Debug, Clone)]
+pub struct Unused {
+    pub name: Symbol,
+    pub span: Span,
+}
+
+/// Returns true if name is not intentionally unused
+fn is_checked(name: Symbol) -> bool {
+    !name.as_str().starts_with('_')
+}
+
+/// Module resolver.
+///
+/// Every definition counts its uses by `lookup`, so unused ones
+/// are reported, when scope is popped or module is checked. Items
+/// also record references from the item bodies, so items, that are
+/// only used by each other, are found unreachable.
+///
 #[derive(Default)]
 pub struct Resolver {
     /// Scopes stack
-    scopes: Vec<HashMap<String, Ty>>,
+    scopes: Vec<HashMap<Symbol, Binding<Scheme>>>,
 
     /// Module level definitions
-    items: HashMap<String, ItemDef>,
+    items: HashMap<Symbol, Binding<ItemDef>>,
 
     /// Imported modules
-    mods: HashMap<String, Id<ModDef>>,
+    mods: HashMap<Symbol, Binding<Id<ModDef>>>,
+
+    /// Names of items, that are imported from other modules
+    imported: HashSet<Symbol>,
+
+    /// Item, whose body is being checked. Its uses
+    /// from its own body aren't counted
+    owner: Option<Symbol>,
+
+    /// Items, referenced from the body of the item. References
+    /// from outside of the item bodies are keyed by `None`
+    refs: HashMap<Option<Symbol>, HashSet<Symbol>>,
 }
 
 /// Implementation
@@ -39,80 +91,194 @@ impl Resolver {
         self.scopes.push(HashMap::new());
     }
 
-    /// Pops the current scope from the stack
-    pub fn pop_scope(&mut self) {
-        self.scopes.pop();
+    /// Pops the current scope from the stack,
+    /// returns its unused definitions
+    pub fn pop_scope(&mut self) -> Vec<Unused> {
+        let mut unused = self
+            .scopes
+            .pop()
+            .into_iter()
+            .flatten()
+            .filter(|(name, local)| local.uses == 0 && is_checked(*name))
+            .map(|(name, local)| Unused {
+                name,
+                span: local.span,
+            })
+            .collect::<Vec<_>>();
+        unused.sort_by_key(|it| it.span.1.start);
+        unused
+    }
+
+    /// Sets item, whose body is being checked
+    pub fn set_owner(&mut self, owner: Option<Symbol>) {
+        self.owner = owner;
     }
 
     /// Defines top-level item, returns true on success,
     /// returns false if item already defined
-    pub fn define_item(&mut self, name: &str, def: ItemDef) -> bool {
-        if self.items.contains_key(name) {
-            false
-        } else {
-            self.items.insert(name.to_string(), def);
-            true
+    pub fn define_item(&mut self, name: Symbol, span: &Span, def: ItemDef) -> bool {
+        match self.items.entry(name) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(Binding::new(def, span.clone()));
+                true
+            }
+        }
+    }
+
+    /// Defines item, imported from another module, returns
+    /// true on success, returns false if item already defined
+    pub fn import_item(&mut self, name: Symbol, span: &Span, def: ItemDef) -> bool {
+        let defined = self.define_item(name, span, def);
+        if defined {
+            self.imported.insert(name);
         }
+        defined
     }
 
     /// Defines module, returns true on success,
     /// returns false if item already defined
-    pub fn define_mod(&mut self, name: &str, def: Id<ModDef>) -> bool {
-        if self.mods.contains_key(name) {
-            false
-        } else {
-            self.mods.insert(name.to_string(), def);
-            true
+    pub fn define_mod(&mut self, name: Symbol, span: &Span, def: Id<ModDef>) -> bool {
+        match self.mods.entry(name) {
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(Binding::new(def, span.clone()));
+                true
+            }
         }
     }
 
     /// Defines scope-level definition, returns true on success,
     /// returns false if item already defined
-    pub fn define_local(&mut self, name: &str, ty: Ty) -> bool {
+    pub fn define_local(&mut self, name: Symbol, span: &Span, scheme: Scheme) -> bool {
         match self.scopes.last_mut() {
-            Some(scope) => {
-                if scope.contains_key(name) {
-                    false
-                } else {
-                    scope.insert(name.to_string(), ty);
+            Some(scope) => match scope.entry(name) {
+                Entry::Occupied(_) => false,
+                Entry::Vacant(entry) => {
+                    entry.insert(Binding::new(scheme, span.clone()));
                     true
                 }
-            }
+            },
             None => false,
         }
     }
 
-    /// Looks up top-level definition
-    pub fn lookup_item(&self, name: &str) -> Option<ItemDef> {
-        self.items.get(name).cloned()
-    }
-
-    /// Looks up module definition
-    pub fn lookup_mod(&self, name: &str) -> Option<Id<ModDef>> {
-        self.mods.get(name).cloned()
+    /// Looks up top-level definition, without counting its use
+    pub fn lookup_item(&self, name: Symbol) -> Option<ItemDef> {
+        self.items.get(&name).map(|item| item.def.clone())
     }
 
-    /// Looks up top-level adt definition
-    pub fn lookup_adt(&self, name: &str) -> Option<Id<AdtDef>> {
-        match self.items.get(name)?.kind {
+    /// Looks up top-level adt definition, without counting its use
+    pub fn lookup_adt(&self, name: Symbol) -> Option<Id<AdtDef>> {
+        match self.items.get(&name)?.def.kind {
             tir::def::ItemDefKind::Adt(id) => Some(id),
             tir::def::ItemDefKind::Fn(_) => None,
         }
     }
 
-    /// Looks up local-level definition
-    pub fn lookup_local(&self, name: &str) -> Option<Ty> {
+    /// Looks up adt definition, referenced by the type hint, counting its use
+    pub fn lookup_type(&mut self, name: Symbol) -> Option<Id<AdtDef>> {
+        let id = self.lookup_adt(name)?;
+        self.items.get_mut(&name)?.uses += 1;
+        Some(id)
+    }
+
+    /// Returns names of locals, top-level items and modules
+    /// visible from the current scope
+    pub fn names(&self) -> Vec<Symbol> {
         self.scopes
             .iter()
+            .flat_map(|scope| scope.keys())
+            .chain(self.items.keys())
+            .chain(self.mods.keys())
+            .cloned()
+            .collect()
+    }
+
+    /// Returns names of top-level adt definitions
+    pub fn adt_names(&self) -> Vec<Symbol> {
+        self.items
+            .iter()
+            .filter(|(_, item)| matches!(item.def.kind, tir::def::ItemDefKind::Adt(_)))
+            .map(|(name, _)| *name)
+            .collect()
+    }
+
+    /// Looks up local-level, top-level item or module, counting its use
+    pub fn lookup(&mut self, name: Symbol) -> Option<Res> {
+        if let Some(local) = self
+            .scopes
+            .iter_mut()
             .rev()
-            .find_map(|scope| scope.get(name).cloned())
+            .find_map(|scope| scope.get_mut(&name))
+        {
+            return Some(Res::Local(local.use_def(), local.span.clone()));
+        }
+        if let Some(item) = self.items.get_mut(&name) {
+            self.refs.entry(self.owner).or_default().insert(name);
+            return Some(Res::Item(match &self.owner {
+                Some(owner) if *owner == name => item.def.clone(),
+                _ => item.use_def(),
+            }));
+        }
+        self.mods.get_mut(&name).map(|m| Res::Mod(m.use_def()))
+    }
+
+    /// Returns module level items, that are defined in this module and
+    /// are unreachable from the roots and from outside of the item bodies.
+    /// Roots are given by `is_root`, items prefixed with `_` are roots too
+    pub fn unreachable_items(
+        &self,
+        is_root: impl Fn(Symbol, &Span, &ItemDef) -> bool,
+    ) -> Vec<(Unused, ItemDef)> {
+        // Walking references from the roots
+        let mut reachable = HashSet::new();
+        let mut stack = self
+            .items
+            .iter()
+            .filter(|(name, item)| !is_checked(**name) || is_root(**name, &item.span, &item.def))
+            .map(|(name, _)| *name)
+            .chain(self.refs.get(&None).into_iter().flatten().copied())
+            .collect::<Vec<_>>();
+        while let Some(name) = stack.pop() {
+            if reachable.insert(name) {
+                stack.extend(self.refs.get(&Some(name)).into_iter().flatten().copied());
+            }
+        }
+
+        let mut unused = self
+            .items
+            .iter()
+            .filter(|(name, _)| !reachable.contains(*name) && !self.imported.contains(*name))
+            .map(|(name, item)| {
+                let unused = Unused {
+                    name: *name,
+                    span: item.span.clone(),
+                };
+                (unused, item.def.clone())
+            })
+            .collect::<Vec<_>>();
+        unused.sort_by_key(|(it, _)| it.span.1.start);
+        unused
     }
 
-    /// Looks up local-level, top-level item or module
-    pub fn lookup(&self, name: &str) -> Option<Res> {
-        self.lookup_local(name)
-            .map(Res::Local)
-            .or_else(|| self.lookup_item(name).map(Res::Item))
-            .or_else(|| self.lookup_mod(name).map(Res::Mod))
+    /// Returns unused imported modules and items
+    pub fn unused_imports(&self) -> Vec<Unused> {
+        let items = self
+            .items
+            .iter()
+            .filter(|(name, _)| self.imported.contains(*name))
+            .map(|(name, item)| (name, &item.span, item.uses));
+        let mods = self.mods.iter().map(|(name, m)| (name, &m.span, m.uses));
+        let mut unused = items
+            .chain(mods)
+            .filter(|(name, _, uses)| *uses == 0 && is_checked(**name))
+            .map(|(name, span, _)| Unused {
+                name: *name,
+                span: span.clone(),
+            })
+            .collect::<Vec<_>>();
+        unused.sort_by_key(|it| it.span.1.start);
+        unused
     }
 }
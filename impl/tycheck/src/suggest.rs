@@ -0,0 +1,114 @@
+/// Imports
+use crate::check::ModuleTyck;
+use common::{symbol::Symbol, token::Span};
+use tir::{
+    def::{AdtDef, ItemDefKind},
+    ty::{Ty, TyKind, TyMeta},
+};
+
+/// Calculates edit distance between two strings, counting
+/// insertions, deletions, substitutions and transpositions
+fn edit_distance(a: &str, b: &str) -> usize {
+    let (a, b) = (a.chars().collect::<Vec<_>>(), b.chars().collect::<Vec<_>>());
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    (0..=a.len()).for_each(|i| d[i][0] = i);
+    (0..=b.len()).for_each(|j| d[0][j] = j);
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1)
+                .min(d[i][j - 1] + 1)
+                .min(d[i - 1][j - 1] + cost);
+            // Adjacent characters are swapped
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Returns the most similar candidate to `name`. Candidates
+/// differing by more than a third of the name length are ignored
+fn similar<'c>(name: &str, candidates: impl IntoIterator<Item = &'c str>) -> Option<&'c str> {
+    let max = (name.chars().count() / 3).max(1);
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max)
+        .min_by(|(d1, c1), (d2, c2)| d1.cmp(d2).then(c1.cmp(c2)))
+        .map(|(_, candidate)| candidate)
+}
+
+/// "Did you mean" suggestions for the unresolved names.
+///
+/// Candidates are looked up in the resolver scopes, module items,
+/// generics in scope and ADT fields. When nothing similar is found in
+/// scope, but the exact name is defined in another module, that module
+/// is suggested instead.
+///
+impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
+    /// Suggests module, that defines item `name`, but isn't imported
+    fn suggest_module(&self, span: &Span, name: Symbol, is_type: bool) -> Option<String> {
+        self.icx
+            .tcx
+            .modules
+            .iter()
+            .map(|(_, m)| m)
+            .filter(|m| m.file != span.0)
+            .find(|m| match m.defs.get(&name) {
+                Some(def) => !is_type || matches!(def.kind, ItemDefKind::Adt(_)),
+                None => false,
+            })
+            .map(|m| {
+                let module = self.icx.tcx.sources.get(m.file).module_name();
+                format!("`{name}` is defined in module `{module}`, consider importing it")
+            })
+    }
+
+    /// Suggests value for the unresolved name
+    pub(crate) fn suggest_name(&self, span: &Span, name: Symbol) -> Option<String> {
+        let names = self.resolver.names();
+        similar(name.as_str(), names.iter().map(Symbol::as_str))
+            .map(|found| format!("did you mean `{found}`?"))
+            .or_else(|| self.suggest_module(span, name, false))
+    }
+
+    /// Suggests type for the unresolved type name
+    pub(crate) fn suggest_type(&self, span: &Span, name: Symbol) -> Option<String> {
+        let adts = self.resolver.adt_names();
+        let generics = self.icx.generics.names();
+        let primitives = self.icx.tcx.types.primitives().map(|(name, _)| name);
+        let candidates = adts
+            .iter()
+            .chain(generics.iter())
+            .chain(primitives.iter())
+            .map(Symbol::as_str);
+        similar(name.as_str(), candidates)
+            .map(|found| format!("did you mean `{found}`?"))
+            .or_else(|| self.suggest_module(span, name, true))
+    }
+
+    /// Suggests field of the `ty` for the unresolved field: struct field,
+    /// enum variant or module item
+    pub(crate) fn suggest_field(&self, ty: Ty, name: Symbol) -> Option<String> {
+        let name = name.as_str();
+        let tcx = &self.icx.tcx;
+        let found = match tcx.kind(ty) {
+            TyKind::Meta(TyMeta::Module(id)) => {
+                similar(name, tcx._mod(*id).defs.keys().map(Symbol::as_str))
+            }
+            TyKind::Meta(TyMeta::Adt(id)) => match tcx.adt(*id) {
+                AdtDef::Enum(e) => similar(name, e.variants.iter().map(|v| v.name.as_str())),
+                AdtDef::Struct(_) => None,
+            },
+            TyKind::Adt(id, _) => match tcx.adt(*id) {
+                AdtDef::Struct(s) => similar(name, s.fields.iter().map(|f| f.name.as_str())),
+                AdtDef::Enum(_) => None,
+            },
+            _ => None,
+        };
+        found.map(|found| format!("did you mean `{found}`?"))
+    }
+}
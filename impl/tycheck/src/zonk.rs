@@ -0,0 +1,252 @@
+/// Imports
+use crate::{check::ModuleTyck, errors::TypeckError};
+use common::token::Span;
+use id_arena::Id;
+use std::collections::HashMap;
+use tir::{
+    atom::Param,
+    expr::{Expr, ExprKind, InterpPart},
+    item::Function,
+    results::Adjustment,
+    stmt::{Stmt, StmtKind},
+    ty::{Ty, TyVar},
+    visit::{self, Folder},
+};
+
+/// Site, that introduces the zonked type
+#[derive(Clone, Copy)]
+enum Site<'s> {
+    /// Let binding with name
+    Let(&'s str),
+
+    /// Function param with name
+    FnParam(&'s str),
+
+    /// Closure param with name
+    ClosureParam(&'s str),
+
+    /// Any other expression
+    Expr,
+}
+
+/// Implementation
+impl Site<'_> {
+    /// Describes what can't be inferred
+    fn what(&self) -> String {
+        match self {
+            Site::Let(name) => format!("can't infer type of `{name}`"),
+            Site::FnParam(name) => format!("can't infer type of param `{name}`"),
+            Site::ClosureParam(name) => format!("can't infer type of closure param `{name}`"),
+            Site::Expr => "can't infer type of this expression".to_string(),
+        }
+    }
+
+    /// Suggests where to add a type hint
+    fn help(&self) -> String {
+        match self {
+            Site::Let(name) => format!("consider giving `{name}` a type: `let {name}: Type = ...`"),
+            Site::FnParam(name) => format!("consider giving `{name}` a type: `{name}: Type`"),
+            Site::ClosureParam(name) => format!(
+                "consider giving the closure a type: `let f: fn(Type) -> Type = |{name}| ...`"
+            ),
+            Site::Expr => {
+                "consider binding this expression with a type: `let value: Type = ...`".to_string()
+            }
+        }
+    }
+}
+
+/// Zonking: the final pass over the function typed IR.
+///
+/// Applies substitutions to every type and instantiation, reports inference variables left
+/// unbound after checking once — at the first binding or expression that
+/// introduced them — and replaces them with `Ty::Error`, so later phases
+/// never see inference holes.
+///
+/// Variables quantified by let-bound schemes are not holes, they are
+/// replaced with scheme params `TyKind::Param`, numbered within the function.
+///
+impl<'tcx, 'icx> ModuleTyck<'tcx, 'icx> {
+    /// Zonks type introduced at `span`
+    fn zonk_ty(&mut self, ty: Ty, span: &Span, site: Site) -> Ty {
+        let ty = self.icx.apply(ty);
+        let vars = self.icx.unresolved_vars(ty);
+        if vars.is_empty() {
+            return ty;
+        }
+
+        // Reporting and binding variables to the error,
+        // so they are never reported again
+        self.diagnostics.push(TypeckError::AnnotationsNeeded {
+            src: self.icx.tcx.source(span),
+            span: span.1.clone().into(),
+            what: site.what(),
+            help: site.help(),
+        });
+        for var in vars {
+            self.icx.subst(var, self.icx.tcx.types.error);
+        }
+        self.icx.apply(ty)
+    }
+
+    /// Zonks function, which signature variables
+    /// are mapped to its generics by `generics`
+    pub(crate) fn zonk_fn(
+        &mut self,
+        function: Function,
+        generics: HashMap<Id<TyVar>, Ty>,
+    ) -> Function {
+        Zonker {
+            tyck: self,
+            generics,
+            params: HashMap::new(),
+        }
+        .fold_function(function)
+    }
+}
+
+/// Folder, that zonks every type of the function
+struct Zonker<'z, 'tcx, 'icx> {
+    /// Type checker reference
+    tyck: &'z mut ModuleTyck<'tcx, 'icx>,
+
+    /// Generics of the function signature variables
+    generics: HashMap<Id<TyVar>, Ty>,
+
+    /// Scheme params of the quantified variables
+    params: HashMap<Id<TyVar>, Ty>,
+}
+
+/// Implementation
+impl Zonker<'_, '_, '_> {
+    /// Zonks type introduced at `span`, replacing signature variables
+    /// with generics and quantified variables with scheme params
+    fn zonk_ty(&mut self, ty: Ty, span: &Span, site: Site) -> Ty {
+        let ty = self.tyck.icx.apply(ty);
+        let ty = self.tyck.icx.replace_vars(ty, &self.generics);
+        let ty = self.tyck.zonk_ty(ty, span, site);
+        self.parametrize(ty)
+    }
+
+    /// Replaces quantified variables of the
+    /// substituted type with scheme params
+    fn parametrize(&mut self, ty: Ty) -> Ty {
+        let icx = &mut self.tyck.icx;
+        for var in icx.quantified_vars(ty) {
+            let next = self.params.len();
+            self.params
+                .entry(var)
+                .or_insert_with(|| icx.tcx.mk_param(next));
+        }
+        icx.replace_vars(ty, &self.params)
+    }
+}
+
+/// Folder implementation
+impl Folder for Zonker<'_, '_, '_> {
+    /// Zonks function. Its type is only substituted,
+    /// since holes in it are reported at params and body
+    fn fold_function(&mut self, function: Function) -> Function {
+        let params = function
+            .params
+            .into_iter()
+            .map(|param| {
+                let ty = self.zonk_ty(param.ty, &param.span, Site::FnParam(param.name.as_str()));
+                Param { ty, ..param }
+            })
+            .collect();
+        let block = self.fold_block(function.block);
+        let ty = self.tyck.icx.apply(function.ty);
+        Function {
+            params,
+            block,
+            ty,
+            ..function
+        }
+    }
+
+    /// Zonks closure param
+    fn fold_param(&mut self, param: Param) -> Param {
+        let ty = self.zonk_ty(
+            param.ty,
+            &param.span,
+            Site::ClosureParam(param.name.as_str()),
+        );
+        Param { ty, ..param }
+    }
+
+    /// Zonks statement. Let bindings are zonked before
+    /// their values, so holes are reported at the binding
+    fn fold_stmt(&mut self, stmt: Stmt) -> Stmt {
+        let kind = match stmt.kind {
+            StmtKind::Let(name, span, ty, expr) => {
+                let ty = self.zonk_ty(ty, &span, Site::Let(name.as_str()));
+                StmtKind::Let(name, span, ty, self.fold_expr(expr))
+            }
+            StmtKind::Expr(expr) => StmtKind::Expr(self.fold_expr(expr)),
+            StmtKind::Semi(expr) => StmtKind::Semi(self.fold_expr(expr)),
+        };
+        let ty = self.zonk_ty(stmt.ty, &stmt.span, Site::Expr);
+        self.tyck.results.node_types.insert(stmt.id, ty);
+
+        Stmt {
+            id: stmt.id,
+            kind,
+            span: stmt.span,
+            ty,
+        }
+    }
+
+    /// Zonks expression. Children are zonked first,
+    /// so holes are reported at the innermost expression
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        let expr = match expr.kind {
+            ExprKind::Cast(what, ty) => {
+                let ty = self.zonk_ty(ty, &expr.span, Site::Expr);
+                Expr {
+                    kind: ExprKind::Cast(Box::new(self.fold_expr(*what)), ty),
+                    ..expr
+                }
+            }
+            ExprKind::Interp(parts) => {
+                let parts = parts
+                    .into_iter()
+                    .map(|part| match part {
+                        InterpPart::Lit(text) => InterpPart::Lit(text),
+                        InterpPart::Expr(value) => {
+                            let value = self.fold_expr(value);
+                            self.tyck.check_printable(&value);
+                            InterpPart::Expr(value)
+                        }
+                    })
+                    .collect();
+                Expr {
+                    kind: ExprKind::Interp(parts),
+                    ..expr
+                }
+            }
+            _ => visit::fold_expr(self, expr),
+        };
+        let ty = self.zonk_ty(expr.ty, &expr.span, Site::Expr);
+        self.tyck.results.node_types.insert(expr.id, ty);
+
+        // Substituting instantiation of the expression
+        let adjustment = self.tyck.results.adjustments.get(&expr.id).cloned();
+        if let Some(Adjustment::Instantiate(args)) = adjustment {
+            let args = args
+                .into_iter()
+                .map(|arg| {
+                    let arg = self.tyck.icx.apply(arg);
+                    let arg = self.tyck.icx.replace_vars(arg, &self.generics);
+                    self.parametrize(arg)
+                })
+                .collect();
+            self.tyck
+                .results
+                .adjustments
+                .insert(expr.id, Adjustment::Instantiate(args));
+        }
+
+        Expr { ty, ..expr }
+    }
+}
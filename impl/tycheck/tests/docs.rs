@@ -0,0 +1,94 @@
+/// Imports
+use lexer::Lexer;
+use parser::Parser;
+use tir::item::ItemKind;
+use tycheck::{
+    check::ModuleTyck,
+    cx::{icx::InferCx, tcx::TyCx},
+};
+
+#[test]
+fn doc_comments_are_attached_to_items() {
+    let src = r#"
+/// A point
+///   on the plane
+struct Point {
+    /// Horizontal
+    x: Int,
+    # not a doc comment
+    y: Int
+}
+
+@allow(dead_code)
+/// A shape
+enum Shape {
+    /// Just a point
+    Dot(Point),
+    Empty
+}
+
+/// Entry point
+fn main() {
+    /// Dangling doc comment
+    let _a = 1;
+}
+"#;
+    let mut tcx = TyCx::default();
+    let file = tcx.sources.add("test.bit", src.to_string());
+    let file = tcx.sources.get(file);
+    let module = Parser::new(file.source(), Lexer::new(file)).parse();
+
+    let mut icx = InferCx::new(&mut tcx);
+    let mut tyck = ModuleTyck::new(&mut icx);
+    let module = tyck.check_module(module);
+
+    // Typed items
+    let docs = module
+        .items
+        .iter()
+        .map(|item| item.docs.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        docs,
+        [
+            Some("A point\n  on the plane"),
+            Some("A shape"),
+            Some("Entry point")
+        ]
+    );
+    let ItemKind::Struct(point) = &module.items[0].kind else {
+        panic!("expected struct");
+    };
+    let fields = point
+        .fields
+        .iter()
+        .map(|f| f.docs.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, [Some("Horizontal"), None]);
+
+    // Definitions
+    let adts = tcx
+        .adt
+        .iter()
+        .map(|(_, adt)| adt.docs())
+        .collect::<Vec<_>>();
+    assert_eq!(adts, [Some("A point\n  on the plane"), Some("A shape")]);
+    let variants = tcx
+        .adt
+        .iter()
+        .nth(1)
+        .unwrap()
+        .1
+        .as_enum()
+        .variants
+        .iter()
+        .map(|v| v.docs.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(variants, [Some("Just a point"), None]);
+    let functions = tcx
+        .functions
+        .iter()
+        .map(|(_, f)| f.docs.as_deref())
+        .collect::<Vec<_>>();
+    assert_eq!(functions, [Some("Entry point")]);
+}
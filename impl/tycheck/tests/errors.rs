@@ -0,0 +1,558 @@
+/// Imports
+use ast::atom::Publicity;
+use common::{symbol::Symbol, token::Span};
+use lexer::Lexer;
+use miette::{Diagnostic, Severity};
+use parser::Parser;
+use std::collections::HashMap;
+use tir::def::{AdtDef, ItemDef, ItemDefKind, ModDef, StructDef};
+use tycheck::{
+    check::ModuleTyck,
+    cx::{icx::InferCx, tcx::TyCx},
+    errors::TypeckError,
+    lint::{Level, LintLevels},
+};
+
+/// Checks source and returns reported errors, without lints
+fn diagnostics(src: &str) -> Vec<TypeckError> {
+    diagnostics_with(src, LintLevels::default())
+        .into_iter()
+        .filter(|diag| !matches!(diag, TypeckError::Lint(_)))
+        .collect()
+}
+
+/// Checks source and returns reported lints
+fn lints(src: &str) -> Vec<TypeckError> {
+    diagnostics_with(src, LintLevels::default())
+        .into_iter()
+        .filter(|diag| matches!(diag, TypeckError::Lint(_)))
+        .collect()
+}
+
+/// Checks source with the given lint levels and returns reported diagnostics
+fn diagnostics_with(src: &str, levels: LintLevels) -> Vec<TypeckError> {
+    let mut tcx = TyCx::default();
+    let file = tcx.sources.add("test.bit", src.to_string());
+    let file = tcx.sources.get(file);
+    let mut parser = Parser::new(file.source(), Lexer::new(file));
+    let module = parser.parse();
+
+    let mut icx = InferCx::new(&mut tcx);
+    let mut tyck = ModuleTyck::new(&mut icx).with_lint_levels(levels);
+    tyck.check_module(module);
+    tyck.take_diagnostics()
+}
+
+#[test]
+fn unresolved_name_is_reported_once() {
+    let diags = diagnostics(
+        r#"
+fn main() {
+    let a = missing + 1;
+    let b = a * 2 - -a;
+    let c = a.field;
+    let d = a(1, 2);
+    let e: Int = a;
+    let f = if a { a } else { 1 };
+    let g = a == 3 && !a;
+}
+"#,
+    );
+    assert!(
+        matches!(diags.as_slice(), [TypeckError::UnresolvedName { .. }]),
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn unresolved_type_is_reported_once() {
+    let diags = diagnostics(
+        r#"
+fn area(shape: Shape) -> Int {
+    shape.width * shape.height
+}
+
+fn main() {
+    let a: Int = area(1);
+}
+"#,
+    );
+    assert!(
+        matches!(diags.as_slice(), [TypeckError::UnresolvedType { .. }]),
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn invalid_operation_is_reported_once() {
+    let diags = diagnostics(
+        r#"
+fn main() {
+    let a = -"text";
+    let b = a + 1;
+    let c: String = b;
+    let d = b(c);
+}
+"#,
+    );
+    assert!(
+        matches!(diags.as_slice(), [TypeckError::InvalidUnaryOp { .. }]),
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn erroneous_return_type_is_absorbed_by_callers() {
+    let diags = diagnostics(
+        r#"
+fn broken() {
+    missing
+}
+
+fn main() {
+    let a: Int = broken();
+    let b: String = broken() + 1;
+}
+"#,
+    );
+    assert!(
+        matches!(diags.as_slice(), [TypeckError::UnresolvedName { .. }]),
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn independent_errors_are_reported_separately() {
+    let diags = diagnostics(
+        r#"
+fn main() {
+    let a: Int = "text";
+    let b = missing;
+}
+"#,
+    );
+    assert!(
+        matches!(
+            diags.as_slice(),
+            [
+                TypeckError::TypeMismatch { .. },
+                TypeckError::UnresolvedName { .. }
+            ]
+        ),
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn unresolved_type_is_reported_once_at_binding() {
+    let diags = diagnostics(
+        r#"
+fn main() {
+    let f = |x| x;
+    let r = f(f);
+    let s = r;
+}
+"#,
+    );
+    assert!(
+        matches!(diags.as_slice(), [TypeckError::AnnotationsNeeded { what, .. }] if what.contains("`r`")),
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn mismatch_names_variables_and_meta_types() {
+    let diags = diagnostics(
+        r#"
+struct House { rooms: Int }
+
+fn main() {
+    let f = |x, y| x;
+    let a: Int = f;
+    let b: Int = House;
+}
+"#,
+    );
+    let messages = diags
+        .iter()
+        .filter(|d| matches!(d, TypeckError::TypeMismatch { .. }))
+        .map(|d| d.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        messages,
+        [
+            "type mismatch: expected `Int`, found `fn(?T1, ?T2) -> ?T1`",
+            "type mismatch: expected `Int`, found type `House`",
+        ],
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn mismatch_labels_origin_of_expected_type() {
+    let diags = diagnostics(
+        r#"
+fn area(w: Int, h: Int) -> Int { w * h }
+
+struct Point { x: Int, y: Int }
+
+enum Shape {
+    Dot(Point),
+    Circle(Point, Int)
+}
+
+fn main() {
+    let a = area(1, "two");
+    let c = if true { 1 } else { "s" };
+    let p = Point(1, "two");
+    let s = Shape.Circle(Point(1, 2), "three");
+}
+"#,
+    );
+    let notes = diags
+        .iter()
+        .filter_map(|d| match d {
+            TypeckError::TypeMismatch {
+                origin: Some(_),
+                note,
+                ..
+            } => Some(note.as_str()),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        notes,
+        [
+            "parameter declared here",
+            "expected due to this branch",
+            "field declared here",
+            "variant declared here",
+        ],
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn unresolved_names_suggest_similar() {
+    let diags = diagnostics(
+        r#"
+struct House { rooms: Int }
+
+fn main() {
+    let count = 1;
+    let a = cuont + 1;
+    let h: Hose = House(1);
+    let r = House(2).romos;
+}
+"#,
+    );
+    let helps = diags
+        .iter()
+        .map(|d| match d {
+            TypeckError::UnresolvedName { help, .. }
+            | TypeckError::UnresolvedType { help, .. }
+            | TypeckError::UnresolvedField { help, .. } => help.as_deref(),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        helps,
+        [
+            Some("did you mean `count`?"),
+            Some("did you mean `House`?"),
+            Some("did you mean `rooms`?"),
+        ],
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn unknown_lint_is_a_warning() {
+    let diags = lints(
+        r#"
+@allow(unused_thing)
+fn main() {}
+"#,
+    );
+    assert!(
+        matches!(diags.as_slice(), [TypeckError::Lint(lint)] if lint.severity() == Some(Severity::Warning)),
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn lint_levels_from_attributes() {
+    let diags = diagnostics_with(
+        r#"
+@allow(unknown_lints, unused_thing)
+fn _a() {}
+
+@deny(unknown_lints)
+@warn(unused_thing)
+fn _b() {}
+
+@inline()
+fn _c() {}
+"#,
+        LintLevels::default(),
+    );
+    assert!(
+        matches!(
+            diags.as_slice(),
+            [TypeckError::Lint(lint), TypeckError::UnknownAttribute { .. }]
+                if lint.severity() == Some(Severity::Error)
+        ),
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn warnings_are_promoted_from_command_line() {
+    let src = r#"
+@allow(unused_thing)
+fn main() {}
+"#;
+    let mut levels = LintLevels::default();
+    levels.set("warnings", Level::Deny);
+    let diags = diagnostics_with(src, levels.clone());
+    assert!(
+        matches!(diags.as_slice(), [TypeckError::Lint(lint)] if lint.severity() == Some(Severity::Error)),
+        "{diags:?}"
+    );
+
+    levels.set("unknown_lints", Level::Allow);
+    let diags = diagnostics_with(src, levels);
+    assert!(diags.is_empty(), "{diags:?}");
+}
+
+/// Returns messages of the reported lints
+fn lint_messages(src: &str) -> Vec<String> {
+    lints(src).iter().map(|lint| lint.to_string()).collect()
+}
+
+#[test]
+fn unused_variables_are_reported() {
+    let messages = lint_messages(
+        r#"
+fn area(w: Int, h: Int, _scale: Int) -> Int {
+    let unused = w;
+    let f = |x, _y| 1;
+    f(2, 3)
+}
+
+fn main() {
+    let _a = area(1, 2, 3);
+}
+"#,
+    );
+    assert_eq!(
+        messages,
+        [
+            "unused variable: `x`",
+            "unused variable: `unused`",
+            "unused variable: `h`",
+        ],
+    );
+}
+
+#[test]
+fn unused_variable_is_reported_at_its_name() {
+    let src = r#"
+fn main() {
+    let unused: Int = 1;
+}
+"#;
+    let lints = lints(src);
+    let [lint] = lints.as_slice() else {
+        panic!("{lints:?}");
+    };
+    let label = lint.labels().unwrap().next().unwrap();
+    assert_eq!(&src[label.offset()..label.offset() + label.len()], "unused");
+}
+
+#[test]
+fn mutually_recursive_dead_functions_are_reported() {
+    let messages = lint_messages(
+        r#"
+fn a() { b() }
+
+fn b() { a() }
+
+fn c() { d() }
+
+fn d() { c() }
+
+@allow(dead_code)
+fn e() { f() }
+
+fn f() {}
+
+fn main() {
+    c();
+}
+"#,
+    );
+    assert_eq!(
+        messages,
+        ["function `a` is never used", "function `b` is never used",]
+    );
+}
+
+#[test]
+fn dead_functions_are_reported() {
+    let messages = lint_messages(
+        r#"
+fn fact(n: Int) -> Int { if n == 0 { 1 } else { n * fact(n - 1) } }
+
+fn used() -> Int { 1 }
+
+fn _helper() {}
+
+@allow(dead_code)
+fn allowed() {}
+
+fn main() {
+    let _a = used();
+}
+"#,
+    );
+    assert_eq!(messages, ["function `fact` is never used"]);
+}
+
+#[test]
+fn unused_imports_are_reported() {
+    let src = r#"
+use geo
+use geo as g
+use geo for Point
+
+fn main() {
+    let _a = geo;
+}
+"#;
+    let mut tcx = TyCx::default();
+
+    // Defining `geo` module with a public struct
+    let geo = tcx.sources.add("geo.bit", String::new());
+    let point = tcx.insert_adt(AdtDef::Struct(StructDef {
+        docs: None,
+        span: Span(geo, 0..0),
+        name: Symbol::intern("Point"),
+        generics: Vec::new(),
+        fields: Vec::new(),
+    }));
+    let defs = HashMap::from([(
+        Symbol::intern("Point"),
+        ItemDef {
+            publicity: Publicity::Pub,
+            kind: ItemDefKind::Adt(point),
+        },
+    )]);
+    tcx.insert_mod(ModDef { file: geo, defs });
+
+    let file = tcx.sources.add("main.bit", src.to_string());
+    let file = tcx.sources.get(file);
+    let mut parser = Parser::new(file.source(), Lexer::new(file));
+    let module = parser.parse();
+    let mut icx = InferCx::new(&mut tcx);
+    let mut tyck = ModuleTyck::new(&mut icx);
+    tyck.check_module(module);
+    let messages = tyck
+        .take_diagnostics()
+        .iter()
+        .map(|diag| diag.to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(messages, ["unused import: `g`", "unused import: `Point`"],);
+}
+
+#[test]
+fn unicode_identifiers_are_normalized() {
+    // `café` is written composed, then decomposed
+    let diags = diagnostics_with(
+        "fn main() {\n    let café = 1;\n    let _площадь: Int = cafe\u{301} * 2;\n}\n",
+        LintLevels::default(),
+    );
+    assert!(diags.is_empty(), "{diags:?}");
+}
+
+#[test]
+fn confusable_identifiers_are_reported() {
+    // The second `а` in `pаy` is cyrillic
+    let messages = lint_messages(
+        r#"
+fn main() {
+    let pay = 1;
+    let _total = pаy + pay;
+}
+"#,
+    );
+    assert_eq!(
+        messages,
+        [
+            "identifier `pаy` mixes Cyrillic and Latin scripts",
+            "identifier `pаy` is confusable with `pay`",
+        ],
+    );
+}
+
+#[test]
+fn confusable_identifiers_respect_item_levels() {
+    // `а` in `pаy` and `рау` is cyrillic
+    let messages = lint_messages(
+        r#"
+@allow(confusable_idents, mixed_script_confusables)
+fn main() {
+    let pay = 1;
+    let _total = pаy + pay;
+}
+
+fn other() {
+    let рау = 2;
+    рау
+}
+"#,
+    );
+    assert_eq!(
+        messages,
+        [
+            "function `other` is never used",
+            "identifier `рау` is confusable with `pay`",
+        ],
+    );
+}
+
+#[test]
+fn interpolated_values_must_be_printable() {
+    let src = r#"
+fn greet(name: String, age: Int) -> String {
+    "Hello, {name}! You are {age + 1} years old, {{not {age > 1}}}"
+}
+
+fn nothing() {}
+
+fn show<T>(value: T) -> String {
+    "value: {value}, {greet("a", 1)}, unit: {nothing()}"
+}
+"#;
+    let diags = diagnostics(src);
+    let spans = diags
+        .iter()
+        .map(|diag| match diag {
+            TypeckError::NotPrintable { ty, .. } => {
+                let label = diag.labels().unwrap().next().unwrap();
+                (
+                    ty.clone(),
+                    &src[label.offset()..label.offset() + label.len()],
+                )
+            }
+            _ => panic!("unexpected diagnostic {diag:?}"),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        spans,
+        [
+            ("`T0`".to_string(), "value"),
+            ("`()`".to_string(), "nothing()")
+        ]
+    );
+}
@@ -0,0 +1,62 @@
+/// Imports
+use ast::item::{Function, ItemKind};
+use common::source::SourceMap;
+use lexer::Lexer;
+use parser::Parser;
+use tycheck::graph::CallGraph;
+
+/// Builds call graph of the source functions
+/// and returns its strongly connected components
+fn sccs(src: &str) -> Vec<Vec<usize>> {
+    let mut sources = SourceMap::default();
+    let id = sources.add("test.bit", src.to_string());
+    let file = sources.get(id);
+    let module = Parser::new(file.source(), Lexer::new(file)).parse();
+    let functions = module
+        .items
+        .iter()
+        .filter_map(|item| match &item.kind {
+            ItemKind::Function(f) => Some(f),
+            _ => None,
+        })
+        .collect::<Vec<&Function>>();
+    CallGraph::build(&functions).sccs()
+}
+
+#[test]
+fn callees_come_before_callers() {
+    let sccs = sccs(
+        r#"
+fn main() { a() }
+fn a() { b() + c() }
+fn b() { c() }
+fn c() { 1 }
+"#,
+    );
+    assert_eq!(sccs, [vec![3], vec![2], vec![1], vec![0]]);
+}
+
+#[test]
+fn mutually_recursive_functions_share_component() {
+    let sccs = sccs(
+        r#"
+fn main() { odd(3) }
+fn even(n: Int) { if n == 0 { true } else { odd(n - 1) } }
+fn odd(n: Int) { if n == 0 { false } else { even(n - 1) } }
+fn unrelated() { unrelated() }
+"#,
+    );
+    assert_eq!(sccs, [vec![1, 2], vec![0], vec![3]]);
+}
+
+#[test]
+fn shadowed_names_are_not_references() {
+    let sccs = sccs(
+        r#"
+fn a(b: Int) { let c = b; c }
+fn b() { a(1) }
+fn c() { 2 }
+"#,
+    );
+    assert_eq!(sccs, [vec![0], vec![1], vec![2]]);
+}
@@ -0,0 +1,278 @@
+/// Imports
+use lexer::Lexer;
+use parser::Parser;
+use tir::{def::FnDef, ty::TyKind};
+use tycheck::{
+    check::ModuleTyck,
+    cx::{icx::InferCx, tcx::TyCx},
+    errors::TypeckError,
+};
+
+/// Checks source and returns the context with reported errors, without lints
+fn check(src: &str) -> (TyCx, Vec<TypeckError>) {
+    let mut tcx = TyCx::default();
+    let file = tcx.sources.add("test.bit", src.to_string());
+    let file = tcx.sources.get(file);
+    let module = Parser::new(file.source(), Lexer::new(file)).parse();
+
+    let mut icx = InferCx::new(&mut tcx);
+    let mut tyck = ModuleTyck::new(&mut icx);
+    tyck.check_module(module);
+    let diags = tyck
+        .take_diagnostics()
+        .into_iter()
+        .filter(|diag| !matches!(diag, TypeckError::Lint(_)))
+        .collect();
+    (tcx, diags)
+}
+
+/// Returns definition of the function by its name
+fn function<'tcx>(tcx: &'tcx TyCx, name: &str) -> &'tcx FnDef {
+    tcx.functions
+        .iter()
+        .map(|(_, f)| f)
+        .find(|f| f.name == name)
+        .unwrap_or_else(|| panic!("no function `{name}`"))
+}
+
+#[test]
+fn let_bound_closure_is_generalized() {
+    let (_, diags) = check(
+        r#"
+fn main() {
+    let id = |x| x;
+    id(1);
+    id("s");
+}
+"#,
+    );
+    assert!(diags.is_empty(), "{diags:?}");
+}
+
+#[test]
+fn non_value_binding_stays_monomorphic() {
+    let (_, diags) = check(
+        r#"
+fn make<T>() -> fn(T) -> T {
+    |x| x
+}
+
+fn main() {
+    let f = make();
+    f(1);
+    f("s");
+}
+"#,
+    );
+    assert!(
+        matches!(diags.as_slice(), [TypeckError::TypeMismatch { .. }]),
+        "{diags:?}"
+    );
+}
+
+#[test]
+fn unannotated_fn_is_generalized() {
+    let (tcx, diags) = check(
+        r#"
+fn identity() {
+    |x| x
+}
+
+fn main() {
+    identity()(1);
+    identity()("s");
+}
+"#,
+    );
+    assert!(diags.is_empty(), "{diags:?}");
+    assert_eq!(function(&tcx, "identity").generics.len(), 1);
+}
+
+#[test]
+fn generated_generics_do_not_clash_with_declared() {
+    let (tcx, diags) = check(
+        r#"
+fn pair<T1>(a: T1) {
+    |x| x
+}
+
+fn main() {}
+"#,
+    );
+    assert!(diags.is_empty(), "{diags:?}");
+    let generics = &function(&tcx, "pair").generics;
+    assert_eq!(generics.len(), 2);
+    assert_ne!(generics[0], generics[1]);
+}
+
+#[test]
+fn forward_call_uses_inferred_return_type() {
+    let (tcx, diags) = check(
+        r#"
+fn main() {
+    let a: Int = area(2, 3);
+}
+
+fn area(w: Int, h: Int) {
+    w * h
+}
+"#,
+    );
+    assert!(diags.is_empty(), "{diags:?}");
+    assert_eq!(tcx.kind(function(&tcx, "area").ret), &TyKind::Int);
+}
+
+#[test]
+fn mutually_recursive_functions_are_inferred_together() {
+    let (tcx, diags) = check(
+        r#"
+fn even(n: Int) {
+    if n == 0 { true } else { odd(n - 1) }
+}
+
+fn odd(n: Int) {
+    if n == 0 { false } else { even(n - 1) }
+}
+
+fn main() {
+    let a: Bool = even(4);
+}
+"#,
+    );
+    assert!(diags.is_empty(), "{diags:?}");
+    assert_eq!(tcx.kind(function(&tcx, "even").ret), &TyKind::Bool);
+    assert_eq!(tcx.kind(function(&tcx, "odd").ret), &TyKind::Bool);
+}
+
+#[test]
+fn functions_of_group_are_generalized_separately() {
+    let (tcx, diags) = check(
+        r#"
+fn wrap(n: Int) {
+    if n == 0 { |x| x } else { let _s = size(n); |x| x }
+}
+
+fn size(n: Int) {
+    if n == 0 { 1 } else { wrap(n - 1); size(n - 1) }
+}
+
+fn main() {
+    wrap(1)(1);
+    wrap(1)("s");
+}
+"#,
+    );
+    // `size` leaves the variable of `wrap` unconstrained
+    let [TypeckError::AnnotationsNeeded { what, .. }] = diags.as_slice() else {
+        panic!("{diags:?}");
+    };
+    assert_eq!(what, "can't infer type of this expression");
+    assert_eq!(function(&tcx, "wrap").generics.len(), 1);
+    assert!(function(&tcx, "size").generics.is_empty());
+}
+
+#[test]
+fn group_sharing_return_variable_is_generalized_per_function() {
+    let (tcx, diags) = check(
+        r#"
+fn ping(n: Int) {
+    pong(n)
+}
+
+fn pong(n: Int) {
+    ping(n)
+}
+
+fn main() {}
+"#,
+    );
+    assert!(diags.is_empty(), "{diags:?}");
+    for name in ["ping", "pong"] {
+        let f = function(&tcx, name);
+        assert_eq!(f.generics.len(), 1, "{name}");
+        assert!(matches!(tcx.kind(f.ret), TyKind::Generic(_)), "{name}");
+    }
+}
+
+#[test]
+fn return_type_needed_suggests_inferred_type() {
+    let (_, diags) = check(
+        r#"
+struct House { rooms: Int }
+
+fn rooms(n: Int) {
+    house(n).rooms
+}
+
+fn house(n: Int) {
+    if n == 0 { House(1) } else { House(rooms(n - 1)) }
+}
+
+fn main() {}
+"#,
+    );
+    let [TypeckError::ReturnTypeNeeded { name, help, .. }] = diags.as_slice() else {
+        panic!("{diags:?}");
+    };
+    assert_eq!(name, "house");
+    assert_eq!(help, "add return type annotation: `fn house(..) -> House`");
+}
+
+#[test]
+fn closure_params_are_inferred_from_annotation() {
+    let (_, diags) = check(
+        r#"
+struct Point { x: Int, y: Int }
+
+fn main() {
+    let inc: fn(Int) -> Int = |x| x + 1;
+    let get: fn(Point) -> Int = |p| p.x;
+    inc(get(Point(1, 2)));
+}
+"#,
+    );
+    assert!(diags.is_empty(), "{diags:?}");
+}
+
+#[test]
+fn expected_type_is_pushed_into_branches_and_blocks() {
+    let src = r#"
+struct Point { x: Int, y: Int }
+
+fn main() {
+    let c = true;
+    let f: fn(Point) -> Int = if c { |p| p.x } else { |p| p.y };
+    let g: fn(Point) -> Int = if c {
+        let k = 1;
+        |p| p.x + k
+    } else if !c {
+        |p| p.y
+    } else {
+        f
+    };
+    let a: Int = if c { 1 } else { "s" };
+}
+"#;
+    let (_, diags) = check(src);
+    let [TypeckError::TypeMismatch { span, .. }] = diags.as_slice() else {
+        panic!("{diags:?}");
+    };
+    let literal = src.find("\"s\"").unwrap();
+    assert!((span.offset()..span.offset() + span.len()).contains(&(literal + 1)));
+}
+
+#[test]
+fn annotated_let_checks_literal() {
+    let src = r#"
+fn main() {
+    let a: Int = 1;
+    let b: String = 2;
+}
+"#;
+    let (_, diags) = check(src);
+    let [TypeckError::TypeMismatch { span, t1, t2, .. }] = diags.as_slice() else {
+        panic!("{diags:?}");
+    };
+    assert_eq!(span.offset(), src.find('2').unwrap());
+    assert_eq!((t1.as_str(), t2.as_str()), ("`String`", "`Int`"));
+}
@@ -0,0 +1,70 @@
+/// Imports
+use common::{symbol::Symbol, token::Span};
+use id_arena::Id;
+use tir::{
+    def::{AdtDef, StructDef},
+    ty::TyMeta,
+};
+use tycheck::cx::{icx::InferCx, tcx::TyCx};
+
+/// Defines struct `House` in the file, returns its id
+fn house(tcx: &mut TyCx, file: &str) -> Id<AdtDef> {
+    let file = tcx.sources.add(file, String::new());
+    tcx.insert_adt(AdtDef::Struct(StructDef {
+        docs: None,
+        span: Span(file, 0..0),
+        name: Symbol::intern("House"),
+        generics: Vec::new(),
+        fields: Vec::new(),
+    }))
+}
+
+#[test]
+fn colliding_adt_names_are_qualified() {
+    let mut tcx = TyCx::default();
+    let geo = house(&mut tcx, "geo.bit");
+    let city = house(&mut tcx, "city.bit");
+    let meta = tcx.mk_meta(TyMeta::Adt(city));
+    let (geo, city) = (tcx.mk_adt(geo, Vec::new()), tcx.mk_adt(city, Vec::new()));
+    let int = tcx.types.int;
+    let both = tcx.mk_fn_ref(vec![geo, city], int);
+    let icx = InferCx::new(&mut tcx);
+
+    // Single ADT is not qualified
+    assert_eq!(icx.pretty(geo), "House");
+    assert_eq!(icx.describe(meta), "type `House`");
+
+    // ADTs of the same diagnostic are qualified
+    assert_eq!(icx.pretty(both), "fn(geo.House, city.House) -> Int");
+    let mut printer = icx.printer(&[geo, meta]);
+    assert_eq!(printer.print(geo), "geo.House");
+    assert_eq!(printer.describe(meta), "type `city.House`");
+}
+
+#[test]
+fn inference_variables_are_numbered_by_appearance() {
+    let mut tcx = TyCx::default();
+    let (t0, t1) = (tcx.mk_generic(0), tcx.mk_generic(1));
+    let sig = tcx.mk_fn_ref(vec![t1, t0, t1], t0);
+    let params = (tcx.mk_param(0), tcx.mk_param(27));
+    let mut icx = InferCx::new(&mut tcx);
+    let args = icx.fresh_generics(2);
+    let ty = icx.instantiate(sig, &args);
+
+    // Variables are named in order of their first appearance
+    let mut printer = icx.printer(&[ty, args[0]]);
+    assert_eq!(printer.print(ty), "fn(?T1, ?T2, ?T1) -> ?T2");
+    assert_eq!(printer.print(args[0]), "?T2");
+    assert_eq!(printer.print(args[1]), "?T1");
+
+    // Every printer numbers its own variables
+    assert_eq!(icx.pretty(args[0]), "?T1");
+
+    // Unified variables share the name
+    icx.unify(args[0], args[1]).unwrap();
+    assert_eq!(icx.pretty(ty), "fn(?T1, ?T1, ?T1) -> ?T1");
+
+    // Let-bound scheme params are named by letters
+    assert_eq!(icx.pretty(params.0), "'a");
+    assert_eq!(icx.pretty(params.1), "'b1");
+}
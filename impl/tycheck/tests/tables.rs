@@ -0,0 +1,194 @@
+/// Imports
+use ast::{
+    expr::Expr,
+    item::Module,
+    stmt::Stmt,
+    visit::{self, Visitor},
+};
+use common::id::HirId;
+use lexer::Lexer;
+use parser::Parser;
+use std::collections::HashSet;
+use tir::{
+    def::ItemDefKind,
+    results::{Adjustment, Resolution},
+    ty::TyKind,
+};
+use tycheck::{
+    check::ModuleTyck,
+    cx::{icx::InferCx, tcx::TyCx},
+};
+
+/// Source, used by the tests
+const SRC: &str = r#"
+struct Point {
+    x: Int,
+    y: Int
+}
+
+fn id<T>(x: T) -> T {
+    x
+}
+
+fn main() {
+    let p = id(Point(1, 2));
+    let same = |v| v;
+    let _n = same(p.x) + same(p.y);
+}
+"#;
+
+/// Parses and checks the source, returns checked module and the context
+fn check() -> (Module, TyCx) {
+    let mut tcx = TyCx::default();
+    let file = tcx.sources.add("test.bit", SRC.to_string());
+    let file = tcx.sources.get(file);
+    let module = Parser::new(file.source(), Lexer::new(file)).parse();
+
+    let mut icx = InferCx::new(&mut tcx);
+    let mut tyck = ModuleTyck::new(&mut icx);
+    tyck.check_module(module.clone());
+    let diags = tyck.take_diagnostics();
+    assert!(diags.is_empty(), "{diags:?}");
+    (module, tcx)
+}
+
+/// Collects ids of every node and source texts of the expressions
+#[derive(Default)]
+struct Nodes {
+    ids: Vec<HirId>,
+    exprs: Vec<(String, HirId)>,
+}
+
+/// Implementation
+impl Nodes {
+    /// Returns id of the expression with the source text
+    fn expr(&self, text: &str) -> HirId {
+        self.exprs
+            .iter()
+            .find(|(it, _)| it == text)
+            .map(|(_, id)| *id)
+            .unwrap_or_else(|| panic!("no expression `{text}`"))
+    }
+}
+
+/// Visitor implementation
+impl<'ast> Visitor<'ast> for Nodes {
+    fn visit_item(&mut self, item: &'ast ast::item::Item) {
+        self.ids.push(item.id);
+        visit::walk_item(self, item);
+    }
+
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        self.ids.push(stmt.id);
+        visit::walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        self.ids.push(expr.id);
+        self.exprs
+            .push((SRC[expr.span.1.clone()].to_string(), expr.id));
+        visit::walk_expr(self, expr);
+    }
+}
+
+/// Collects nodes of the module
+fn nodes(module: &Module) -> Nodes {
+    let mut nodes = Nodes::default();
+    nodes.visit_module(module);
+    nodes
+}
+
+#[test]
+fn parser_assigns_unique_ids() {
+    let (module, _) = check();
+    let nodes = nodes(&module);
+    let unique = nodes.ids.iter().collect::<HashSet<_>>();
+    assert_eq!(unique.len(), nodes.ids.len());
+    assert!(nodes.ids.len() > 20);
+}
+
+#[test]
+fn node_types_are_recorded() {
+    let (module, tcx) = check();
+    let nodes = nodes(&module);
+    let ty = |text| tcx.kind(tcx.results.node_type(nodes.expr(text)).unwrap());
+
+    assert!(matches!(ty("id(Point(1, 2))"), TyKind::Adt(..)));
+    assert_eq!(ty("p.x"), &TyKind::Int);
+    assert_eq!(ty("same(p.x) + same(p.y)"), &TyKind::Int);
+    assert!(matches!(ty("|v| v"), TyKind::FnRef(_)));
+}
+
+#[test]
+fn quantified_vars_become_scheme_params() {
+    let (module, tcx) = check();
+    let nodes = nodes(&module);
+    let ty = |text| tcx.kind(tcx.results.node_type(nodes.expr(text)).unwrap());
+
+    let param = tcx.kind(tcx.results.node_type(nodes.expr("v")).unwrap());
+    assert_eq!(param, &TyKind::Param(0));
+    let TyKind::FnRef(sig) = ty("|v| v") else {
+        panic!("expected function pointer");
+    };
+    assert_eq!(tcx.kind(sig.params[0]), &TyKind::Param(0));
+    assert_eq!(tcx.kind(sig.ret), &TyKind::Param(0));
+}
+
+#[test]
+fn resolutions_are_recorded() {
+    let (module, tcx) = check();
+    let nodes = nodes(&module);
+    let res = |text| tcx.results.resolution(nodes.expr(text)).unwrap();
+
+    // Function and structure
+    let Resolution::Item(def) = res("id") else {
+        panic!("expected item");
+    };
+    let ItemDefKind::Fn(function) = def.kind else {
+        panic!("expected function");
+    };
+    assert_eq!(tcx._fn(function).name, "id");
+    assert!(matches!(res("Point"), Resolution::Item(_)));
+
+    // Local, resolved to its binding
+    let Resolution::Local(span) = res("p") else {
+        panic!("expected local");
+    };
+    assert_eq!(&SRC[span.1.clone()], "p");
+
+    // Field
+    let Resolution::Field(adt, name) = res("p.x") else {
+        panic!("expected field");
+    };
+    assert_eq!(tcx.adt(*adt).as_struct().name, "Point");
+    assert_eq!(*name, "x");
+}
+
+#[test]
+fn instantiations_are_recorded() {
+    let (module, tcx) = check();
+    let nodes = nodes(&module);
+    let args = |id| match tcx.results.adjustment(id) {
+        Some(Adjustment::Instantiate(args)) => args
+            .iter()
+            .map(|arg| tcx.kind(*arg).clone())
+            .collect::<Vec<_>>(),
+        None => Vec::new(),
+    };
+
+    // Generic function
+    let id = nodes.expr("id");
+    assert!(matches!(args(id).as_slice(), [TyKind::Adt(..)]));
+
+    // Generalized closure
+    let same = nodes
+        .exprs
+        .iter()
+        .filter(|(text, _)| text == "same")
+        .map(|(_, id)| args(*id))
+        .collect::<Vec<_>>();
+    assert_eq!(same, [[TyKind::Int], [TyKind::Int]]);
+
+    // Monomorphic reference
+    assert!(tcx.results.adjustment(nodes.expr("p")).is_none());
+}
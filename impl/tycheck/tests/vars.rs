@@ -0,0 +1,68 @@
+/// Imports
+use tir::ty::TyVar;
+use tycheck::cx::{tcx::TyCx, vars::VarStore};
+
+#[test]
+fn union_keeps_smallest_level() {
+    let mut store = VarStore::default();
+    let a = store.new_var(TyVar::Unbound(2));
+    let b = store.new_var(TyVar::Unbound(1));
+    store.union(a, b);
+    assert_eq!(store.find(a), store.find(b));
+    assert_eq!(store.value(a), &TyVar::Unbound(1));
+}
+
+#[test]
+fn rollback_reverts_unions_and_bindings() {
+    let int = TyCx::default().types.int;
+    let mut store = VarStore::default();
+    let a = store.new_var(TyVar::Unbound(0));
+    let b = store.new_var(TyVar::Unbound(0));
+    let c = store.new_var(TyVar::Unbound(0));
+    store.union(a, b);
+
+    let snapshot = store.snapshot();
+    store.union(b, c);
+    store.set_value(c, TyVar::Bound(int));
+    assert_eq!(store.value(a), &TyVar::Bound(int));
+    store.rollback_to(snapshot);
+
+    assert_eq!(store.find(a), store.find(b));
+    assert_ne!(store.find(a), store.find(c));
+    assert_eq!(store.value(a), &TyVar::Unbound(0));
+    assert_eq!(store.value(c), &TyVar::Unbound(0));
+}
+
+#[test]
+fn commit_keeps_changes() {
+    let mut store = VarStore::default();
+    let a = store.new_var(TyVar::Unbound(0));
+    let b = store.new_var(TyVar::Unbound(0));
+
+    let snapshot = store.snapshot();
+    store.union(a, b);
+    store.commit(snapshot);
+
+    assert_eq!(store.find(a), store.find(b));
+}
+
+#[test]
+fn rollback_reverts_compressed_paths() {
+    let mut store = VarStore::default();
+    let a = store.new_var(TyVar::Unbound(0));
+    let b = store.new_var(TyVar::Unbound(0));
+    let c = store.new_var(TyVar::Unbound(0));
+    let d = store.new_var(TyVar::Unbound(0));
+    store.union(a, b);
+    store.union(c, d);
+
+    // `d` is compressed through the union, that is rolled back
+    let snapshot = store.snapshot();
+    store.union(a, c);
+    assert_eq!(store.find(d), store.find(a));
+    store.rollback_to(snapshot);
+
+    assert_eq!(store.find(d), store.find(c));
+    assert_ne!(store.find(d), store.find(a));
+    assert_eq!(store.find(b), store.find(a));
+}
@@ -0,0 +1,184 @@
+/// Imports
+use ast::{
+    expr::{Expr, ExprKind, Lit},
+    item::Module,
+    visit::{self as ast_visit, Folder, Visitor as AstVisitor, VisitorMut},
+};
+use common::symbol::Symbol;
+use lexer::Lexer;
+use parser::Parser;
+use tir::{
+    ty::{Ty, TyKind},
+    visit::Visitor as TirVisitor,
+};
+use tycheck::{
+    check::ModuleTyck,
+    cx::{icx::InferCx, tcx::TyCx},
+};
+
+/// Source, used by the tests
+const SRC: &str = r#"
+struct Point {
+    x: Int,
+    y: Int
+}
+
+fn dist(p: Point, f: fn(Int) -> Int) -> Int {
+    f(p.x) + f(p.y)
+}
+
+fn main() {
+    let p = Point(1, 2);
+    let d = dist(p, |v| v * v);
+    let _s = "distance is {d}";
+    if d > 2 { d } else { 0 };
+}
+"#;
+
+/// Parses the source
+fn parse(tcx: &mut TyCx) -> Module {
+    let file = tcx.sources.add("test.bit", SRC.to_string());
+    let file = tcx.sources.get(file);
+    Parser::new(file.source(), Lexer::new(file)).parse()
+}
+
+/// Collects names of the called functions and the type hints
+#[derive(Default)]
+struct Names<'ast> {
+    calls: Vec<&'ast str>,
+    hints: Vec<&'ast str>,
+}
+
+/// Visitor implementation
+impl<'ast> AstVisitor<'ast> for Names<'ast> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let ExprKind::Call(what, _) = &expr.kind
+            && let ExprKind::Id(name) = &what.kind
+        {
+            self.calls.push(name.as_str());
+        }
+        ast_visit::walk_expr(self, expr);
+    }
+
+    fn visit_type_hint(&mut self, hint: &'ast ast::atom::TypeHint) {
+        if let ast::atom::TypeHint::Local { name, .. } = hint {
+            self.hints.push(name.as_str());
+        }
+        ast_visit::walk_type_hint(self, hint);
+    }
+}
+
+#[test]
+fn ast_visitor_reaches_nested_nodes() {
+    let module = parse(&mut TyCx::default());
+    let mut names = Names::default();
+    names.visit_module(&module);
+
+    assert_eq!(names.calls, ["f", "f", "Point", "dist"]);
+    assert_eq!(names.hints, ["Int", "Int", "Point", "Int", "Int", "Int"]);
+}
+
+/// Replaces number literals with zero
+struct Zeroes;
+
+/// Visitor implementation
+impl VisitorMut for Zeroes {
+    fn visit_expr_mut(&mut self, expr: &mut Expr) {
+        if let ExprKind::Lit(Lit::Number(number)) = &mut expr.kind {
+            *number = "0".to_string();
+        }
+        ast_visit::walk_expr_mut(self, expr);
+    }
+}
+
+/// Renames variables, keeping their spans
+struct Rename<'a>(&'a str, &'a str);
+
+/// Folder implementation
+impl Folder for Rename<'_> {
+    fn fold_expr(&mut self, expr: Expr) -> Expr {
+        match expr.kind {
+            ExprKind::Id(name) if name == self.0 => Expr {
+                id: expr.id,
+                kind: ExprKind::Id(Symbol::intern(self.1)),
+                span: expr.span,
+            },
+            _ => ast_visit::fold_expr(self, expr),
+        }
+    }
+}
+
+/// Collects numbers and identifiers
+#[derive(Default)]
+struct Leaves(Vec<String>);
+
+/// Visitor implementation
+impl<'ast> AstVisitor<'ast> for Leaves {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match &expr.kind {
+            ExprKind::Lit(Lit::Number(it)) => self.0.push(it.clone()),
+            ExprKind::Id(it) => self.0.push(it.to_string()),
+            _ => ast_visit::walk_expr(self, expr),
+        }
+    }
+}
+
+#[test]
+fn ast_visitor_mut_and_folder_rewrite_nodes() {
+    let mut module = parse(&mut TyCx::default());
+    Zeroes.visit_module_mut(&mut module);
+    let module = Rename("d", "distance").fold_module(module);
+
+    let mut leaves = Leaves::default();
+    leaves.visit_module(&module);
+    assert_eq!(
+        leaves.0,
+        [
+            "f", "p", "f", "p", "Point", "0", "0", "dist", "p", "v", "v", "distance", "distance",
+            "0", "distance", "0"
+        ]
+    );
+}
+
+/// Counts visited types by their kinds
+struct Types<'tcx> {
+    tcx: &'tcx TyCx,
+    ints: usize,
+    functions: usize,
+    total: usize,
+}
+
+/// Visitor implementation
+impl<'tir> TirVisitor<'tir> for Types<'_> {
+    fn visit_ty(&mut self, ty: Ty) {
+        self.total += 1;
+        match self.tcx.kind(ty) {
+            TyKind::Int => self.ints += 1,
+            TyKind::FnDef(..) | TyKind::FnRef(_) => self.functions += 1,
+            _ => {}
+        }
+    }
+}
+
+#[test]
+fn tir_visitor_visits_every_type() {
+    let mut tcx = TyCx::default();
+    let module = parse(&mut tcx);
+    let mut icx = InferCx::new(&mut tcx);
+    let mut tyck = ModuleTyck::new(&mut icx);
+    let module = tyck.check_module(module);
+    let diags = tyck.take_diagnostics();
+    assert!(diags.is_empty(), "{diags:?}");
+
+    let mut types = Types {
+        tcx: &tcx,
+        ints: 0,
+        functions: 0,
+        total: 0,
+    };
+    types.visit_module(&module);
+
+    assert!(types.total > types.ints + types.functions);
+    assert!(types.ints > 10);
+    assert!(types.functions >= 3);
+}